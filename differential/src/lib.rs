@@ -0,0 +1,149 @@
+//! Helpers for differential tests of `p256-cm4` against the RustCrypto [`p256`] crate.
+//!
+//! The tests in this crate run natively with the portable backend, or with the
+//! assembly under `qemu-arm` user-mode emulation when built for an ARM Linux target.
+//!
+//! Each test runs [`ITERATIONS`] random inputs through both implementations, besides its edge
+//! cases, so `keygen`, `sign`, `verify`, `ecdh_calc_shared_secret`, `scalarmult_generic` and
+//! `octet_string_to_point` each see 1000 to 3000 random inputs per run.  Every test prints its
+//! seed, the number of iterations and the backend it ran against.
+
+use p256::{
+    AffinePoint, EncodedPoint, FieldBytes, NonZeroScalar, PublicKey,
+    elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint},
+};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+/// Number of random inputs each test runs through both implementations.
+pub const ITERATIONS: usize = 1_000;
+
+/// The backend of `p256-cm4` the tests run against, the assembly on ARM and the portable rust
+/// implementation elsewhere.
+pub const BACKEND: &str = if cfg!(target_arch = "arm") {
+    "asm"
+} else {
+    "soft"
+};
+
+/// The order of the P256 curve, as little-endian limbs.
+pub const ORDER: [u32; 8] = [
+    0xfc632551, 0xf3b9cac2, 0xa7179e84, 0xbce6faad, 0xffffffff, 0xffffffff, 0x00000000, 0xffffffff,
+];
+
+/// The P256 prime, as little-endian limbs.
+pub const PRIME: [u32; 8] = [
+    0xffffffff, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000000, 0x00000001, 0xffffffff,
+];
+
+/// Create the random number generator for a test.
+///
+/// The seed is taken from the `DIFFERENTIAL_SEED` environment variable if set,
+/// and is printed so that failures can be reproduced.
+pub fn rng() -> StdRng {
+    let seed: u64 = match std::env::var("DIFFERENTIAL_SEED") {
+        Ok(seed) => seed.parse().expect("DIFFERENTIAL_SEED must be a u64"),
+        Err(_) => rand::random(),
+    };
+    println!("DIFFERENTIAL_SEED={seed} ITERATIONS={ITERATIONS} BACKEND={BACKEND}");
+    StdRng::seed_from_u64(seed)
+}
+
+/// Convert a big-endian integer to little-endian limbs.
+pub fn limbs_from_be(bytes: &[u8; 32]) -> [u32; 8] {
+    core::array::from_fn(|i| {
+        let start: usize = 28 - 4 * i;
+        u32::from_be_bytes(bytes[start..start + 4].try_into().unwrap())
+    })
+}
+
+/// Convert little-endian limbs to a big-endian integer.
+pub fn limbs_to_be(limbs: &[u32; 8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    limbs.iter().enumerate().for_each(|(i, limb)| {
+        let start: usize = 28 - 4 * i;
+        out[start..start + 4].copy_from_slice(&limb.to_be_bytes());
+    });
+    out
+}
+
+/// `a + b mod 2^256`, for building edge cases around `n` and `p`.
+pub fn add_small(a: &[u32; 8], b: i64) -> [u32; 8] {
+    let mut out = [0u32; 8];
+    let mut carry: i64 = b;
+    (0..8).for_each(|i| {
+        let t: i64 = i64::from(a[i]) + carry;
+        out[i] = t as u32;
+        carry = t >> 32;
+    });
+    out
+}
+
+/// A uniformly random 256-bit integer, which may be outside of `1..=n-1`.
+pub fn random_limbs(rng: &mut StdRng) -> [u32; 8] {
+    core::array::from_fn(|_| rng.next_u32())
+}
+
+/// A random scalar in `1..=n-1`.
+pub fn random_scalar(rng: &mut StdRng) -> [u32; 8] {
+    loop {
+        let candidate: [u32; 8] = random_limbs(rng);
+        if p256_cm4::check_range_n(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+/// Scalars that are likely to hit special cases: zero, the boundaries of `1..=n-1`,
+/// values just outside of the range, and values with long runs of zero or one bits.
+pub fn edge_case_scalars() -> Vec<[u32; 8]> {
+    let mut scalars: Vec<[u32; 8]> = vec![
+        [0; 8],
+        [1, 0, 0, 0, 0, 0, 0, 0],
+        [2, 0, 0, 0, 0, 0, 0, 0],
+        [3, 0, 0, 0, 0, 0, 0, 0],
+        [0xffffffff, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 1, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0x80000000],
+        [0xffffffff; 8],
+        [0x55555555; 8],
+        [0xaaaaaaaa; 8],
+        ORDER,
+        PRIME,
+    ];
+    scalars.extend((-3..=3).map(|delta| add_small(&ORDER, delta)));
+    scalars.extend((-2..=2).map(|delta| add_small(&PRIME, delta)));
+    scalars
+}
+
+/// Convert little-endian limbs to a RustCrypto scalar, if they are in `1..=n-1`.
+pub fn to_nonzero_scalar(limbs: &[u32; 8]) -> Option<NonZeroScalar> {
+    NonZeroScalar::from_repr(FieldBytes::from(limbs_to_be(limbs))).into()
+}
+
+/// Convert little-endian affine coordinates to a RustCrypto public key, if the point is valid.
+pub fn to_public_key(x: &[u32; 8], y: &[u32; 8]) -> Option<PublicKey> {
+    let encoded = EncodedPoint::from_affine_coordinates(
+        &FieldBytes::from(limbs_to_be(x)),
+        &FieldBytes::from(limbs_to_be(y)),
+        false,
+    );
+    PublicKey::from_encoded_point(&encoded).into()
+}
+
+/// Convert a RustCrypto affine point to little-endian coordinates.
+pub fn from_affine(point: &AffinePoint) -> ([u32; 8], [u32; 8]) {
+    let encoded: EncodedPoint = point.to_encoded_point(false);
+    (
+        limbs_from_be(encoded.x().unwrap().as_ref()),
+        limbs_from_be(encoded.y().unwrap().as_ref()),
+    )
+}
+
+/// A random key pair, as (private key, public x, public y).
+pub fn random_keypair(rng: &mut StdRng) -> ([u32; 8], [u32; 8], [u32; 8]) {
+    let private_key: [u32; 8] = random_scalar(rng);
+    let public_key: PublicKey =
+        PublicKey::from_secret_scalar(&to_nonzero_scalar(&private_key).unwrap());
+    let (x, y) = from_affine(public_key.as_affine());
+    (private_key, x, y)
+}
@@ -0,0 +1,345 @@
+//! Differential tests of the RustCrypto `elliptic-curve` arithmetic trait implementations.
+
+use differential::{
+    ITERATIONS, ORDER, PRIME, add_small, edge_case_scalars, limbs_to_be, random_keypair,
+    random_limbs, rng,
+};
+use hex_literal::hex;
+use p256::elliptic_curve::{
+    Field, PrimeField,
+    bigint::U256,
+    ecdh::diffie_hellman,
+    group::{Group, GroupEncoding},
+    ops::{MulByGenerator, Reduce},
+    scalar::IsHigh,
+    sec1::ToEncodedPoint,
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq},
+};
+use rand::{RngCore, rngs::StdRng};
+
+/// Random scalars reduced modulo `n`, followed by the reduced edge cases.
+fn scalars(rng: &mut StdRng) -> Vec<(p256_cm4::Scalar, p256::Scalar)> {
+    (0..ITERATIONS)
+        .map(|_| random_limbs(rng))
+        .chain(edge_case_scalars())
+        .map(|limbs| {
+            let bytes = limbs_to_be(&limbs).into();
+            (
+                <p256_cm4::Scalar as Reduce<_>>::reduce_bytes(&bytes),
+                <p256::Scalar as Reduce<_>>::reduce_bytes(&bytes),
+            )
+        })
+        .collect()
+}
+
+/// Random field elements, with both implementations rejecting the same unreduced inputs.
+fn field_elements(rng: &mut StdRng) -> Vec<(p256_cm4::FieldElement, p256::FieldElement)> {
+    (0..ITERATIONS)
+        .map(|_| random_limbs(rng))
+        .chain(edge_case_scalars())
+        .filter_map(|limbs| {
+            let bytes = limbs_to_be(&limbs).into();
+            let ours: Option<p256_cm4::FieldElement> =
+                p256_cm4::FieldElement::from_repr(bytes).into();
+            let expected: Option<p256::FieldElement> = p256::FieldElement::from_repr(bytes).into();
+            assert_eq!(ours.is_some(), expected.is_some(), "{limbs:08x?}");
+            ours.zip(expected)
+        })
+        .collect()
+}
+
+/// Random points, as `k*G` for the scalars from [`scalars`].
+fn points(rng: &mut StdRng) -> Vec<(p256_cm4::ProjectivePoint, p256::ProjectivePoint)> {
+    scalars(rng)
+        .into_iter()
+        .map(|(a, b)| {
+            (
+                p256_cm4::ProjectivePoint::mul_by_generator(&a),
+                p256::ProjectivePoint::mul_by_generator(&b),
+            )
+        })
+        .collect()
+}
+
+fn assert_points_eq(ours: &p256_cm4::ProjectivePoint, expected: &p256::ProjectivePoint) {
+    assert_eq!(
+        ours.to_affine().to_encoded_point(false).as_bytes(),
+        expected.to_affine().to_encoded_point(false).as_bytes()
+    );
+}
+
+#[test]
+fn scalar_arithmetic() {
+    let mut rng: StdRng = rng();
+    let scalars = scalars(&mut rng);
+    scalars.windows(2).for_each(|pair| {
+        let [(a, a_ref), (b, b_ref)] = pair else {
+            unreachable!()
+        };
+        assert_eq!((a + b).to_repr(), (a_ref + b_ref).to_repr());
+        assert_eq!((a - b).to_repr(), (a_ref - b_ref).to_repr());
+        assert_eq!((a * b).to_repr(), (a_ref * b_ref).to_repr());
+        assert_eq!((-a).to_repr(), (-*a_ref).to_repr());
+        assert_eq!(a.square().to_repr(), a_ref.square().to_repr());
+        assert_eq!(a.double().to_repr(), a_ref.double().to_repr());
+        assert_eq!(
+            Option::from(a.invert()).map(|s: p256_cm4::Scalar| s.to_repr()),
+            Option::from(a_ref.invert()).map(|s: p256::Scalar| s.to_repr())
+        );
+        assert_eq!(
+            Option::from(a.sqrt()).map(|s: p256_cm4::Scalar| s.to_repr()),
+            Option::from(a_ref.sqrt()).map(|s: p256::Scalar| s.to_repr())
+        );
+        assert_eq!(bool::from(a.is_high()), bool::from(a_ref.is_high()));
+        assert_eq!(bool::from(a.is_odd()), bool::from(a_ref.is_odd()));
+        assert_eq!(a < b, a_ref < b_ref);
+    });
+}
+
+#[test]
+fn scalar_inherent_api() {
+    type S = p256_cm4::Scalar;
+    // 2^256 mod n
+    let r: p256::Scalar = <p256::Scalar as Reduce<U256>>::reduce(U256::MAX) + p256::Scalar::ONE;
+
+    let mut rng: StdRng = rng();
+    let mut wide = [0u8; 64];
+    (0..ITERATIONS).for_each(|i| {
+        match i {
+            0 => wide.fill(0xff),
+            1 => wide.fill(0),
+            _ => rng.fill_bytes(&mut wide),
+        }
+        let (high, low) = wide.split_at(32);
+        let high = <p256::Scalar as Reduce<U256>>::reduce_bytes(high.into());
+        let low = <p256::Scalar as Reduce<U256>>::reduce_bytes(low.into());
+        let a: S = S::from_wide_bytes(&wide);
+        assert_eq!(a.to_repr(), (high * r + low).to_repr());
+        let b: S = S::from_bytes_reduced(wide[..32].try_into().unwrap());
+        assert_eq!(b.to_repr(), high.to_repr());
+
+        // without the operator and trait implementations
+        assert_eq!(S::add(&a, &b), a + b);
+        assert_eq!(S::sub(&a, &b), a - b);
+        assert_eq!(S::multiply(&a, &b), a * b);
+        assert_eq!(S::neg(&a), -a);
+        assert_eq!(S::square(&a), a * a);
+        assert_eq!(S::double(&a), a + a);
+        if let Some(inverse) = Option::<S>::from(S::invert(&a)) {
+            assert_eq!(inverse * a, S::ONE);
+        }
+        assert_eq!(S::from_limbs(&a.to_limbs()).unwrap(), a);
+    });
+
+    assert!(bool::from(S::invert(&S::ZERO).is_none()));
+    assert_eq!(S::neg(&S::ZERO), S::ZERO);
+    assert_eq!(S::from_bytes(&[0; 32]).unwrap(), S::ZERO);
+    assert!(bool::from(S::from_limbs(&ORDER).is_none()));
+    assert_eq!(
+        S::from_bytes_reduced(&limbs_to_be(&add_small(&ORDER, 5))),
+        S::from(5)
+    );
+}
+
+#[test]
+fn field_arithmetic() {
+    let mut rng: StdRng = rng();
+    let elements = field_elements(&mut rng);
+    elements.windows(2).for_each(|pair| {
+        let [(a, a_ref), (b, b_ref)] = pair else {
+            unreachable!()
+        };
+        assert_eq!((a + b).to_repr(), (a_ref + b_ref).to_repr());
+        assert_eq!((a - b).to_repr(), (a_ref - b_ref).to_repr());
+        assert_eq!((a * b).to_repr(), (a_ref * b_ref).to_repr());
+        assert_eq!((-a).to_repr(), (-*a_ref).to_repr());
+        assert_eq!(a.square().to_repr(), a_ref.square().to_repr());
+        assert_eq!(a.double().to_repr(), a_ref.double().to_repr());
+        assert_eq!(
+            Option::from(a.invert()).map(|s: p256_cm4::FieldElement| s.to_repr()),
+            Option::from(a_ref.invert()).map(|s: p256::FieldElement| s.to_repr())
+        );
+        // the two implementations may return either square root
+        let sqrt: Option<p256_cm4::FieldElement> = a.sqrt().into();
+        let sqrt_ref: Option<p256::FieldElement> = a_ref.sqrt().into();
+        assert_eq!(sqrt.is_some(), sqrt_ref.is_some());
+        if let Some(sqrt) = sqrt {
+            assert_eq!(sqrt.square(), *a);
+        }
+        assert_eq!(bool::from(a.is_odd()), bool::from(a_ref.is_odd()));
+    });
+}
+
+#[test]
+fn field_inherent_api() {
+    type Fe = p256_cm4::FieldElement;
+    let b: Fe = Fe::from_bytes(&hex!(
+        "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"
+    ))
+    .unwrap();
+    let three: Fe = Fe::from(3);
+
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        let x: Fe = Fe::from_limbs(&x).unwrap();
+        let y: Fe = Fe::from_limbs(&y).unwrap();
+
+        // y^2 = x^3 - 3x + b, without the operator and trait implementations
+        let rhs: Fe = Fe::add(&Fe::sub(&x.square().multiply(&x), &three.multiply(&x)), &b);
+        assert_eq!(Fe::square(&y), rhs);
+        let root: Fe = Fe::sqrt(&rhs).unwrap();
+        assert!(root == y || root == Fe::neg(&y));
+        assert_eq!(Fe::invert(&y).unwrap().multiply(&y), Fe::ONE);
+        assert_eq!(Fe::double(&x), x + x);
+
+        assert_eq!(Fe::from_limbs(&x.to_limbs()).unwrap(), x);
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(0)), x);
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(1)), y);
+        assert!(bool::from(x.ct_eq(&x)));
+        assert!(!bool::from(x.ct_eq(&y)));
+    });
+
+    assert!(bool::from(Fe::invert(&Fe::ZERO).is_none()));
+    assert!(bool::from(Fe::from_limbs(&PRIME).is_none()));
+    assert_eq!(
+        Fe::from_limbs(&add_small(&PRIME, -1)).unwrap(),
+        Fe::neg(&Fe::ONE)
+    );
+}
+
+#[test]
+fn point_arithmetic() {
+    let mut rng: StdRng = rng();
+    let points = points(&mut rng);
+    points.windows(2).for_each(|pair| {
+        let [(p, p_ref), (q, q_ref)] = pair else {
+            unreachable!()
+        };
+        assert_points_eq(p, p_ref);
+        assert_points_eq(&(p + q), &(p_ref + q_ref));
+        assert_points_eq(&(p - q), &(p_ref - q_ref));
+        assert_points_eq(&(p + q.to_affine()), &(*p_ref + q_ref.to_affine()));
+        assert_points_eq(&(p - q.to_affine()), &(*p_ref - q_ref.to_affine()));
+        assert_points_eq(&p.double(), &p_ref.double());
+        assert_points_eq(&(p + p), &p_ref.double());
+        assert_points_eq(&-p, &-p_ref);
+        assert!(bool::from((p - p).is_identity()));
+        assert_eq!(p + q, q + p);
+        assert_eq!(p + p, p.double());
+        assert_eq!(p + p256_cm4::ProjectivePoint::IDENTITY, *p);
+        assert_eq!(p256_cm4::ProjectivePoint::IDENTITY + p, *p);
+    });
+}
+
+#[test]
+fn point_inherent_api() {
+    type P = p256_cm4::ProjectivePoint;
+    type A = p256_cm4::AffinePoint;
+    let mut rng: StdRng = rng();
+    let points = points(&mut rng);
+    points.windows(2).for_each(|pair| {
+        let [(p, p_ref), (q, q_ref)] = pair else {
+            unreachable!()
+        };
+        // without the operator and trait implementations
+        assert_points_eq(&P::add(p, q), &(p_ref + q_ref));
+        assert_points_eq(&P::sub(p, q), &(p_ref - q_ref));
+        assert_points_eq(&P::add_affine(p, &P::to_affine(q)), &(p_ref + q_ref));
+        assert_points_eq(&P::sub_affine(p, &P::to_affine(q)), &(p_ref - q_ref));
+        assert_points_eq(&P::double(p), &p_ref.double());
+        assert_points_eq(&P::neg(p), &-p_ref);
+        assert_eq!(P::from(A::neg(&P::to_affine(p))), P::neg(p));
+
+        assert_eq!(P::add(&P::IDENTITY, p), *p);
+        assert_eq!(P::add(p, &P::IDENTITY), *p);
+        assert_eq!(P::add_affine(&P::IDENTITY, &P::to_affine(p)), *p);
+        assert_eq!(P::add_affine(p, &A::IDENTITY), *p);
+        assert!(bool::from(P::is_identity(&P::sub(p, p))));
+        assert!(bool::from(P::is_identity(&P::sub_affine(
+            p,
+            &P::to_affine(p)
+        ))));
+        assert_eq!(P::add(p, p), P::double(p));
+
+        // the edge cases include the identity
+        if let Some((x, y)) = P::to_affine(p).coordinates().into_option() {
+            assert_eq!(A::from_coordinates(&x, &y).unwrap(), P::to_affine(p));
+            assert!(bool::from(A::from_coordinates(&y, &x).is_none()));
+        }
+    });
+
+    assert!(bool::from(A::IDENTITY.coordinates().is_none()));
+    assert!(bool::from(P::to_affine(&P::IDENTITY).is_identity()));
+    assert_eq!(A::neg(&A::IDENTITY), A::IDENTITY);
+    assert_eq!(P::double(&P::IDENTITY), P::IDENTITY);
+}
+
+#[test]
+fn scalar_multiplication() {
+    let mut rng: StdRng = rng();
+    let points = points(&mut rng);
+    let scalars = scalars(&mut rng);
+    points
+        .iter()
+        .chain([&(
+            p256_cm4::ProjectivePoint::IDENTITY,
+            p256::ProjectivePoint::IDENTITY,
+        )])
+        .zip(scalars.iter().rev())
+        .for_each(|((p, p_ref), (k, k_ref))| {
+            assert_points_eq(&(p * k), &(p_ref * k_ref));
+            assert_points_eq(
+                &p256_cm4::ProjectivePoint::mul_by_generator(k),
+                &p256::ProjectivePoint::mul_by_generator(k_ref),
+            );
+        });
+}
+
+#[test]
+fn group_encoding() {
+    let mut rng: StdRng = rng();
+    let points = points(&mut rng);
+    points
+        .iter()
+        .chain([&(
+            p256_cm4::ProjectivePoint::IDENTITY,
+            p256::ProjectivePoint::IDENTITY,
+        )])
+        .for_each(|(p, p_ref)| {
+            let bytes = p.to_bytes();
+            assert_eq!(bytes[..], p_ref.to_bytes()[..]);
+            let decoded: p256_cm4::ProjectivePoint =
+                Option::from(p256_cm4::ProjectivePoint::from_bytes(&bytes)).unwrap();
+            assert_eq!(decoded, *p);
+        });
+}
+
+#[test]
+fn generic_ecdh() {
+    let mut rng: StdRng = rng();
+    let scalars = scalars(&mut rng);
+    scalars.windows(2).for_each(|pair| {
+        let [(a, a_ref), (b, b_ref)] = pair else {
+            unreachable!()
+        };
+        let (Some(a), Some(a_ref)) = (
+            p256::elliptic_curve::NonZeroScalar::new(*a).into_option(),
+            p256::NonZeroScalar::new(*a_ref).into_option(),
+        ) else {
+            return;
+        };
+        let public = p256_cm4::ProjectivePoint::mul_by_generator(b).to_affine();
+        let public_ref = p256::ProjectivePoint::mul_by_generator(b_ref).to_affine();
+        if bool::from(public_ref.is_identity()) {
+            return;
+        }
+
+        let shared = diffie_hellman::<p256_cm4::NistP256>(a, public);
+        let expected = diffie_hellman::<p256::NistP256>(a_ref, public_ref);
+        assert_eq!(
+            shared.raw_secret_bytes()[..],
+            expected.raw_secret_bytes()[..]
+        );
+    });
+}
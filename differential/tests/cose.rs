@@ -0,0 +1,326 @@
+//! Differential tests of `COSE_Key` and ES256 `COSE_Sign1`, with the `Sig_structure` built by
+//! hand and the signatures checked by the reference.
+
+use differential::{ITERATIONS, PRIME, limbs_to_be, random_keypair, random_scalar, rng};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use rand::{Rng, RngCore, rngs::StdRng};
+use signature::{Signer, Verifier};
+
+// CBOR head of the given major type, with the shortest argument.
+fn head(major: u8, value: usize) -> Vec<u8> {
+    match value {
+        0..=23 => vec![(major << 5) | value as u8],
+        24..=0xff => vec![(major << 5) | 24, value as u8],
+        _ => [&[(major << 5) | 25][..], &(value as u16).to_be_bytes()].concat(),
+    }
+}
+
+fn bstr(data: &[u8]) -> Vec<u8> {
+    [head(2, data.len()), data.to_vec()].concat()
+}
+
+fn sig_structure(protected: &[u8], external_aad: &[u8], payload: &[u8]) -> Vec<u8> {
+    [
+        vec![0x84],
+        head(3, 10),
+        b"Signature1".to_vec(),
+        bstr(protected),
+        bstr(external_aad),
+        bstr(payload),
+    ]
+    .concat()
+}
+
+fn random_bytes(rng: &mut StdRng, max_len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![0; rng.gen_range(0..=max_len)];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn cose_key() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        let (x_bytes, y_bytes) = (limbs_to_be(&x), limbs_to_be(&y));
+
+        let mut cose_key = [0u8; p256_cm4::COSE_KEY_LEN];
+        p256_cm4::point_to_cose_key(&mut cose_key, &x, &y);
+        let expected: Vec<u8> = [
+            &[0xa4, 0x01, 0x02, 0x20, 0x01, 0x21][..],
+            &bstr(&x_bytes),
+            &[0x22],
+            &bstr(&y_bytes),
+        ]
+        .concat();
+        assert_eq!(cose_key.as_slice(), expected);
+
+        let mut x_out = [0u32; 8];
+        let mut y_out = [0u32; 8];
+        assert!(p256_cm4::cose_key_to_point(
+            &mut x_out, &mut y_out, &cose_key
+        ));
+        assert_eq!((x_out, y_out), (x, y));
+
+        let public_key = p256_cm4::PublicKey::from_affine(&x, &y).unwrap();
+        assert_eq!(public_key.to_cose_key(), cose_key);
+        assert_eq!(
+            p256_cm4::PublicKey::from_cose_key(&cose_key),
+            Ok(public_key)
+        );
+
+        // compressed point, other member order, the algorithm, and additional parameters
+        let sign_bit: u8 = if y[0] & 1 == 1 { 0xf5 } else { 0xf4 };
+        let compressed: Vec<u8> = [
+            &[0xa7, 0x22, sign_bit, 0x03, 0x26, 0x02][..],
+            &bstr(b"kid"),
+            &[0x63],
+            b"foo",
+            &[0x82, 0x01, 0x80, 0x21],
+            &bstr(&x_bytes),
+            &[0x01, 0x02, 0x20, 0x01],
+        ]
+        .concat();
+        assert_eq!(
+            p256_cm4::PublicKey::from_cose_key(&compressed),
+            Ok(public_key)
+        );
+    });
+}
+
+#[test]
+fn sign1() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, x, y) = random_keypair(&mut rng);
+        let k: [u32; 8] = random_scalar(&mut rng);
+        let payload: Vec<u8> = random_bytes(&mut rng, 300);
+        let external_aad: Vec<u8> = random_bytes(&mut rng, 40);
+        let verifying_key = VerifyingKey::from_sec1_bytes(
+            &[&[0x04][..], &limbs_to_be(&x), &limbs_to_be(&y)].concat(),
+        )
+        .unwrap();
+
+        let mut message: Vec<u8> = vec![0; p256_cm4::cose_sign1_len(payload.len())];
+        assert!(p256_cm4::cose_sign1(
+            &mut message,
+            &payload,
+            &external_aad,
+            &private_key,
+            &k
+        ));
+        let prefix: Vec<u8> = [
+            &[0xd2, 0x84, 0x43, 0xa1, 0x01, 0x26, 0xa0][..],
+            &bstr(&payload),
+            &[0x58, 0x40],
+        ]
+        .concat();
+        assert_eq!(&message[..prefix.len()], prefix);
+        let signature = Signature::from_slice(&message[prefix.len()..]).unwrap();
+        let tbs: Vec<u8> = sig_structure(&[0xa1, 0x01, 0x26], &external_aad, &payload);
+        assert!(verifying_key.verify(&tbs, &signature).is_ok());
+
+        assert_eq!(
+            p256_cm4::try_cose_sign1_verify(&x, &y, &message, &external_aad),
+            Ok(payload.as_slice())
+        );
+        let mut other_aad: Vec<u8> = external_aad.clone();
+        other_aad.push(0);
+        assert_eq!(
+            p256_cm4::try_cose_sign1_verify(&x, &y, &message, &other_aad),
+            Err(p256_cm4::Error::InvalidSignature)
+        );
+
+        let secret_key = p256_cm4::SecretKey::from_limbs(&private_key).unwrap();
+        let mut typed: Vec<u8> = vec![0; message.len() + 3];
+        assert_eq!(
+            secret_key.cose_sign1(&mut typed, &payload, &external_aad, &k),
+            Ok(message.len())
+        );
+        assert_eq!(&typed[..message.len()], message);
+        assert_eq!(
+            secret_key
+                .public_key()
+                .verify_cose_sign1(&message, &external_aad),
+            Ok(payload.as_slice())
+        );
+
+        // an untagged message signed by the reference, with the algorithm in the unprotected
+        // header and an empty protected header
+        let signing_key = SigningKey::from_slice(&limbs_to_be(&private_key)).unwrap();
+        let tbs: Vec<u8> = sig_structure(&[], &external_aad, &payload);
+        let signature: Signature = signing_key.sign(&tbs);
+        let message: Vec<u8> = [
+            &[0x84, 0x40, 0xa2, 0x01, 0x26, 0x04][..],
+            &bstr(b"kid"),
+            &bstr(&payload),
+            &bstr(&signature.to_bytes()),
+        ]
+        .concat();
+        assert_eq!(
+            p256_cm4::try_cose_sign1_verify(&x, &y, &message, &external_aad),
+            Ok(payload.as_slice())
+        );
+    });
+}
+
+#[test]
+fn rejects() {
+    let mut rng: StdRng = rng();
+    let (private_key, x, y) = random_keypair(&mut rng);
+    let (_, other_x, other_y) = random_keypair(&mut rng);
+    let (x_bytes, y_bytes) = (limbs_to_be(&x), limbs_to_be(&y));
+    let cose_key = |kty: u8, crv: u8, x: &[u8], y: &[u8]| -> Vec<u8> {
+        [
+            &[0xa4, 0x01, kty, 0x20, crv, 0x21][..],
+            &bstr(x),
+            &[0x22],
+            &bstr(y),
+        ]
+        .concat()
+    };
+    let valid: Vec<u8> = cose_key(0x02, 0x01, &x_bytes, &y_bytes);
+
+    let invalid: [Vec<u8>; 12] = [
+        Vec::new(),
+        vec![0xa0],
+        cose_key(0x01, 0x01, &x_bytes, &y_bytes),
+        cose_key(0x02, 0x02, &x_bytes, &y_bytes),
+        cose_key(0x02, 0x01, &x_bytes[1..], &y_bytes),
+        cose_key(0x02, 0x01, &x_bytes, &[0; 33]),
+        [&valid[..], &[0x00]].concat(),
+        valid[..valid.len() - 1].to_vec(),
+        // a duplicate curve, and ECDH-ES as the algorithm
+        [&[0xa5, 0x20, 0x01], &valid[1..]].concat(),
+        [&[0xa5, 0x03, 0x38, 0x18], &valid[1..]].concat(),
+        // indefinite length map, and nesting beyond the limit
+        [&[0xbf], &valid[1..], &[0xff]].concat(),
+        [
+            &[0xa5, 0x63],
+            &b"foo"[..],
+            &[0x81; 10],
+            &[0x80],
+            &valid[1..],
+        ]
+        .concat(),
+    ];
+    invalid.iter().for_each(|cose_key| {
+        assert_eq!(
+            p256_cm4::PublicKey::from_cose_key(cose_key),
+            Err(p256_cm4::Error::InvalidCose),
+            "{cose_key:02x?}"
+        );
+    });
+
+    let mut x_out = [1u32; 8];
+    let mut y_out = [1u32; 8];
+    [
+        (
+            cose_key(0x02, 0x01, &x_bytes, &limbs_to_be(&other_y)),
+            p256_cm4::Error::NotOnCurve,
+        ),
+        (
+            cose_key(0x02, 0x01, &limbs_to_be(&PRIME), &y_bytes),
+            p256_cm4::Error::CoordinateOutOfRange,
+        ),
+    ]
+    .iter()
+    .for_each(|(cose_key, error)| {
+        assert_eq!(
+            p256_cm4::try_cose_key_to_point(&mut x_out, &mut y_out, cose_key),
+            Err(*error)
+        );
+        assert_eq!((x_out, y_out), ([0; 8], [0; 8]));
+    });
+
+    let k: [u32; 8] = random_scalar(&mut rng);
+    let payload: &[u8] = b"This is the content.";
+    let mut message = [0u8; 7 + 1 + 20 + 2 + 64];
+    assert_eq!(p256_cm4::cose_sign1_len(payload.len()), message.len());
+    assert_eq!(
+        p256_cm4::try_cose_sign1(
+            &mut message[..7 + 1 + 20 + 2 + 63],
+            payload,
+            &[],
+            &private_key,
+            &k
+        ),
+        Err(p256_cm4::Error::BufferTooSmall)
+    );
+    assert_eq!(
+        p256_cm4::try_cose_sign1(&mut message, payload, &[], &private_key, &[0; 8]),
+        Err(p256_cm4::Error::ScalarOutOfRange)
+    );
+    assert_eq!(message, [0; 7 + 1 + 20 + 2 + 64]);
+    assert!(p256_cm4::cose_sign1(
+        &mut message,
+        payload,
+        &[],
+        &private_key,
+        &k
+    ));
+    assert!(p256_cm4::cose_sign1_verify(&x, &y, &message, &[]));
+    assert!(!p256_cm4::cose_sign1_verify(
+        &other_x,
+        &other_y,
+        &message,
+        &[]
+    ));
+
+    let replace = |at: usize, bytes: &[u8]| -> Vec<u8> {
+        [&message[..at], bytes, &message[at + 1..]].concat()
+    };
+    let signature_at: usize = message.len() - 64;
+    let invalid: [Vec<u8>; 9] = [
+        // another tag, and trailing data
+        replace(0, &[0xd1]),
+        [&message[..], &[0x00]].concat(),
+        // a truncated signature
+        [
+            &message[..signature_at - 2],
+            &[0x58, 0x3f],
+            &message[signature_at + 1..],
+        ]
+        .concat(),
+        // ES384, a second algorithm, and no algorithm
+        [
+            &message[..2],
+            &[0x44, 0xa1, 0x01, 0x38, 0x22],
+            &message[6..],
+        ]
+        .concat(),
+        replace(6, &[0xa1, 0x01, 0x26]),
+        [&message[..2], &[0x40], &message[6..]].concat(),
+        // critical headers
+        replace(6, &[0xa1, 0x02, 0x81, 0x04]),
+        // the protected header with trailing data
+        [
+            &message[..2],
+            &[0x44, 0xa1, 0x01, 0x26, 0x00],
+            &message[6..],
+        ]
+        .concat(),
+        // a detached payload
+        [&message[..7], &[0xf6], &message[7 + 1 + 20..]].concat(),
+    ];
+    invalid.iter().for_each(|message| {
+        assert_eq!(
+            p256_cm4::try_cose_sign1_verify(&x, &y, message, &[]),
+            Err(p256_cm4::Error::InvalidCose),
+            "{message:02x?}"
+        );
+    });
+
+    let mut modified = message;
+    modified[7 + 1] ^= 1;
+    assert_eq!(
+        p256_cm4::try_cose_sign1_verify(&x, &y, &modified, &[]),
+        Err(p256_cm4::Error::InvalidSignature)
+    );
+    let mut modified = message;
+    modified[signature_at..signature_at + 32].fill(0);
+    assert_eq!(
+        p256_cm4::try_cose_sign1_verify(&x, &y, &modified, &[]),
+        Err(p256_cm4::Error::SignatureOutOfRange)
+    );
+}
@@ -0,0 +1,154 @@
+//! Differential tests of the DER and fixed-size `r || s` signature encodings.
+
+use differential::{ITERATIONS, ORDER, add_small, limbs_from_be, limbs_to_be, random_scalar, rng};
+use p256::ecdsa::Signature;
+use rand::{Rng, rngs::StdRng};
+
+fn reference_from_der(der: &[u8]) -> Option<([u32; 8], [u32; 8])> {
+    let signature = Signature::from_der(der).ok()?;
+    let (r, s) = signature.split_bytes();
+    Some((limbs_from_be(&r.into()), limbs_from_be(&s.into())))
+}
+
+fn check_encode(r: &[u32; 8], s: &[u32; 8]) {
+    let mut der = [0u8; p256_cm4::MAX_DER_SIGNATURE_LEN];
+    let len: usize = p256_cm4::signature_to_der(&mut der, r, s);
+    let expected = Signature::from_scalars(limbs_to_be(r), limbs_to_be(s))
+        .unwrap()
+        .to_der();
+    assert_eq!(&der[..len], expected.as_bytes());
+
+    let signature = p256_cm4::Signature::from_scalars(r, s).unwrap();
+    assert_eq!(signature.to_der().as_bytes(), expected.as_bytes());
+    assert_eq!(p256_cm4::Signature::from_der(&der[..len]), Ok(signature));
+}
+
+fn check_decode(der: &[u8]) {
+    let mut r = [0u32; 8];
+    let mut s = [0u32; 8];
+    let ok: bool = p256_cm4::signature_from_der(&mut r, &mut s, der);
+    match reference_from_der(der) {
+        Some(expected) => {
+            assert!(ok, "rejected {der:02x?}");
+            assert_eq!((r, s), expected, "mismatch for {der:02x?}");
+        }
+        None => {
+            assert!(!ok, "accepted {der:02x?}");
+            assert_eq!((r, s), ([0; 8], [0; 8]));
+        }
+    }
+}
+
+#[test]
+fn encode() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        check_encode(&random_scalar(&mut rng), &random_scalar(&mut rng));
+    });
+
+    // minimal and maximal lengths, and values with and without the most significant bit set
+    let edge_cases: [[u32; 8]; 6] = [
+        [1, 0, 0, 0, 0, 0, 0, 0],
+        [0x80, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0x00800000],
+        [0, 0, 0, 0, 0, 0, 0, 0x7fffffff],
+        [0, 0, 0, 0, 0, 0, 0, 0x80000000],
+        add_small(&ORDER, -1),
+    ];
+    edge_cases.iter().for_each(|r| {
+        edge_cases.iter().for_each(|s| check_encode(r, s));
+    });
+}
+
+#[test]
+fn decode_mutated() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let mut der = [0u8; p256_cm4::MAX_DER_SIGNATURE_LEN];
+        let len: usize = p256_cm4::signature_to_der(
+            &mut der,
+            &random_scalar(&mut rng),
+            &random_scalar(&mut rng),
+        );
+        let mut der: Vec<u8> = der[..len].to_vec();
+        check_decode(&der);
+
+        match rng.gen_range(0..4) {
+            0 => {
+                let i: usize = rng.gen_range(0..der.len());
+                der[i] = rng.r#gen();
+            }
+            1 => der.truncate(rng.gen_range(0..der.len())),
+            2 => der.push(rng.r#gen()),
+            _ => {
+                // a non-minimal leading zero
+                der.insert(4, 0);
+                der[1] += 1;
+                der[3] += 1;
+            }
+        }
+        check_decode(&der);
+    });
+}
+
+#[test]
+fn decode_rejects() {
+    let n: Vec<u8> = [&[0u8][..], &limbs_to_be(&ORDER)].concat();
+    let cases: [&[u8]; 12] = [
+        &[],
+        &[0x30, 0x00],
+        &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+        &[0x30, 0x81, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+        &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+        &[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+        &[0x30, 0x06, 0x03, 0x01, 0x01, 0x02, 0x01, 0x01],
+        &[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+        &[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01],
+        &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+        &[0x30, 0x05, 0x02, 0x00, 0x02, 0x01, 0x01],
+        &[
+            0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01,
+        ],
+    ];
+    cases.iter().for_each(|der| check_decode(der));
+    check_decode(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]);
+
+    let mut der: Vec<u8> = vec![0x30, 0x26, 0x02, 0x21];
+    der.extend(&n);
+    der.extend([0x02, 0x01, 0x01]);
+    check_decode(&der);
+    assert_eq!(
+        p256_cm4::Signature::from_der(&der),
+        Err(p256_cm4::Error::SignatureOutOfRange)
+    );
+}
+
+#[test]
+fn fixed_size_bytes() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let r: [u32; 8] = random_scalar(&mut rng);
+        let s: [u32; 8] = random_scalar(&mut rng);
+        let expected = Signature::from_scalars(limbs_to_be(&r), limbs_to_be(&s)).unwrap();
+
+        let signature = p256_cm4::Signature::from_scalars(&r, &s).unwrap();
+        let bytes: [u8; 64] = signature.to_bytes();
+        assert_eq!(bytes, <[u8; 64]>::from(expected.to_bytes()));
+        assert_eq!(p256_cm4::Signature::from_bytes(&bytes), Ok(signature));
+    });
+
+    let valid: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+    [[0; 8], ORDER, add_small(&ORDER, 1), [0xffffffff; 8]]
+        .iter()
+        .for_each(|invalid| {
+            let invalid: [u8; 32] = limbs_to_be(invalid);
+            [[valid, invalid], [invalid, valid]]
+                .iter()
+                .for_each(|halves| {
+                    assert_eq!(
+                        p256_cm4::Signature::from_bytes(&halves.concat().try_into().unwrap()),
+                        Err(p256_cm4::Error::SignatureOutOfRange)
+                    );
+                });
+        });
+}
@@ -0,0 +1,90 @@
+//! Differential tests of ECDH and generic scalar multiplication.
+
+use differential::{
+    ITERATIONS, PRIME, add_small, edge_case_scalars, from_affine, random_keypair, random_limbs,
+    random_scalar, rng, to_nonzero_scalar, to_public_key,
+};
+use p256::{AffinePoint, ProjectivePoint, ecdh::diffie_hellman};
+use rand::{Rng, rngs::StdRng};
+
+fn check_ecdh(private_key: &[u32; 8], x: &[u32; 8], y: &[u32; 8]) {
+    let mut shared_secret = [0u8; 32];
+    let ok: bool = p256_cm4::ecdh_calc_shared_secret(&mut shared_secret, private_key, x, y);
+    match to_public_key(x, y) {
+        Some(public_key) => {
+            assert!(ok, "ecdh rejected x={x:08x?} y={y:08x?}");
+            let expected = diffie_hellman(
+                to_nonzero_scalar(private_key).unwrap(),
+                public_key.as_affine(),
+            );
+            assert_eq!(
+                &shared_secret[..],
+                &expected.raw_secret_bytes()[..],
+                "ecdh mismatch"
+            );
+        }
+        None => assert!(!ok, "ecdh accepted x={x:08x?} y={y:08x?}"),
+    }
+}
+
+#[test]
+fn ecdh() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_ecdh(&random_scalar(&mut rng), &x, &y);
+    });
+}
+
+#[test]
+fn ecdh_invalid_public_key() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, mut x, mut y) = random_keypair(&mut rng);
+        match rng.gen_range(0..4) {
+            0 => x = random_limbs(&mut rng),
+            1 => y = random_limbs(&mut rng),
+            2 => y[0] ^= 1,
+            _ => x = add_small(&PRIME, rng.gen_range(0..3)),
+        }
+        check_ecdh(&random_scalar(&mut rng), &x, &y);
+    });
+    // the point at infinity has no affine encoding, the all-zero coordinates are not on the curve
+    check_ecdh(&random_scalar(&mut rng), &[0; 8], &[0; 8]);
+}
+
+fn check_scalarmult(scalar: &[u32; 8], x: &[u32; 8], y: &[u32; 8]) {
+    let mut result_x = [0u32; 8];
+    let mut result_y = [0u32; 8];
+    let ok: bool = p256_cm4::scalarmult_generic(&mut result_x, &mut result_y, scalar, x, y);
+    match (to_nonzero_scalar(scalar), to_public_key(x, y)) {
+        (Some(k), Some(point)) => {
+            assert!(ok, "scalarmult_generic rejected scalar={scalar:08x?}");
+            let expected: AffinePoint = (ProjectivePoint::from(*point.as_affine()) * *k).into();
+            assert_eq!(
+                (result_x, result_y),
+                from_affine(&expected),
+                "scalarmult_generic mismatch"
+            );
+        }
+        _ => assert!(!ok, "scalarmult_generic accepted scalar={scalar:08x?}"),
+    }
+}
+
+#[test]
+fn scalarmult_generic() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_scalarmult(&random_scalar(&mut rng), &x, &y);
+    });
+    // 2 and n-2 take a separate path in the variable base scalar multiplication
+    edge_case_scalars().iter().for_each(|scalar| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_scalarmult(scalar, &x, &y);
+    });
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_scalarmult(&random_limbs(&mut rng), &x, &y);
+    });
+}
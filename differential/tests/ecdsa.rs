@@ -0,0 +1,465 @@
+//! Differential tests of key generation, signing and verification.
+
+use differential::{
+    ITERATIONS, edge_case_scalars, from_affine, limbs_from_be, limbs_to_be, random_keypair,
+    random_limbs, random_scalar, rng, to_nonzero_scalar, to_public_key,
+};
+use ecdsa::hazmat::{bits2field, sign_prehashed, verify_prehashed};
+use p256::{FieldBytes, NistP256, ProjectivePoint, PublicKey, Scalar, ecdsa::Signature};
+use rand::{Rng, RngCore, rngs::StdRng};
+use std::time::{Duration, Instant};
+
+fn reference_keygen(private_key: &[u32; 8]) -> Option<([u32; 8], [u32; 8])> {
+    let scalar = to_nonzero_scalar(private_key)?;
+    Some(from_affine(
+        PublicKey::from_secret_scalar(&scalar).as_affine(),
+    ))
+}
+
+fn check_keygen(private_key: &[u32; 8]) {
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    let ok: bool = p256_cm4::keygen(&mut x, &mut y, private_key);
+    match reference_keygen(private_key) {
+        Some(expected) => {
+            assert!(ok, "keygen rejected {private_key:08x?}");
+            assert_eq!((x, y), expected, "keygen mismatch for {private_key:08x?}");
+        }
+        None => assert!(!ok, "keygen accepted {private_key:08x?}"),
+    }
+}
+
+#[test]
+fn keygen() {
+    let mut rng: StdRng = rng();
+    edge_case_scalars().iter().for_each(check_keygen);
+    (0..ITERATIONS).for_each(|_| check_keygen(&random_limbs(&mut rng)));
+    (0..ITERATIONS).for_each(|_| check_keygen(&random_scalar(&mut rng)));
+}
+
+fn random_hash(rng: &mut StdRng) -> Vec<u8> {
+    // RustCrypto rejects hashes shorter than half of the field size.
+    let mut hash: Vec<u8> = vec![0; rng.gen_range(16..=64)];
+    rng.fill_bytes(&mut hash);
+    hash
+}
+
+fn reference_sign(hash: &[u8], private_key: &[u32; 8], k: &[u32; 8]) -> Option<Signature> {
+    let d = to_nonzero_scalar(private_key)?;
+    let k: Scalar = *to_nonzero_scalar(k)?;
+    let z: FieldBytes = bits2field::<NistP256>(hash).unwrap();
+    sign_prehashed::<NistP256, _>(&d, k, &z)
+        .ok()
+        .map(|(sig, _)| sig)
+}
+
+fn check_sign(hash: &[u8], private_key: &[u32; 8], k: &[u32; 8]) {
+    let mut r = [0u32; 8];
+    let mut s = [0u32; 8];
+    let ok: bool = p256_cm4::sign(&mut r, &mut s, hash, private_key, k);
+    match reference_sign(hash, private_key, k) {
+        Some(sig) => {
+            assert!(ok, "sign rejected d={private_key:08x?} k={k:08x?}");
+            assert_eq!(r, limbs_from_be(&sig.r().to_bytes().into()), "r mismatch");
+            assert_eq!(s, limbs_from_be(&sig.s().to_bytes().into()), "s mismatch");
+        }
+        None => assert!(!ok, "sign accepted d={private_key:08x?} k={k:08x?}"),
+    }
+}
+
+#[test]
+fn sign() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let hash: Vec<u8> = random_hash(&mut rng);
+        check_sign(&hash, &random_scalar(&mut rng), &random_scalar(&mut rng));
+    });
+    edge_case_scalars().iter().for_each(|k| {
+        let hash: Vec<u8> = random_hash(&mut rng);
+        check_sign(&hash, &random_scalar(&mut rng), k);
+    });
+    // the private key is not validated by `sign`, it must come from `keygen`
+    edge_case_scalars()
+        .iter()
+        .filter(|private_key| p256_cm4::check_range_n(private_key))
+        .for_each(|private_key| {
+            let hash: Vec<u8> = random_hash(&mut rng);
+            check_sign(&hash, private_key, &random_scalar(&mut rng));
+        });
+}
+
+#[test]
+fn sign_two_step() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let hash: Vec<u8> = random_hash(&mut rng);
+        let private_key: [u32; 8] = random_scalar(&mut rng);
+        let k: [u32; 8] = random_scalar(&mut rng);
+
+        let mut precomp = p256_cm4::SignPrecomp::default();
+        let mut r = [0u32; 8];
+        let mut s = [0u32; 8];
+        assert!(p256_cm4::sign_step1(&mut precomp, &k));
+        assert!(p256_cm4::sign_step2(
+            &mut r,
+            &mut s,
+            &hash,
+            &private_key,
+            &mut precomp
+        ));
+        assert_eq!(precomp.r, [0; 8]);
+        assert_eq!(precomp.k_inv, [0; 8]);
+
+        let sig: Signature = reference_sign(&hash, &private_key, &k).unwrap();
+        assert_eq!(r, limbs_from_be(&sig.r().to_bytes().into()));
+        assert_eq!(s, limbs_from_be(&sig.s().to_bytes().into()));
+    });
+}
+
+#[test]
+fn sign_two_step_failure_zeroizes_precomp() {
+    let mut rng: StdRng = rng();
+    let hash: Vec<u8> = random_hash(&mut rng);
+    let private_key: [u32; 8] = random_scalar(&mut rng);
+    let k: [u32; 8] = random_scalar(&mut rng);
+
+    let mut precomp = p256_cm4::SignPrecomp::default();
+    let mut r = [0u32; 8];
+    let mut s = [0u32; 8];
+    assert!(p256_cm4::sign_step1(&mut precomp, &k));
+    precomp.k_inv = [0; 8];
+    assert_eq!(
+        p256_cm4::try_sign_step2(&mut r, &mut s, &hash, &private_key, &mut precomp),
+        Err(p256_cm4::Error::InvalidPrecomp)
+    );
+    assert_eq!(precomp.r, [0; 8]);
+    assert_eq!(r, [0; 8]);
+    assert_eq!(s, [0; 8]);
+}
+
+fn reference_verify(x: &[u32; 8], y: &[u32; 8], hash: &[u8], r: &[u32; 8], s: &[u32; 8]) -> bool {
+    let Some(public_key) = to_public_key(x, y) else {
+        return false;
+    };
+    let Ok(sig) = Signature::from_scalars(limbs_to_be(r), limbs_to_be(s)) else {
+        return false;
+    };
+    let z: FieldBytes = bits2field::<NistP256>(hash).unwrap();
+    verify_prehashed::<NistP256>(&ProjectivePoint::from(*public_key.as_affine()), &z, &sig).is_ok()
+}
+
+fn check_verify(x: &[u32; 8], y: &[u32; 8], hash: &[u8], r: &[u32; 8], s: &[u32; 8]) -> bool {
+    let expected: bool = reference_verify(x, y, hash, r, s);
+    assert_eq!(
+        p256_cm4::verify(x, y, hash, r, s),
+        expected,
+        "verify mismatch for x={x:08x?} y={y:08x?} r={r:08x?} s={s:08x?}"
+    );
+    match p256_cm4::PreparedPublicKey::new(x, y) {
+        Ok(prepared) => assert_eq!(
+            prepared.verify(hash, r, s),
+            expected,
+            "prepared verify mismatch for x={x:08x?} y={y:08x?} r={r:08x?} s={s:08x?}"
+        ),
+        Err(_) => assert!(
+            to_public_key(x, y).is_none(),
+            "prepare rejected x={x:08x?} y={y:08x?}"
+        ),
+    }
+    expected
+}
+
+#[test]
+fn verify() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, x, y) = random_keypair(&mut rng);
+        let hash: Vec<u8> = random_hash(&mut rng);
+        let sig: Signature = reference_sign(&hash, &private_key, &random_scalar(&mut rng)).unwrap();
+        let r: [u32; 8] = limbs_from_be(&sig.r().to_bytes().into());
+        let s: [u32; 8] = limbs_from_be(&sig.s().to_bytes().into());
+
+        assert!(check_verify(&x, &y, &hash, &r, &s));
+
+        // both s and n - s are valid
+        let s_neg: [u32; 8] = limbs_from_be(&(-*sig.s()).to_bytes().into());
+        assert!(check_verify(&x, &y, &hash, &r, &s_neg));
+
+        // flip a single bit in one of the inputs
+        let mut hash_mut: Vec<u8> = hash.clone();
+        let bit: usize = rng.gen_range(0..hash_mut.len() * 8);
+        hash_mut[bit / 8] ^= 1 << (bit % 8);
+        check_verify(&x, &y, &hash_mut, &r, &s);
+
+        let mut inputs: [[u32; 8]; 4] = [x, y, r, s];
+        let which: usize = rng.gen_range(0..4);
+        let bit: usize = rng.gen_range(0..256);
+        inputs[which][bit / 32] ^= 1 << (bit % 32);
+        let [x_mut, y_mut, r_mut, s_mut] = inputs;
+        assert!(!check_verify(&x_mut, &y_mut, &hash, &r_mut, &s_mut));
+    });
+}
+
+#[test]
+fn verify_edge_cases() {
+    let mut rng: StdRng = rng();
+    let (_, x, y) = random_keypair(&mut rng);
+    let hash: Vec<u8> = random_hash(&mut rng);
+    let edge_cases: Vec<[u32; 8]> = edge_case_scalars();
+    edge_cases.iter().for_each(|r| {
+        edge_cases.iter().for_each(|s| {
+            check_verify(&x, &y, &hash, r, s);
+        });
+        check_verify(&x, &y, &hash, r, &random_limbs(&mut rng));
+        check_verify(&x, &y, &hash, &random_limbs(&mut rng), r);
+        check_verify(
+            r,
+            &y,
+            &hash,
+            &random_scalar(&mut rng),
+            &random_scalar(&mut rng),
+        );
+        check_verify(
+            &x,
+            r,
+            &hash,
+            &random_scalar(&mut rng),
+            &random_scalar(&mut rng),
+        );
+    });
+}
+
+#[test]
+fn verify_prepared() {
+    let mut rng: StdRng = rng();
+    let (private_key, x, y) = random_keypair(&mut rng);
+    let prepared = p256_cm4::PreparedPublicKey::new(&x, &y).unwrap();
+    (0..ITERATIONS).for_each(|_| {
+        let hash: Vec<u8> = random_hash(&mut rng);
+        let sig: Signature = reference_sign(&hash, &private_key, &random_scalar(&mut rng)).unwrap();
+        let r: [u32; 8] = limbs_from_be(&sig.r().to_bytes().into());
+        let mut s: [u32; 8] = limbs_from_be(&sig.s().to_bytes().into());
+        assert_eq!(prepared.try_verify(&hash, &r, &s), Ok(()));
+
+        s[rng.gen_range(0..8)] ^= 1 << rng.gen_range(0..32);
+        assert_eq!(
+            prepared.verify(&hash, &r, &s),
+            reference_verify(&x, &y, &hash, &r, &s)
+        );
+    });
+}
+
+/// The median time of `f` for the inputs.
+fn median_time<T>(inputs: &[T], mut f: impl FnMut(&T) -> bool) -> Duration {
+    let mut times: Vec<Duration> = inputs
+        .iter()
+        .map(|input| {
+            let start = Instant::now();
+            assert!(f(input));
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[times.len() / 2]
+}
+
+#[test]
+fn verify_prepared_timing() {
+    let mut rng: StdRng = rng();
+    let (private_key, x, y) = random_keypair(&mut rng);
+    let prepared = p256_cm4::PreparedPublicKey::new(&x, &y).unwrap();
+    let inputs: Vec<(Vec<u8>, [u32; 8], [u32; 8])> = (0..ITERATIONS)
+        .map(|_| {
+            let hash: Vec<u8> = random_hash(&mut rng);
+            let sig: Signature =
+                reference_sign(&hash, &private_key, &random_scalar(&mut rng)).unwrap();
+            let r: [u32; 8] = limbs_from_be(&sig.r().to_bytes().into());
+            let s: [u32; 8] = limbs_from_be(&sig.s().to_bytes().into());
+            (hash, r, s)
+        })
+        .collect();
+
+    // alternate between both functions in chunks, so that both see the same load of the machine
+    let (mut verify_time, mut prepared_time) = (Duration::ZERO, Duration::ZERO);
+    inputs.chunks(ITERATIONS / 10).for_each(|chunk| {
+        verify_time += median_time(chunk, |(hash, r, s)| p256_cm4::verify(&x, &y, hash, r, s));
+        prepared_time += median_time(chunk, |(hash, r, s)| prepared.verify(hash, r, s));
+    });
+    let ratio: f64 = prepared_time.as_secs_f64() / verify_time.as_secs_f64();
+    println!("PreparedPublicKey::verify takes {ratio:.3} times as long as verify");
+    assert!(
+        ratio < 0.95,
+        "PreparedPublicKey::verify takes {ratio:.3} times as long as verify"
+    );
+}
+
+#[test]
+fn verify_batch() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS / 50).for_each(|_| {
+        let len: usize = rng.gen_range(1..40);
+        let hashes: Vec<Vec<u8>> = (0..len).map(|_| random_hash(&mut rng)).collect();
+        // Some batches are all valid, so that the groups pass the combined check.
+        let modify: bool = rng.gen_bool(0.75);
+        let signatures: Vec<p256_cm4::BatchSignature> = hashes
+            .iter()
+            .map(|hash| {
+                let (private_key, x, y) = random_keypair(&mut rng);
+                let sig: Signature =
+                    reference_sign(hash, &private_key, &random_scalar(&mut rng)).unwrap();
+                let mut signature = p256_cm4::BatchSignature {
+                    public_key_x: x,
+                    public_key_y: y,
+                    hash,
+                    r: limbs_from_be(&sig.r().to_bytes().into()),
+                    s: limbs_from_be(&sig.s().to_bytes().into()),
+                };
+                match if modify { rng.gen_range(0..10) } else { 9 } {
+                    0 => signature.r[rng.gen_range(0..8)] ^= 1 << rng.gen_range(0..32),
+                    1 => {
+                        let edge_cases: Vec<[u32; 8]> = edge_case_scalars();
+                        signature.s = edge_cases[rng.gen_range(0..edge_cases.len())];
+                    }
+                    2 => signature.public_key_y[0] ^= 1,
+                    3 => signature.s[rng.gen_range(0..8)] ^= 1 << rng.gen_range(0..32),
+                    _ => (),
+                }
+                signature
+            })
+            .collect();
+
+        let mut results = vec![Ok(()); len];
+        let mut valid = vec![true; len];
+        let mut scratch = vec![[0u32; 8]; len];
+        let result = p256_cm4::try_verify_batch(&signatures, &mut results, &mut scratch, &mut rng);
+        let all_valid: bool =
+            p256_cm4::verify_batch(&signatures, &mut valid, &mut scratch, &mut rng);
+
+        signatures.iter().enumerate().for_each(|(i, signature)| {
+            let expected = p256_cm4::try_verify(
+                &signature.public_key_x,
+                &signature.public_key_y,
+                signature.hash,
+                &signature.r,
+                &signature.s,
+            );
+            assert_eq!(
+                expected.is_ok(),
+                reference_verify(
+                    &signature.public_key_x,
+                    &signature.public_key_y,
+                    signature.hash,
+                    &signature.r,
+                    &signature.s,
+                )
+            );
+            assert_eq!(results[i], expected, "verify_batch mismatch at {i}");
+            assert_eq!(valid[i], expected.is_ok(), "verify_batch mismatch at {i}");
+        });
+        let first = results
+            .iter()
+            .copied()
+            .find(Result::is_err)
+            .unwrap_or(Ok(()));
+        assert_eq!(result, first);
+        assert_eq!(all_valid, first.is_ok());
+    });
+}
+
+/// The hash, public key `(x, y)` and signature `(r, s)` of a signature.
+type Input = (Vec<u8>, [u32; 8], [u32; 8], [u32; 8], [u32; 8]);
+
+#[test]
+fn verify_batch_timing() {
+    const BATCH_LEN: usize = 32;
+    let mut rng: StdRng = rng();
+    let batches: Vec<Vec<Input>> = (0..ITERATIONS / BATCH_LEN)
+        .map(|_| {
+            (0..BATCH_LEN)
+                .map(|_| {
+                    let (private_key, x, y) = random_keypair(&mut rng);
+                    let hash: Vec<u8> = random_hash(&mut rng);
+                    let sig: Signature =
+                        reference_sign(&hash, &private_key, &random_scalar(&mut rng)).unwrap();
+                    let r: [u32; 8] = limbs_from_be(&sig.r().to_bytes().into());
+                    let s: [u32; 8] = limbs_from_be(&sig.s().to_bytes().into());
+                    (hash, x, y, r, s)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut valid = [false; BATCH_LEN];
+    let mut scratch = [[0u32; 8]; BATCH_LEN];
+    let mut batch_verify = |batch: &Vec<Input>| {
+        let signatures: Vec<p256_cm4::BatchSignature> = batch
+            .iter()
+            .map(|(hash, x, y, r, s)| p256_cm4::BatchSignature {
+                public_key_x: *x,
+                public_key_y: *y,
+                hash,
+                r: *r,
+                s: *s,
+            })
+            .collect();
+        p256_cm4::verify_batch(&signatures, &mut valid, &mut scratch, &mut rng)
+    };
+
+    // alternate between both functions in chunks, so that both see the same load of the machine
+    let (mut verify_time, mut batch_time) = (Duration::ZERO, Duration::ZERO);
+    batches.chunks(batches.len() / 4).for_each(|chunk| {
+        verify_time += median_time(chunk, |batch| {
+            batch
+                .iter()
+                .all(|(hash, x, y, r, s)| p256_cm4::verify(x, y, hash, r, s))
+        });
+        batch_time += median_time(chunk, &mut batch_verify);
+    });
+    let ratio: f64 = batch_time.as_secs_f64() / verify_time.as_secs_f64();
+    println!("verify_batch takes {ratio:.3} times as long as verify for each signature");
+    assert!(
+        ratio < 0.95,
+        "verify_batch takes {ratio:.3} times as long as verify for each signature"
+    );
+}
+
+#[test]
+fn verify_batch_buffer_too_small() {
+    let mut rng: StdRng = rng();
+    let (private_key, x, y) = random_keypair(&mut rng);
+    let hash: Vec<u8> = random_hash(&mut rng);
+    let sig: Signature = reference_sign(&hash, &private_key, &random_scalar(&mut rng)).unwrap();
+    let signature = p256_cm4::BatchSignature {
+        public_key_x: x,
+        public_key_y: y,
+        hash: &hash,
+        r: limbs_from_be(&sig.r().to_bytes().into()),
+        s: limbs_from_be(&sig.s().to_bytes().into()),
+    };
+    let signatures = [signature; 3];
+
+    let mut results = [Ok(()); 3];
+    let mut valid = [true; 3];
+    assert_eq!(
+        p256_cm4::try_verify_batch(&signatures, &mut results, &mut [[0; 8]; 2], &mut rng),
+        Err(p256_cm4::Error::BufferTooSmall)
+    );
+    assert!(!p256_cm4::verify_batch(
+        &signatures,
+        &mut valid,
+        &mut [[0; 8]; 2],
+        &mut rng
+    ));
+    assert_eq!(valid, [false; 3]);
+    assert_eq!(
+        p256_cm4::try_verify_batch(&signatures, &mut results[..2], &mut [[0; 8]; 3], &mut rng),
+        Err(p256_cm4::Error::BufferTooSmall)
+    );
+
+    assert_eq!(
+        p256_cm4::try_verify_batch(&signatures, &mut results, &mut [[0; 8]; 3], &mut rng),
+        Ok(())
+    );
+    assert_eq!(results, [Ok(()); 3]);
+    assert!(p256_cm4::verify_batch(&[], &mut [], &mut [], &mut rng));
+}
@@ -0,0 +1,149 @@
+//! Differential tests of batch inversion and batch conversion to affine coordinates.
+
+use differential::{
+    ITERATIONS, ORDER, PRIME, edge_case_scalars, limbs_from_be, limbs_to_be, random_limbs, rng,
+};
+use p256::elliptic_curve::{
+    PrimeField,
+    group::{Curve, Group},
+};
+use p256_cm4::{Error, FieldElement};
+use rand::{Rng, rngs::StdRng};
+
+/// The inverse with RustCrypto, zero for zero, or `None` if the value is out of range.
+fn invert_ref<F: PrimeField<Repr = p256::FieldBytes>>(limbs: &[u32; 8]) -> Option<[u32; 8]> {
+    let value: Option<F> = F::from_repr(limbs_to_be(limbs).into()).into();
+    let inverse: F = value?.invert().unwrap_or(F::ZERO);
+    Some(limbs_from_be(&inverse.to_repr().into()))
+}
+
+fn check_batch_invert_mod_n(values: &[[u32; 8]]) {
+    let expected: Option<Vec<[u32; 8]>> = values.iter().map(invert_ref::<p256::Scalar>).collect();
+
+    let mut inverses: Vec<[u32; 8]> = values.to_vec();
+    let mut scratch: Vec<[u32; 8]> = vec![[0; 8]; values.len()];
+    let result = p256_cm4::try_batch_invert_mod_n(&mut inverses, &mut scratch);
+    match expected {
+        Some(expected) => {
+            assert_eq!(result, Ok(()), "rejected {} values", values.len());
+            assert_eq!(inverses, expected, "batch_invert_mod_n mismatch");
+        }
+        None => {
+            assert_eq!(result, Err(Error::ScalarOutOfRange));
+            assert_eq!(inverses, values, "modified values on failure");
+        }
+    }
+}
+
+/// Field elements only hold values in range, the others are skipped.
+fn check_batch_invert_mod_p(values: &[[u32; 8]]) {
+    let Some(mut inverses): Option<Vec<FieldElement>> = values
+        .iter()
+        .map(|value| FieldElement::from_limbs(value).into())
+        .collect()
+    else {
+        return;
+    };
+    let expected: Vec<[u32; 8]> = values
+        .iter()
+        .map(|value| invert_ref::<p256::FieldElement>(value).unwrap())
+        .collect();
+
+    let mut scratch: Vec<FieldElement> = vec![FieldElement::ZERO; values.len()];
+    assert_eq!(
+        p256_cm4::try_batch_invert_mod_p(&mut inverses, &mut scratch),
+        Ok(())
+    );
+    let inverses: Vec<[u32; 8]> = inverses.iter().map(FieldElement::to_limbs).collect();
+    assert_eq!(inverses, expected, "batch_invert_mod_p mismatch");
+}
+
+fn check_batch_invert(values: &[[u32; 8]]) {
+    check_batch_invert_mod_n(values);
+    check_batch_invert_mod_p(values);
+}
+
+#[test]
+fn batch_invert() {
+    let mut rng: StdRng = rng();
+    [0, 1, 2, 3, 16, 100].iter().for_each(|&len| {
+        (0..ITERATIONS / 100).for_each(|_| {
+            // random limbs are below p and n with overwhelming probability
+            let values: Vec<[u32; 8]> = (0..len).map(|_| random_limbs(&mut rng)).collect();
+            check_batch_invert(&values);
+        });
+    });
+}
+
+#[test]
+fn batch_invert_edge_cases() {
+    let mut rng: StdRng = rng();
+    // each edge case within random values, including zero and out of range values
+    edge_case_scalars().iter().for_each(|value| {
+        let mut values: Vec<[u32; 8]> = (0..5).map(|_| random_limbs(&mut rng)).collect();
+        values[rng.gen_range(0..5)] = *value;
+        check_batch_invert(&values);
+        check_batch_invert(&[*value]);
+    });
+
+    // only zeros, and the largest values in range
+    check_batch_invert(&[[0; 8]; 4]);
+    let mut n_minus_one: [u32; 8] = ORDER;
+    n_minus_one[0] -= 1;
+    let mut p_minus_one: [u32; 8] = PRIME;
+    p_minus_one[0] -= 1;
+    check_batch_invert(&[n_minus_one, p_minus_one, [1, 0, 0, 0, 0, 0, 0, 0]]);
+}
+
+#[test]
+fn batch_invert_scratch_too_short() {
+    let mut rng: StdRng = rng();
+    let mut values: Vec<[u32; 8]> = (0..3).map(|_| random_limbs(&mut rng)).collect();
+    let mut scratch = [[0u32; 8]; 2];
+    assert_eq!(
+        p256_cm4::try_batch_invert_mod_n(&mut values, &mut scratch),
+        Err(Error::BufferTooSmall)
+    );
+
+    let mut elements = [FieldElement::ONE; 3];
+    let mut scratch = [FieldElement::ZERO; 2];
+    assert_eq!(
+        p256_cm4::try_batch_invert_mod_p(&mut elements, &mut scratch),
+        Err(Error::BufferTooSmall)
+    );
+
+    let mut values = [[1u32, 0, 0, 0, 0, 0, 0, 0]; 2];
+    assert!(!p256_cm4::batch_invert_mod_n(&mut values, &mut []));
+    let mut scratch = [[0u32; 8]; 3];
+    assert!(p256_cm4::batch_invert_mod_n(&mut values, &mut scratch));
+
+    let mut elements = [FieldElement::ONE; 2];
+    assert!(!p256_cm4::batch_invert_mod_p(&mut elements, &mut []));
+    let mut scratch = [FieldElement::ZERO; 3];
+    assert!(p256_cm4::batch_invert_mod_p(&mut elements, &mut scratch));
+}
+
+#[test]
+fn batch_to_affine() {
+    type P = p256_cm4::ProjectivePoint;
+    let mut rng: StdRng = rng();
+    [0, 1, 2, 32, 100].iter().for_each(|&len| {
+        // random points, with non-trivial Z from additions, and some identities
+        let mut points: Vec<P> = (0..len)
+            .map(|_| P::random(&mut rng) + P::random(&mut rng))
+            .collect();
+        if len > 1 {
+            points[0] = P::IDENTITY;
+            points[len - 1] = P::IDENTITY;
+        }
+        let expected: Vec<p256_cm4::AffinePoint> = points.iter().map(P::to_affine).collect();
+
+        let mut affine = vec![p256_cm4::AffinePoint::GENERATOR; len];
+        P::batch_to_affine(&points, &mut affine);
+        assert_eq!(affine, expected);
+
+        let mut affine = vec![p256_cm4::AffinePoint::GENERATOR; len];
+        P::batch_normalize(&points, &mut affine);
+        assert_eq!(affine, expected);
+    });
+}
@@ -0,0 +1,214 @@
+//! Differential tests of the JWK encoding of public and private keys.
+
+use differential::{ITERATIONS, ORDER, PRIME, limbs_to_be, random_keypair, rng, to_public_key};
+use p256::{PublicKey, SecretKey};
+use rand::{Rng, rngs::StdRng};
+
+fn check_decode_public(jwk: &str) {
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    let result = p256_cm4::try_jwk_to_point(&mut x, &mut y, jwk.as_bytes());
+    match PublicKey::from_jwk_str(jwk) {
+        Ok(expected) => {
+            assert!(result.is_ok(), "rejected {jwk}: {result:?}");
+            assert_eq!(to_public_key(&x, &y), Some(expected));
+        }
+        Err(_) => {
+            assert!(result.is_err(), "accepted {jwk}");
+            assert_eq!((x, y), ([0; 8], [0; 8]));
+        }
+    }
+}
+
+fn base64url(limbs: &[u32; 8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let bytes: [u8; 32] = limbs_to_be(limbs);
+    let bits = bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1))
+        .collect::<Vec<u8>>();
+    bits.chunks(6)
+        .map(|chunk| {
+            let value: usize = (0..6).fold(0, |acc, i| {
+                (acc << 1) | usize::from(chunk.get(i).copied().unwrap_or(0))
+            });
+            char::from(ALPHABET[value])
+        })
+        .collect()
+}
+
+#[test]
+fn public_key() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        let reference: PublicKey = to_public_key(&x, &y).unwrap();
+
+        let mut jwk = [0u8; p256_cm4::PUBLIC_KEY_JWK_LEN];
+        p256_cm4::point_to_jwk(&mut jwk, &x, &y);
+        let jwk: &str = core::str::from_utf8(&jwk).unwrap();
+        assert_eq!(jwk, reference.to_jwk_string());
+        check_decode_public(jwk);
+
+        let public_key = p256_cm4::PublicKey::from_affine(&x, &y).unwrap();
+        assert_eq!(public_key.to_jwk().as_slice(), jwk.as_bytes());
+        assert_eq!(
+            p256_cm4::PublicKey::from_jwk(jwk.as_bytes()),
+            Ok(public_key)
+        );
+
+        // member order, whitespace and additional members, which the reference only accepts
+        // when they are strings
+        let reordered: String = format!(
+            "{{ \"use\": \"sig\", \"y\": \"{}\",\n\t\"crv\": \"P-256\", \"key_ops\": [\"verify\"], \
+             \"x\": \"{}\", \"ext\": true, \"kty\": \"EC\", \"kid\": {{\"n\": [1, -2.5e3, null]}} }}",
+            base64url(&y),
+            base64url(&x),
+        );
+        assert_eq!(
+            p256_cm4::PublicKey::from_jwk(reordered.as_bytes()),
+            Ok(public_key)
+        );
+
+        // random modifications of the coordinates
+        let mut modified: Vec<u8> = jwk.as_bytes().to_vec();
+        let i: usize = rng.gen_range(31..31 + 43 + 7 + 43);
+        modified[i] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_+/="
+            [rng.gen_range(0..67)];
+        check_decode_public(core::str::from_utf8(&modified).unwrap());
+    });
+}
+
+#[test]
+fn private_key() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, _, _) = random_keypair(&mut rng);
+        let reference = SecretKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+
+        let mut jwk = [0u8; p256_cm4::PRIVATE_KEY_JWK_LEN];
+        assert!(p256_cm4::private_key_to_jwk(&mut jwk, &private_key));
+        let jwk: &str = core::str::from_utf8(&jwk).unwrap();
+        assert_eq!(jwk, reference.to_jwk_string().as_str());
+
+        let mut private_key_out = [0u32; 8];
+        let mut x = [0u32; 8];
+        let mut y = [0u32; 8];
+        assert!(p256_cm4::private_key_from_jwk(
+            &mut private_key_out,
+            &mut x,
+            &mut y,
+            jwk.as_bytes()
+        ));
+        assert_eq!(private_key_out, private_key);
+        assert_eq!(to_public_key(&x, &y), Some(reference.public_key()));
+
+        let secret_key = p256_cm4::SecretKey::from_limbs(&private_key).unwrap();
+        assert_eq!(secret_key.to_jwk().as_slice(), jwk.as_bytes());
+        assert_eq!(
+            p256_cm4::SecretKey::from_jwk(jwk.as_bytes())
+                .unwrap()
+                .as_limbs(),
+            &private_key
+        );
+
+        // the private key is ignored when decoding the public key
+        assert_eq!(
+            p256_cm4::PublicKey::from_jwk(jwk.as_bytes()),
+            Ok(secret_key.public_key())
+        );
+    });
+}
+
+#[test]
+fn rejects() {
+    let mut rng: StdRng = rng();
+    let (private_key, x, y) = random_keypair(&mut rng);
+    let (_, other_x, other_y) = random_keypair(&mut rng);
+    let (x, y, other_x, other_y) = (
+        base64url(&x),
+        base64url(&y),
+        base64url(&other_x),
+        base64url(&other_y),
+    );
+    let d: String = base64url(&private_key);
+    let jwk = |kty: &str, crv: &str, x: &str, y: &str| {
+        format!(r#"{{"kty":"{kty}","crv":"{crv}","x":"{x}","y":"{y}"}}"#)
+    };
+
+    let invalid: [String; 16] = [
+        String::new(),
+        "{}".into(),
+        "[]".into(),
+        jwk("RSA", "P-256", &x, &y),
+        jwk("EC", "P-384", &x, &y),
+        jwk("EC", "secp256k1", &x, &y),
+        jwk("EC", "P-256", &x, ""),
+        // padded, standard alphabet, and a 33 byte coordinate
+        jwk("EC", "P-256", &format!("{x}="), &y),
+        jwk("EC", "P-256", &format!("+{}", &x[1..]), &y),
+        jwk("EC", "P-256", &format!("AA{x}"), &y),
+        jwk("EC", "P-256", &x, &y) + "{}",
+        jwk("EC", "P-256", &x, &y).replace("}", ",}"),
+        jwk("EC", "P-256", &x, &y).replace(r#""y""#, r#""x":"","y""#),
+        jwk("EC", "P-256", &x, &y).replace(r#""kty":"EC""#, r#""kty":"EC","kty":"EC""#),
+        jwk("EC", "P-256", &x, &y).replace(r#""kty""#, r#""a":[[[[[[[[[[]]]]]]]]]],"kty""#),
+        jwk("EC", "P-256", &x, &y).replace(r#""kty""#, r#""a":tru,"kty""#),
+    ];
+    invalid.iter().for_each(|jwk| {
+        assert_eq!(
+            p256_cm4::PublicKey::from_jwk(jwk.as_bytes()),
+            Err(p256_cm4::Error::InvalidJwk),
+            "{jwk}"
+        );
+    });
+
+    assert_eq!(
+        p256_cm4::PublicKey::from_jwk(jwk("EC", "P-256", &x, &other_y).as_bytes()),
+        Err(p256_cm4::Error::NotOnCurve)
+    );
+    assert_eq!(
+        p256_cm4::PublicKey::from_jwk(jwk("EC", "P-256", &base64url(&PRIME), &y).as_bytes()),
+        Err(p256_cm4::Error::CoordinateOutOfRange)
+    );
+
+    let with_d = |x: &str, y: &str, d: &str| {
+        format!(r#"{{"kty":"EC","crv":"P-256","x":"{x}","y":"{y}","d":"{d}"}}"#)
+    };
+    let mut private_key_out = [1u32; 8];
+    let mut x_out = [1u32; 8];
+    let mut y_out = [1u32; 8];
+    [
+        (jwk("EC", "P-256", &x, &y), p256_cm4::Error::InvalidJwk),
+        (
+            with_d(&other_x, &other_y, &d),
+            p256_cm4::Error::PublicKeyMismatch,
+        ),
+        (
+            with_d(&x, &y, &base64url(&ORDER)),
+            p256_cm4::Error::ScalarOutOfRange,
+        ),
+        (
+            with_d(&x, &y, &base64url(&[0; 8])),
+            p256_cm4::Error::ScalarOutOfRange,
+        ),
+    ]
+    .iter()
+    .for_each(|(jwk, error)| {
+        assert_eq!(
+            p256_cm4::try_private_key_from_jwk(
+                &mut private_key_out,
+                &mut x_out,
+                &mut y_out,
+                jwk.as_bytes()
+            ),
+            Err(*error),
+            "{jwk}"
+        );
+        assert_eq!((private_key_out, x_out, y_out), ([0; 8], [0; 8], [0; 8]));
+    });
+
+    let mut out = [0xffu8; p256_cm4::PRIVATE_KEY_JWK_LEN];
+    assert!(!p256_cm4::private_key_to_jwk(&mut out, &ORDER));
+    assert_eq!(out, [0; p256_cm4::PRIVATE_KEY_JWK_LEN]);
+}
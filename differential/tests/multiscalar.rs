@@ -0,0 +1,317 @@
+//! Differential tests of double and multi-scalar multiplication.
+
+use differential::{
+    ITERATIONS, ORDER, PRIME, add_small, edge_case_scalars, from_affine, random_keypair,
+    random_limbs, random_scalar, rng, to_nonzero_scalar, to_public_key,
+};
+use p256::{AffinePoint, ProjectivePoint, elliptic_curve::group::Group};
+use p256_cm4::{Error, MultiscalarScratch};
+use rand::{Rng, rngs::StdRng};
+use std::time::{Duration, Instant};
+
+type DoubleScalarMul = fn(
+    &mut [u32; 8],
+    &mut [u32; 8],
+    &[u32; 8],
+    &[u32; 8],
+    &[u32; 8],
+    &[u32; 8],
+) -> Result<(), Error>;
+
+const VARIANTS: [(&str, DoubleScalarMul); 2] = [
+    ("double_scalar_mul", p256_cm4::try_double_scalar_mul),
+    (
+        "double_scalar_mul_vartime",
+        p256_cm4::try_double_scalar_mul_vartime,
+    ),
+];
+
+/// `n - a`, for scalars that cancel.
+fn n_minus(a: &[u32; 8]) -> [u32; 8] {
+    let mut out = [0u32; 8];
+    let mut borrow: i64 = 0;
+    (0..8).for_each(|i| {
+        let t: i64 = i64::from(ORDER[i]) - i64::from(a[i]) - borrow;
+        out[i] = t as u32;
+        borrow = i64::from(t < 0);
+    });
+    out
+}
+
+fn check_double_scalar_mul(a: &[u32; 8], b: &[u32; 8], x: &[u32; 8], y: &[u32; 8]) {
+    VARIANTS.iter().for_each(|(name, f)| {
+        let mut result_x = [0u32; 8];
+        let mut result_y = [0u32; 8];
+        let result = f(&mut result_x, &mut result_y, a, b, x, y);
+        match (
+            to_nonzero_scalar(a),
+            to_nonzero_scalar(b),
+            to_public_key(x, y),
+        ) {
+            (Some(ka), Some(kb), Some(point)) => {
+                let expected: ProjectivePoint = ProjectivePoint::GENERATOR * *ka
+                    + ProjectivePoint::from(*point.as_affine()) * *kb;
+                if expected.is_identity().into() {
+                    assert_eq!(result, Err(Error::PointAtInfinity), "{name} at infinity");
+                } else {
+                    assert_eq!(result, Ok(()), "{name} rejected a={a:08x?} b={b:08x?}");
+                    let expected: AffinePoint = expected.into();
+                    assert_eq!(
+                        (result_x, result_y),
+                        from_affine(&expected),
+                        "{name} mismatch"
+                    );
+                }
+            }
+            (None, _, _) | (_, None, _) => {
+                assert_eq!(result, Err(Error::ScalarOutOfRange), "{name} scalar range")
+            }
+            (_, _, None) => assert!(result.is_err(), "{name} accepted x={x:08x?} y={y:08x?}"),
+        }
+    });
+}
+
+#[test]
+fn double_scalar_mul() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_double_scalar_mul(&random_scalar(&mut rng), &random_scalar(&mut rng), &x, &y);
+    });
+}
+
+#[test]
+fn double_scalar_mul_edge_cases() {
+    let mut rng: StdRng = rng();
+    let scalars: Vec<[u32; 8]> = edge_case_scalars();
+    scalars.iter().for_each(|a| {
+        scalars.iter().for_each(|b| {
+            let (_, x, y) = random_keypair(&mut rng);
+            check_double_scalar_mul(a, b, &x, &y);
+        });
+    });
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        check_double_scalar_mul(&random_limbs(&mut rng), &random_scalar(&mut rng), &x, &y);
+    });
+}
+
+#[test]
+fn double_scalar_mul_related_points() {
+    let mut rng: StdRng = rng();
+    let (gx, gy) = from_affine(&AffinePoint::GENERATOR);
+    let (nx, ny) = from_affine(&(-AffinePoint::GENERATOR));
+    (0..ITERATIONS / 10).for_each(|_| {
+        let a: [u32; 8] = random_scalar(&mut rng);
+        // a*G + (n-a)*G and a*G + a*(-G) are the point at infinity
+        check_double_scalar_mul(&a, &n_minus(&a), &gx, &gy);
+        check_double_scalar_mul(&a, &a, &nx, &ny);
+        // a*G + a*G adds equal points, which needs a doubling
+        check_double_scalar_mul(&a, &a, &gx, &gy);
+        check_double_scalar_mul(&a, &add_small(&a, 1), &nx, &ny);
+    });
+    check_double_scalar_mul(
+        &[1, 0, 0, 0, 0, 0, 0, 0],
+        &n_minus(&[1, 0, 0, 0, 0, 0, 0, 0]),
+        &gx,
+        &gy,
+    );
+}
+
+/// The scalars `a` and `b` and the point `P` of `a*G + b*P`.
+type Input = ([u32; 8], [u32; 8], [u32; 8], [u32; 8]);
+
+/// The median time of `double_scalar_mul` for the inputs.
+fn median_time(inputs: &[Input]) -> Duration {
+    let mut times: Vec<Duration> = inputs
+        .iter()
+        .map(|(a, b, x, y)| {
+            let mut result_x = [0u32; 8];
+            let mut result_y = [0u32; 8];
+            let start = Instant::now();
+            let _ = p256_cm4::try_double_scalar_mul(&mut result_x, &mut result_y, a, b, x, y);
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[times.len() / 2]
+}
+
+#[test]
+fn double_scalar_mul_related_points_timing() {
+    // For P = ±G the intermediate sums of the incomplete Jacobian formulas hit the point at
+    // infinity or need a doubling, which takes a different time than for unrelated points.
+    let mut rng: StdRng = rng();
+    let (gx, gy) = from_affine(&AffinePoint::GENERATOR);
+    let (nx, ny) = from_affine(&(-AffinePoint::GENERATOR));
+    let related: Vec<Input> = (0..ITERATIONS / 4)
+        .flat_map(|_| {
+            let a: [u32; 8] = random_scalar(&mut rng);
+            [
+                (a, n_minus(&a), gx, gy),
+                (a, a, nx, ny),
+                (a, a, gx, gy),
+                (a, add_small(&a, 1), nx, ny),
+            ]
+        })
+        .collect();
+    let unrelated: Vec<Input> = (0..ITERATIONS)
+        .map(|_| {
+            let (_, x, y) = random_keypair(&mut rng);
+            (random_scalar(&mut rng), random_scalar(&mut rng), x, y)
+        })
+        .collect();
+
+    // alternate between both sets in chunks, so that both see the same load of the machine
+    let (mut related_time, mut unrelated_time) = (Duration::ZERO, Duration::ZERO);
+    related
+        .chunks(ITERATIONS / 10)
+        .zip(unrelated.chunks(ITERATIONS / 10))
+        .for_each(|(related, unrelated)| {
+            related_time += median_time(related);
+            unrelated_time += median_time(unrelated);
+        });
+    let ratio: f64 = related_time.as_secs_f64() / unrelated_time.as_secs_f64();
+    assert!(
+        (0.9..1.1).contains(&ratio),
+        "related points take {ratio:.3} times as long as unrelated points"
+    );
+}
+
+#[test]
+fn double_scalar_mul_invalid_point() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS / 10).for_each(|_| {
+        let (_, mut x, mut y) = random_keypair(&mut rng);
+        match rng.gen_range(0..3) {
+            0 => x = random_limbs(&mut rng),
+            1 => y[0] ^= 1,
+            _ => y = add_small(&PRIME, rng.gen_range(0..3)),
+        }
+        check_double_scalar_mul(&random_scalar(&mut rng), &random_scalar(&mut rng), &x, &y);
+    });
+    check_double_scalar_mul(
+        &random_scalar(&mut rng),
+        &random_scalar(&mut rng),
+        &[0; 8],
+        &[0; 8],
+    );
+}
+
+type Term = ([u32; 8], [u32; 8], [u32; 8]);
+
+fn check_multiscalar_mul(terms: &[Term]) {
+    let mut scratch: Vec<MultiscalarScratch> = vec![MultiscalarScratch::new(); terms.len()];
+    let mut result_x = [0u32; 8];
+    let mut result_y = [0u32; 8];
+    let result = p256_cm4::try_multiscalar_mul(&mut result_x, &mut result_y, terms, &mut scratch);
+
+    let expected: Option<ProjectivePoint> =
+        terms
+            .iter()
+            .try_fold(ProjectivePoint::IDENTITY, |sum, (scalar, x, y)| {
+                let point = ProjectivePoint::from(*to_public_key(x, y)?.as_affine());
+                match to_nonzero_scalar(scalar) {
+                    Some(scalar) => Some(sum + point * *scalar),
+                    None if *scalar == [0; 8] => Some(sum),
+                    None => None,
+                }
+            });
+    match expected {
+        Some(expected) if expected.is_identity().into() => {
+            assert_eq!(
+                result,
+                Err(Error::PointAtInfinity),
+                "multiscalar_mul at infinity"
+            )
+        }
+        Some(expected) => {
+            assert_eq!(
+                result,
+                Ok(()),
+                "multiscalar_mul rejected {} terms",
+                terms.len()
+            );
+            let expected: AffinePoint = expected.into();
+            assert_eq!(
+                (result_x, result_y),
+                from_affine(&expected),
+                "multiscalar_mul mismatch for {} terms",
+                terms.len()
+            );
+        }
+        None => assert!(result.is_err(), "multiscalar_mul accepted invalid input"),
+    }
+}
+
+fn random_terms(rng: &mut StdRng, len: usize) -> Vec<Term> {
+    (0..len)
+        .map(|_| {
+            let (_, x, y) = random_keypair(rng);
+            (random_scalar(rng), x, y)
+        })
+        .collect()
+}
+
+#[test]
+fn multiscalar_mul() {
+    let mut rng: StdRng = rng();
+    // below and above the switch from sliding windows to buckets, and window widths 6, 7 and 8
+    [1, 2, 3, 16, 127, 128, 129, 300, 700, 2000]
+        .iter()
+        .for_each(|&len| check_multiscalar_mul(&random_terms(&mut rng, len)));
+}
+
+#[test]
+fn multiscalar_mul_edge_cases() {
+    let mut rng: StdRng = rng();
+    [4, 130].iter().for_each(|&len| {
+        // edge case scalars, including zero and out of range scalars
+        edge_case_scalars().iter().for_each(|scalar| {
+            let mut terms: Vec<Term> = random_terms(&mut rng, len);
+            terms[len / 2].0 = *scalar;
+            check_multiscalar_mul(&terms);
+        });
+
+        // the same point with the same scalar, which needs doublings
+        let mut terms: Vec<Term> = random_terms(&mut rng, len);
+        let first: Term = terms[0];
+        terms.iter_mut().for_each(|term| *term = first);
+        check_multiscalar_mul(&terms);
+
+        // the same point with negated scalars, which cancel
+        let mut terms: Vec<Term> = random_terms(&mut rng, len);
+        (1..len).for_each(|i| {
+            terms[i].1 = terms[0].1;
+            terms[i].2 = terms[0].2;
+            if i % 2 == 1 {
+                terms[i].0 = n_minus(&terms[i - 1].0);
+            }
+        });
+        check_multiscalar_mul(&terms);
+
+        // all zero scalars
+        let mut terms: Vec<Term> = random_terms(&mut rng, len);
+        terms.iter_mut().for_each(|term| term.0 = [0; 8]);
+        check_multiscalar_mul(&terms);
+
+        // an invalid point
+        let mut terms: Vec<Term> = random_terms(&mut rng, len);
+        terms[len - 1].2[0] ^= 1;
+        check_multiscalar_mul(&terms);
+    });
+    check_multiscalar_mul(&[]);
+}
+
+#[test]
+fn multiscalar_mul_scratch_too_short() {
+    let mut rng: StdRng = rng();
+    let terms: Vec<Term> = random_terms(&mut rng, 3);
+    let mut scratch = [MultiscalarScratch::new(); 2];
+    let mut result_x = [0u32; 8];
+    let mut result_y = [0u32; 8];
+    assert_eq!(
+        p256_cm4::try_multiscalar_mul(&mut result_x, &mut result_y, &terms, &mut scratch),
+        Err(Error::BufferTooSmall)
+    );
+}
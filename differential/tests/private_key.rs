@@ -0,0 +1,197 @@
+//! Differential tests of the SEC1 and PKCS#8 private key encodings.
+
+use differential::{ITERATIONS, ORDER, limbs_from_be, limbs_to_be, random_keypair, rng};
+use p256::{
+    SecretKey,
+    pkcs8::{DecodePrivateKey, EncodePrivateKey},
+};
+use rand::{Rng, rngs::StdRng};
+
+type Decoder =
+    fn(&mut [u32; 8], &mut [u32; 8], &mut [u32; 8], &[u8]) -> Result<(), p256_cm4::Error>;
+
+fn check_decode(decode: Decoder, der: &[u8], reference: Option<SecretKey>) {
+    let mut private_key = [0u32; 8];
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    let result = decode(&mut private_key, &mut x, &mut y, der);
+    match reference {
+        Some(expected) => {
+            assert!(result.is_ok(), "rejected {der:02x?}: {result:?}");
+            assert_eq!(private_key, limbs_from_be(&expected.to_bytes().into()));
+            let mut sec1 = [0u8; 65];
+            p256_cm4::point_to_octet_string_uncompressed(&mut sec1, &x, &y);
+            assert_eq!(
+                sec1.as_slice(),
+                expected.public_key().to_sec1_bytes().as_ref()
+            );
+        }
+        None => {
+            assert!(result.is_err(), "accepted {der:02x?}");
+            assert_eq!((private_key, x, y), ([0; 8], [0; 8], [0; 8]));
+        }
+    }
+}
+
+// Flips a bit of the private key or the public key coordinates, or truncates or extends the
+// encoding. The header bytes are left alone, the reference does not check the curve parameters,
+// is lenient with the tags of the optional fields, and rejects hybrid encoded public keys.
+fn modify(
+    rng: &mut StdRng,
+    der: &[u8],
+    private_key_offset: usize,
+    coordinates_offset: usize,
+) -> Vec<u8> {
+    let mut modified: Vec<u8> = der.to_vec();
+    match rng.gen_range(0..3) {
+        0 => {
+            let i: usize = rng.gen_range(0..32 + 64);
+            let i: usize = if i < 32 {
+                private_key_offset + i
+            } else {
+                coordinates_offset + i - 32
+            };
+            modified[i] ^= 1 << rng.gen_range(0..8);
+        }
+        1 => modified.truncate(rng.gen_range(0..modified.len())),
+        _ => modified.push(0),
+    }
+    modified
+}
+
+#[test]
+fn sec1() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, _, _) = random_keypair(&mut rng);
+        let reference = SecretKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+
+        let mut der = [0u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN];
+        assert!(p256_cm4::private_key_to_sec1_der(&mut der, &private_key));
+        // the reference omits the curve parameters, which RFC 5915 requires
+        assert_eq!(SecretKey::from_sec1_der(&der).as_ref(), Ok(&reference));
+        check_decode(
+            p256_cm4::try_private_key_from_sec1_der,
+            &der,
+            Some(reference.clone()),
+        );
+        check_decode(
+            p256_cm4::try_private_key_from_sec1_der,
+            &reference.to_sec1_der().unwrap(),
+            Some(reference.clone()),
+        );
+
+        let secret_key = p256_cm4::SecretKey::from_limbs(&private_key).unwrap();
+        assert_eq!(secret_key.to_sec1_der(), der);
+        assert_eq!(
+            p256_cm4::SecretKey::from_sec1_der(&der).unwrap().as_limbs(),
+            &private_key
+        );
+
+        // without the optional fields
+        let mut minimal: Vec<u8> = der[..39].to_vec();
+        minimal[1] = 37;
+        check_decode(
+            p256_cm4::try_private_key_from_sec1_der,
+            &minimal,
+            Some(reference.clone()),
+        );
+
+        let modified: Vec<u8> = modify(&mut rng, &der, 7, 57);
+        check_decode(
+            p256_cm4::try_private_key_from_sec1_der,
+            &modified,
+            SecretKey::from_sec1_der(&modified).ok(),
+        );
+    });
+}
+
+#[test]
+fn pkcs8() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, _, _) = random_keypair(&mut rng);
+        let reference = SecretKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+
+        let mut der = [0u8; p256_cm4::PKCS8_PRIVATE_KEY_DER_LEN];
+        assert!(p256_cm4::private_key_to_pkcs8_der(&mut der, &private_key));
+        assert_eq!(der.as_slice(), reference.to_pkcs8_der().unwrap().as_bytes());
+        check_decode(
+            p256_cm4::try_private_key_from_pkcs8_der,
+            &der,
+            Some(reference.clone()),
+        );
+
+        let secret_key = p256_cm4::SecretKey::from_limbs(&private_key).unwrap();
+        assert_eq!(secret_key.to_pkcs8_der(), der);
+        assert_eq!(
+            p256_cm4::SecretKey::from_pkcs8_der(&der)
+                .unwrap()
+                .as_limbs(),
+            &private_key
+        );
+
+        let modified: Vec<u8> = modify(&mut rng, &der, 36, 74);
+        check_decode(
+            p256_cm4::try_private_key_from_pkcs8_der,
+            &modified,
+            SecretKey::from_pkcs8_der(&modified).ok(),
+        );
+    });
+}
+
+#[test]
+fn rejects() {
+    let mut rng: StdRng = rng();
+    let (private_key, _, _) = random_keypair(&mut rng);
+    let (other_private_key, _, _) = random_keypair(&mut rng);
+    let mut der = [0u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN];
+    assert!(p256_cm4::private_key_to_sec1_der(&mut der, &private_key));
+    let mut other = [0u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN];
+    assert!(p256_cm4::private_key_to_sec1_der(
+        &mut other,
+        &other_private_key
+    ));
+
+    let mut private_key_out = [0u32; 8];
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+
+    // public key of another private key
+    let mut mismatch: [u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN] = der;
+    mismatch[51..].copy_from_slice(&other[51..]);
+    assert_eq!(
+        p256_cm4::try_private_key_from_sec1_der(&mut private_key_out, &mut x, &mut y, &mismatch),
+        Err(p256_cm4::Error::PublicKeyMismatch)
+    );
+
+    // private keys out of range
+    [[0; 8], ORDER].iter().for_each(|invalid| {
+        let mut out_of_range: [u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN] = der;
+        out_of_range[7..39].copy_from_slice(&limbs_to_be(invalid));
+        assert_eq!(
+            p256_cm4::try_private_key_from_sec1_der(
+                &mut private_key_out,
+                &mut x,
+                &mut y,
+                &out_of_range
+            ),
+            Err(p256_cm4::Error::ScalarOutOfRange)
+        );
+
+        let mut out = [0xffu8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN];
+        assert!(!p256_cm4::private_key_to_sec1_der(&mut out, invalid));
+        assert_eq!(out, [0; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN]);
+        let mut out = [0xffu8; p256_cm4::PKCS8_PRIVATE_KEY_DER_LEN];
+        assert!(!p256_cm4::private_key_to_pkcs8_der(&mut out, invalid));
+        assert_eq!(out, [0; p256_cm4::PKCS8_PRIVATE_KEY_DER_LEN]);
+    });
+
+    // another curve, secp256k1
+    let mut secp256k1: [u8; p256_cm4::SEC1_PRIVATE_KEY_DER_LEN] = der;
+    secp256k1[41..51].copy_from_slice(&[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a, 0, 0, 0]);
+    assert_eq!(
+        p256_cm4::try_private_key_from_sec1_der(&mut private_key_out, &mut x, &mut y, &secp256k1),
+        Err(p256_cm4::Error::InvalidDer)
+    );
+}
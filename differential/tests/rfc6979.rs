@@ -0,0 +1,202 @@
+//! Deterministic signatures, against the RFC 6979 test vectors and the RustCrypto signer.
+
+use differential::{
+    ITERATIONS, ORDER, limbs_from_be, limbs_to_be, random_keypair, random_scalar, rng,
+};
+use hex_literal::hex;
+use p256::ecdsa::Signature;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use signature::hazmat::{PrehashSigner, RandomizedPrehashSigner};
+
+// RFC 6979 A.2.5, NIST P-256
+const PRIVATE_KEY: [u8; 32] =
+    hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+const PUBLIC_KEY_X: [u8; 32] =
+    hex!("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6");
+const PUBLIC_KEY_Y: [u8; 32] =
+    hex!("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299");
+
+fn check_vector<D>(msg: &[u8], expected_r: &[u8; 32], expected_s: &[u8; 32])
+where
+    D: Digest + sha2::digest::FixedOutputReset + sha2::digest::core_api::BlockSizeUser,
+{
+    let private_key: [u32; 8] = limbs_from_be(&PRIVATE_KEY);
+    let hash = D::digest(msg);
+
+    let mut r = [0u32; 8];
+    let mut s = [0u32; 8];
+    p256_cm4::try_sign_deterministic_with_digest::<D>(&mut r, &mut s, &hash, &private_key).unwrap();
+    assert_eq!(limbs_to_be(&r), *expected_r);
+    assert_eq!(limbs_to_be(&s), *expected_s);
+
+    let secret_key = p256_cm4::SecretKey::from_be_bytes(&PRIVATE_KEY).unwrap();
+    let signature: p256_cm4::Signature = secret_key.sign_deterministic_with_digest::<D>(&hash);
+    assert_eq!(signature.r(), &r);
+    assert_eq!(signature.s(), &s);
+
+    let public_key = p256_cm4::PublicKey::from_affine(
+        &limbs_from_be(&PUBLIC_KEY_X),
+        &limbs_from_be(&PUBLIC_KEY_Y),
+    )
+    .unwrap();
+    assert_eq!(secret_key.public_key(), public_key);
+    assert!(public_key.verify(&hash, &signature));
+    assert!(
+        public_key
+            .prepare()
+            .verify(&hash, signature.r(), signature.s())
+    );
+}
+
+#[test]
+fn rfc6979_sample() {
+    check_vector::<Sha224>(
+        b"sample",
+        &hex!("53b2fff5d1752b2c689df257c04c40a587fababb3f6fc2702f1343af7ca9aa3f"),
+        &hex!("b9afb64fdc03dc1a131c7d2386d11e349f070aa432a4acc918bea988bf75c74c"),
+    );
+    check_vector::<Sha256>(
+        b"sample",
+        &hex!("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+        &hex!("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+    );
+    check_vector::<Sha384>(
+        b"sample",
+        &hex!("0eafea039b20e9b42309fb1d89e213057cbf973dc0cfc8f129edddc800ef7719"),
+        &hex!("4861f0491e6998b9455193e34e7b0d284ddd7149a74b95b9261f13abde940954"),
+    );
+    check_vector::<Sha512>(
+        b"sample",
+        &hex!("8496a60b5e9b47c825488827e0495b0e3fa109ec4568fd3f8d1097678eb97f00"),
+        &hex!("2362ab1adbe2b8adf9cb9edab740ea6049c028114f2460f96554f61fae3302fe"),
+    );
+}
+
+#[test]
+fn rfc6979_test() {
+    check_vector::<Sha224>(
+        b"test",
+        &hex!("c37edb6f0ae79d47c3c27e962fa269bb4f441770357e114ee511f662ec34a692"),
+        &hex!("c820053a05791e521fcaad6042d40aea1d6b1a540138558f47d0719800e18f2d"),
+    );
+    check_vector::<Sha256>(
+        b"test",
+        &hex!("f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367"),
+        &hex!("019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"),
+    );
+    check_vector::<Sha384>(
+        b"test",
+        &hex!("83910e8b48bb0c74244ebdf7f07a1c5413d61472bd941ef3920e623fbccebeb6"),
+        &hex!("8ddbec54cf8cd5874883841d712142a56a8d0f218f5003cb0296b6b509619f2c"),
+    );
+    check_vector::<Sha512>(
+        b"test",
+        &hex!("461d93f31b6540894788fd206c07cfa0cc35f46fa3c91816fff1040ad1581a04"),
+        &hex!("39af9f15de0db8d97e72719c74820d304ce5226e32dedae67519e840d1194e55"),
+    );
+}
+
+#[test]
+fn sign_deterministic() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, x, y) = random_keypair(&mut rng);
+        let reference =
+            p256::ecdsa::SigningKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+        let mut hash: Vec<u8> = vec![0; rng.gen_range(16..=64)];
+        rng.fill_bytes(&mut hash);
+
+        let mut r = [0u32; 8];
+        let mut s = [0u32; 8];
+        assert!(p256_cm4::sign_deterministic(
+            &mut r,
+            &mut s,
+            &hash,
+            &private_key
+        ));
+        let expected: Signature = reference.sign_prehash(&hash).unwrap();
+        assert_eq!(limbs_to_be(&r), <[u8; 32]>::from(expected.r().to_bytes()));
+        assert_eq!(limbs_to_be(&s), <[u8; 32]>::from(expected.s().to_bytes()));
+        assert!(p256_cm4::verify(&x, &y, &hash, &r, &s));
+
+        // the same input results in the same signature
+        let mut r2 = [0u32; 8];
+        let mut s2 = [0u32; 8];
+        assert!(p256_cm4::sign_deterministic(
+            &mut r2,
+            &mut s2,
+            &hash,
+            &private_key
+        ));
+        assert_eq!((r, s), (r2, s2));
+    });
+}
+
+#[test]
+fn sign_hedged() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (private_key, x, y) = random_keypair(&mut rng);
+        let reference =
+            p256::ecdsa::SigningKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+        let mut hash: Vec<u8> = vec![0; rng.gen_range(16..=64)];
+        rng.fill_bytes(&mut hash);
+
+        // both implementations use 32 bytes from the rng as additional data
+        let seed: u64 = rng.next_u64();
+        let mut r = [0u32; 8];
+        let mut s = [0u32; 8];
+        assert!(p256_cm4::sign_hedged(
+            &mut r,
+            &mut s,
+            &hash,
+            &private_key,
+            &mut StdRng::seed_from_u64(seed)
+        ));
+        let expected: Signature = reference
+            .sign_prehash_with_rng(&mut StdRng::seed_from_u64(seed), &hash)
+            .unwrap();
+        assert_eq!(limbs_to_be(&r), <[u8; 32]>::from(expected.r().to_bytes()));
+        assert_eq!(limbs_to_be(&s), <[u8; 32]>::from(expected.s().to_bytes()));
+        assert!(p256_cm4::verify(&x, &y, &hash, &r, &s));
+
+        // different randomness results in a different signature
+        let mut r2 = [0u32; 8];
+        let mut s2 = [0u32; 8];
+        assert!(p256_cm4::sign_hedged(
+            &mut r2,
+            &mut s2,
+            &hash,
+            &private_key,
+            &mut rng
+        ));
+        assert_ne!(r, r2);
+        assert!(p256_cm4::verify(&x, &y, &hash, &r2, &s2));
+    });
+}
+
+#[test]
+fn sign_deterministic_invalid_key() {
+    let mut rng: StdRng = rng();
+    let hash: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+    [[0; 8], ORDER, [0xffffffff; 8]]
+        .iter()
+        .for_each(|private_key| {
+            let mut r = [1u32; 8];
+            let mut s = [1u32; 8];
+            assert_eq!(
+                p256_cm4::try_sign_deterministic(&mut r, &mut s, &hash, private_key),
+                Err(p256_cm4::Error::ScalarOutOfRange)
+            );
+            assert_eq!((r, s), ([0; 8], [0; 8]));
+
+            let mut r = [1u32; 8];
+            let mut s = [1u32; 8];
+            assert_eq!(
+                p256_cm4::try_sign_hedged(&mut r, &mut s, &hash, private_key, &mut rng),
+                Err(p256_cm4::Error::ScalarOutOfRange)
+            );
+            assert_eq!((r, s), ([0; 8], [0; 8]));
+        });
+}
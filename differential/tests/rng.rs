@@ -0,0 +1,188 @@
+//! Key generation and signing with a random number generator.
+
+use differential::{ITERATIONS, ORDER, add_small, limbs_to_be, random_scalar, rng};
+use p256::ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+use rand::{CryptoRng, RngCore, rngs::StdRng};
+
+/// Returns the queued values first, then falls back to the inner rng.
+struct QueuedRng {
+    queue: Vec<[u8; 32]>,
+    inner: StdRng,
+}
+
+impl RngCore for QueuedRng {
+    fn next_u32(&mut self) -> u32 {
+        unimplemented!()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        unimplemented!()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.queue.is_empty() {
+            self.inner.fill_bytes(dest)
+        } else {
+            dest.copy_from_slice(&self.queue.remove(0))
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for QueuedRng {}
+
+fn le_bytes(limbs: &[u32; 8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    limbs.iter().enumerate().for_each(|(i, limb)| {
+        out[4 * i..4 * i + 4].copy_from_slice(&limb.to_le_bytes());
+    });
+    out
+}
+
+fn check_signature(x: &[u32; 8], y: &[u32; 8], hash: &[u8], r: &[u32; 8], s: &[u32; 8]) {
+    assert!(p256_cm4::verify(x, y, hash, r, s));
+    let mut sec1 = [0u8; 65];
+    p256_cm4::point_to_octet_string_uncompressed(&mut sec1, x, y);
+    let verifying_key = VerifyingKey::from_sec1_bytes(&sec1).unwrap();
+    let signature = Signature::from_scalars(limbs_to_be(r), limbs_to_be(s)).unwrap();
+    assert!(verifying_key.verify_prehash(hash, &signature).is_ok());
+}
+
+#[test]
+fn generate_keypair() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let mut private_key = [0u32; 8];
+        let mut x = [0u32; 8];
+        let mut y = [0u32; 8];
+        p256_cm4::generate_keypair(&mut private_key, &mut x, &mut y, &mut rng);
+        assert!(p256_cm4::check_range_n(&private_key));
+
+        let mut expected_x = [0u32; 8];
+        let mut expected_y = [0u32; 8];
+        assert!(p256_cm4::keygen(
+            &mut expected_x,
+            &mut expected_y,
+            &private_key
+        ));
+        assert_eq!((x, y), (expected_x, expected_y));
+    });
+}
+
+#[test]
+fn generate_keypair_rejects_out_of_range() {
+    let mut rng: StdRng = rng();
+    let valid: [u32; 8] = random_scalar(&mut rng);
+    let mut queued = QueuedRng {
+        queue: vec![
+            [0; 32],
+            le_bytes(&ORDER),
+            [0xff; 32],
+            le_bytes(&add_small(&ORDER, 1)),
+            le_bytes(&valid),
+        ],
+        inner: rng,
+    };
+
+    let mut private_key = [0u32; 8];
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    p256_cm4::generate_keypair(&mut private_key, &mut x, &mut y, &mut queued);
+    assert_eq!(private_key, valid);
+    assert!(queued.queue.is_empty());
+
+    queued.queue = vec![le_bytes(&add_small(&ORDER, -1))];
+    let secret_key = p256_cm4::SecretKey::random(&mut queued);
+    assert_eq!(secret_key.as_limbs(), &add_small(&ORDER, -1));
+}
+
+#[test]
+fn sign_with_rng() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let secret_key = p256_cm4::SecretKey::random(&mut rng);
+        let public_key: p256_cm4::PublicKey = secret_key.public_key();
+        let hash: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+
+        let mut r = [0u32; 8];
+        let mut s = [0u32; 8];
+        assert!(p256_cm4::sign_with_rng(
+            &mut r,
+            &mut s,
+            &hash,
+            secret_key.as_limbs(),
+            &mut rng
+        ));
+        check_signature(public_key.x(), public_key.y(), &hash, &r, &s);
+
+        let signature: p256_cm4::Signature = secret_key.sign_with_rng(&hash, &mut rng);
+        assert!(public_key.verify(&hash, &signature));
+    });
+}
+
+#[test]
+fn sign_with_rng_retries() {
+    let mut rng: StdRng = rng();
+    let secret_key = p256_cm4::SecretKey::random(&mut rng);
+    let public_key: p256_cm4::PublicKey = secret_key.public_key();
+    let hash: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+    let k: [u32; 8] = random_scalar(&mut rng);
+    let mut queued = QueuedRng {
+        queue: vec![[0; 32], le_bytes(&ORDER), le_bytes(&k)],
+        inner: rng,
+    };
+
+    let mut r = [0u32; 8];
+    let mut s = [0u32; 8];
+    p256_cm4::try_sign_with_rng(&mut r, &mut s, &hash, secret_key.as_limbs(), &mut queued).unwrap();
+    assert!(queued.queue.is_empty());
+
+    // the signature uses the first valid k
+    let expected: p256_cm4::Signature = secret_key.sign(&hash, &k).unwrap();
+    assert_eq!((&r, &s), (expected.r(), expected.s()));
+    check_signature(public_key.x(), public_key.y(), &hash, &r, &s);
+}
+
+#[test]
+fn sign_step1_with_rng() {
+    let mut rng: StdRng = rng();
+    let secret_key = p256_cm4::SecretKey::random(&mut rng);
+    let public_key: p256_cm4::PublicKey = secret_key.public_key();
+    (0..ITERATIONS).for_each(|_| {
+        let hash: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+        let mut precomp = p256_cm4::SignPrecomp::default();
+        p256_cm4::sign_step1_with_rng(&mut precomp, &mut rng);
+
+        let mut r = [0u32; 8];
+        let mut s = [0u32; 8];
+        assert!(p256_cm4::sign_step2(
+            &mut r,
+            &mut s,
+            &hash,
+            secret_key.as_limbs(),
+            &mut precomp
+        ));
+        check_signature(public_key.x(), public_key.y(), &hash, &r, &s);
+    });
+}
+
+#[test]
+fn sign_with_rng_invalid_key() {
+    let mut rng: StdRng = rng();
+    let hash: [u8; 32] = limbs_to_be(&random_scalar(&mut rng));
+    [[0; 8], ORDER, [0xffffffff; 8]]
+        .iter()
+        .for_each(|private_key| {
+            let mut r = [1u32; 8];
+            let mut s = [1u32; 8];
+            assert_eq!(
+                p256_cm4::try_sign_with_rng(&mut r, &mut s, &hash, private_key, &mut rng),
+                Err(p256_cm4::Error::ScalarOutOfRange)
+            );
+            assert_eq!((r, s), ([0; 8], [0; 8]));
+        });
+}
@@ -0,0 +1,123 @@
+//! Differential tests of the RustCrypto `signature` trait implementations.
+
+use differential::{ITERATIONS, limbs_to_be, random_scalar, rng};
+use rand::{Rng, RngCore, rngs::StdRng};
+use sha2::{Digest, Sha256};
+use signature::{
+    Signer, Verifier,
+    hazmat::{PrehashSigner, PrehashVerifier},
+};
+
+type Signature = ecdsa::Signature<p256_cm4::NistP256>;
+
+/// A RustCrypto `p256` signature as a signature of this crate.
+fn convert(signature: p256::ecdsa::Signature) -> Signature {
+    Signature::from_bytes(&signature.to_bytes()).unwrap()
+}
+
+fn keys(rng: &mut StdRng) -> (p256_cm4::SigningKey, p256::ecdsa::SigningKey) {
+    let private_key: [u32; 8] = random_scalar(rng);
+    let signing_key =
+        p256_cm4::SigningKey::from(p256_cm4::SecretKey::from_limbs(&private_key).unwrap());
+    let reference = p256::ecdsa::SigningKey::from_bytes(&limbs_to_be(&private_key).into()).unwrap();
+    (signing_key, reference)
+}
+
+#[test]
+fn sign() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (signing_key, reference) = keys(&mut rng);
+        let mut msg: Vec<u8> = vec![0; rng.gen_range(0..256)];
+        rng.fill_bytes(&mut msg);
+
+        let signature: Signature = signing_key.sign(&msg);
+        let expected: Signature = convert(reference.sign(&msg));
+        assert_eq!(signature, expected);
+        let signature = p256::ecdsa::Signature::from_bytes(&signature.to_bytes()).unwrap();
+        assert!(reference.verifying_key().verify(&msg, &signature).is_ok());
+    });
+}
+
+#[test]
+fn sign_prehash() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (signing_key, reference) = keys(&mut rng);
+        // RustCrypto rejects hashes shorter than half of the field size.
+        let mut prehash: Vec<u8> = vec![0; rng.gen_range(16..=64)];
+        rng.fill_bytes(&mut prehash);
+
+        let signature: Signature = signing_key.sign_prehash(&prehash).unwrap();
+        let expected: Signature = convert(reference.sign_prehash(&prehash).unwrap());
+        assert_eq!(signature, expected);
+    });
+}
+
+#[test]
+fn verify() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (signing_key, reference) = keys(&mut rng);
+        let verifying_key: p256_cm4::VerifyingKey = signing_key.verifying_key();
+        let mut msg: Vec<u8> = vec![0; rng.gen_range(1..256)];
+        rng.fill_bytes(&mut msg);
+
+        let signature: Signature = convert(reference.sign(&msg));
+        assert!(verifying_key.verify(&msg, &signature).is_ok());
+        let prehash = Sha256::digest(&msg);
+        assert!(verifying_key.verify_prehash(&prehash, &signature).is_ok());
+
+        msg[0] ^= 1;
+        assert!(verifying_key.verify(&msg, &signature).is_err());
+    });
+}
+
+#[test]
+fn p256_signatures() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (signing_key, reference) = keys(&mut rng);
+        let mut msg: Vec<u8> = vec![0; rng.gen_range(1..256)];
+        rng.fill_bytes(&mut msg);
+
+        // the traits of this crate with the signature type of p256
+        let signature: p256::ecdsa::Signature = signing_key.sign(&msg);
+        assert_eq!(signature, reference.sign(&msg));
+        let verifying_key: p256_cm4::VerifyingKey = signing_key.verifying_key();
+        assert!(verifying_key.verify(&msg, &signature).is_ok());
+        let prehash = Sha256::digest(&msg);
+        let prehash_signature: p256::ecdsa::Signature = signing_key.sign_prehash(&prehash).unwrap();
+        assert_eq!(prehash_signature, signature);
+        assert!(verifying_key.verify_prehash(&prehash, &signature).is_ok());
+
+        msg[0] ^= 1;
+        assert!(verifying_key.verify(&msg, &signature).is_err());
+    });
+}
+
+#[test]
+fn p256_keys() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (signing_key, reference) = keys(&mut rng);
+
+        let converted = p256::ecdsa::SigningKey::from(&signing_key);
+        assert_eq!(converted, reference);
+        let converted = p256_cm4::SigningKey::from(&reference);
+        assert_eq!(
+            converted.as_secret_key().as_limbs(),
+            signing_key.as_secret_key().as_limbs()
+        );
+
+        let verifying_key: p256_cm4::VerifyingKey = signing_key.verifying_key();
+        assert_eq!(
+            p256::ecdsa::VerifyingKey::from(verifying_key),
+            *reference.verifying_key()
+        );
+        assert_eq!(
+            p256_cm4::VerifyingKey::from(*reference.verifying_key()),
+            verifying_key
+        );
+    });
+}
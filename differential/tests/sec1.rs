@@ -0,0 +1,116 @@
+//! Differential tests of SEC1 point decoding.
+
+use differential::{ITERATIONS, PRIME, limbs_to_be, random_keypair, rng, to_public_key};
+use p256::{EncodedPoint, PublicKey, elliptic_curve::sec1::FromEncodedPoint};
+use rand::{Rng, RngCore, rngs::StdRng};
+
+fn reference_decode(input: &[u8]) -> Option<PublicKey> {
+    // RustCrypto does not accept the hybrid encoding, so check it by hand.
+    if input.len() == 65 && (input[0] == 6 || input[0] == 7) {
+        let mut uncompressed: [u8; 65] = input.try_into().unwrap();
+        uncompressed[0] = 4;
+        let public_key: PublicKey = reference_decode(&uncompressed)?;
+        return (input[0] & 1 == input[64] & 1).then_some(public_key);
+    }
+    // RustCrypto also accepts the compact encoding, which is not part of SEC1.
+    if !matches!(input.first(), Some(2..=4)) {
+        return None;
+    }
+    let encoded = EncodedPoint::from_bytes(input).ok()?;
+    PublicKey::from_encoded_point(&encoded).into()
+}
+
+fn check_decode(input: &[u8]) {
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    let ok: bool = p256_cm4::octet_string_to_point(&mut x, &mut y, input);
+    match reference_decode(input) {
+        Some(expected) => {
+            assert!(ok, "octet_string_to_point rejected {input:02x?}");
+            assert_eq!(
+                Some(expected),
+                to_public_key(&x, &y),
+                "mismatch for {input:02x?}"
+            );
+        }
+        None => assert!(!ok, "octet_string_to_point accepted {input:02x?}"),
+    }
+}
+
+fn encodings(x: &[u32; 8], y: &[u32; 8]) -> [Vec<u8>; 3] {
+    let mut uncompressed = [0u8; 65];
+    let mut compressed = [0u8; 33];
+    let mut hybrid = [0u8; 65];
+    p256_cm4::point_to_octet_string_uncompressed(&mut uncompressed, x, y);
+    p256_cm4::point_to_octet_string_compressed(&mut compressed, x, y);
+    p256_cm4::point_to_octet_string_hybrid(&mut hybrid, x, y);
+    [uncompressed.to_vec(), compressed.to_vec(), hybrid.to_vec()]
+}
+
+#[test]
+fn valid_points() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        encodings(&x, &y).iter().for_each(|encoding| {
+            check_decode(encoding);
+            let mut x2 = [0u32; 8];
+            let mut y2 = [0u32; 8];
+            assert!(p256_cm4::octet_string_to_point(&mut x2, &mut y2, encoding));
+            assert_eq!((x, y), (x2, y2));
+        });
+    });
+}
+
+#[test]
+fn mutated_points() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        encodings(&x, &y).iter().for_each(|encoding| {
+            let mut mutated: Vec<u8> = encoding.clone();
+            let bit: usize = rng.gen_range(0..mutated.len() * 8);
+            mutated[bit / 8] ^= 1 << (bit % 8);
+            check_decode(&mutated);
+
+            let mut truncated: Vec<u8> = encoding.clone();
+            truncated.truncate(rng.gen_range(0..encoding.len()));
+            check_decode(&truncated);
+        });
+    });
+}
+
+#[test]
+fn random_inputs() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let mut input: Vec<u8> = vec![0; rng.gen_range(0..70)];
+        rng.fill_bytes(&mut input);
+        if let Some(tag) = input.first_mut() {
+            *tag = rng.gen_range(0..8);
+        }
+        check_decode(&input);
+    });
+}
+
+#[test]
+fn out_of_range_coordinates() {
+    let p: [u8; 32] = limbs_to_be(&PRIME);
+    [0x02, 0x03].iter().for_each(|tag| {
+        let mut input = vec![*tag];
+        input.extend_from_slice(&p);
+        check_decode(&input);
+        input[1..33].fill(0xff);
+        check_decode(&input);
+    });
+    let mut rng: StdRng = rng();
+    let (_, x, _) = random_keypair(&mut rng);
+    [0x04, 0x06, 0x07].iter().for_each(|tag| {
+        let mut input = vec![*tag];
+        input.extend_from_slice(&limbs_to_be(&x));
+        input.extend_from_slice(&p);
+        check_decode(&input);
+        input[1..33].copy_from_slice(&p);
+        check_decode(&input);
+    });
+}
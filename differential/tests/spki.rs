@@ -0,0 +1,146 @@
+//! Differential tests of the `SubjectPublicKeyInfo` DER and PEM encodings.
+
+use differential::{ITERATIONS, random_keypair, rng, to_public_key};
+use p256::{
+    PublicKey,
+    pkcs8::{DecodePublicKey, EncodePublicKey, LineEnding},
+};
+use rand::{Rng, rngs::StdRng};
+
+fn check_decode_der(der: &[u8]) {
+    let mut x = [0u32; 8];
+    let mut y = [0u32; 8];
+    let result = p256_cm4::try_spki_der_to_point(&mut x, &mut y, der);
+    match PublicKey::from_public_key_der(der) {
+        Ok(expected) => {
+            assert!(result.is_ok(), "rejected {der:02x?}: {result:?}");
+            assert_eq!(to_public_key(&x, &y), Some(expected));
+        }
+        Err(_) => {
+            assert!(result.is_err(), "accepted {der:02x?}");
+            assert_eq!((x, y), ([0; 8], [0; 8]));
+        }
+    }
+}
+
+#[test]
+fn der() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        let reference: PublicKey = to_public_key(&x, &y).unwrap();
+
+        let mut der = [0u8; p256_cm4::SPKI_UNCOMPRESSED_DER_LEN];
+        p256_cm4::point_to_spki_der_uncompressed(&mut der, &x, &y);
+        assert_eq!(
+            der.as_slice(),
+            reference.to_public_key_der().unwrap().as_bytes()
+        );
+        check_decode_der(&der);
+
+        let mut compressed = [0u8; p256_cm4::SPKI_COMPRESSED_DER_LEN];
+        p256_cm4::point_to_spki_der_compressed(&mut compressed, &x, &y);
+        check_decode_der(&compressed);
+
+        let public_key = p256_cm4::PublicKey::from_affine(&x, &y).unwrap();
+        assert_eq!(public_key.to_spki_der(), der);
+        assert_eq!(public_key.to_spki_der_compressed(), compressed);
+        assert_eq!(p256_cm4::PublicKey::from_spki_der(&der), Ok(public_key));
+        assert_eq!(
+            p256_cm4::PublicKey::from_spki_der(&compressed),
+            Ok(public_key)
+        );
+
+        // random modifications, leaving the point prefix alone as hybrid encoded points are
+        // rejected by the reference
+        let mut modified: Vec<u8> = der.to_vec();
+        match rng.gen_range(0..3) {
+            0 => {
+                let i: usize = rng.gen_range(0..modified.len() - 1);
+                let i: usize = if i < 26 { i } else { i + 1 };
+                modified[i] ^= 1 << rng.gen_range(0..8);
+            }
+            1 => modified.truncate(rng.gen_range(0..modified.len())),
+            _ => modified.push(0),
+        }
+        check_decode_der(&modified);
+    });
+}
+
+#[test]
+fn pem() {
+    let mut rng: StdRng = rng();
+    (0..ITERATIONS).for_each(|_| {
+        let (_, x, y) = random_keypair(&mut rng);
+        let reference: PublicKey = to_public_key(&x, &y).unwrap();
+        let expected: String = reference.to_public_key_pem(LineEnding::LF).unwrap();
+
+        let mut pem = [0u8; p256_cm4::SPKI_UNCOMPRESSED_PEM_LEN];
+        p256_cm4::point_to_spki_pem_uncompressed(&mut pem, &x, &y);
+        assert_eq!(core::str::from_utf8(&pem), Ok(expected.as_str()));
+
+        let public_key = p256_cm4::PublicKey::from_affine(&x, &y).unwrap();
+        assert_eq!(public_key.to_spki_pem(), pem);
+        assert_eq!(p256_cm4::PublicKey::from_spki_pem(&pem), Ok(public_key));
+        let crlf: String = reference.to_public_key_pem(LineEnding::CRLF).unwrap();
+        assert_eq!(
+            p256_cm4::PublicKey::from_spki_pem(crlf.as_bytes()),
+            Ok(public_key)
+        );
+
+        let mut compressed = [0u8; p256_cm4::SPKI_COMPRESSED_PEM_LEN];
+        p256_cm4::point_to_spki_pem_compressed(&mut compressed, &x, &y);
+        assert_eq!(public_key.to_spki_pem_compressed(), compressed);
+        let compressed: &str = core::str::from_utf8(&compressed).unwrap();
+        assert_eq!(PublicKey::from_public_key_pem(compressed), Ok(reference));
+        assert_eq!(
+            p256_cm4::PublicKey::from_spki_pem(compressed.as_bytes()),
+            Ok(public_key)
+        );
+    });
+}
+
+#[test]
+fn pem_rejects() {
+    let mut rng: StdRng = rng();
+    let (_, x, y) = random_keypair(&mut rng);
+    let mut pem = [0u8; p256_cm4::SPKI_UNCOMPRESSED_PEM_LEN];
+    p256_cm4::point_to_spki_pem_uncompressed(&mut pem, &x, &y);
+    let pem: &str = core::str::from_utf8(&pem).unwrap();
+
+    let cases: [String; 6] = [
+        String::new(),
+        pem.replace("PUBLIC KEY", "EC PUBLIC KEY"),
+        pem.replacen("-----END PUBLIC KEY-----", "-----END PRIVATE KEY-----", 1),
+        pem.replacen('M', "*", 1),
+        // 91 bytes are 122 characters and 2 padding characters
+        pem.replacen("==\n-----END", "===\n-----END", 1),
+        pem.replacen("==\n-----END", "\n-----END", 1),
+    ];
+    cases.iter().for_each(|case| {
+        let mut x_out = [1u32; 8];
+        let mut y_out = [1u32; 8];
+        assert_eq!(
+            p256_cm4::try_spki_pem_to_point(&mut x_out, &mut y_out, case.as_bytes()),
+            Err(p256_cm4::Error::InvalidPem),
+            "{case}"
+        );
+        assert_eq!((x_out, y_out), ([0; 8], [0; 8]));
+    });
+
+    // non-zero unused bits in the last character, which is one of A, Q, g and w
+    let last: usize = pem.find("==").unwrap() - 1;
+    let mut non_canonical: Vec<u8> = pem.as_bytes().to_vec();
+    non_canonical[last] += 1;
+    assert_eq!(
+        p256_cm4::PublicKey::from_spki_pem(&non_canonical),
+        Err(p256_cm4::Error::InvalidPem)
+    );
+
+    // leading and trailing whitespace is ignored
+    let whitespace: String = format!("\n  {pem}\r\n");
+    assert_eq!(
+        p256_cm4::PublicKey::from_spki_pem(whitespace.as_bytes()),
+        Ok(p256_cm4::PublicKey::from_affine(&x, &y).unwrap())
+    );
+}
@@ -0,0 +1,128 @@
+//! Points in affine coordinates.
+
+use crate::{
+    FieldElement, ProjectivePoint, Scalar,
+    sys::{Montgomery, point_is_on_curve},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// A point on the curve in affine coordinates, or the identity.
+///
+/// The `arithmetic` feature adds the RustCrypto `group` and SEC1 encoding traits.
+#[derive(Debug, Copy, Clone)]
+pub struct AffinePoint {
+    pub(crate) x: FieldElement,
+    pub(crate) y: FieldElement,
+    /// 1 for the identity, 0 otherwise.
+    pub(crate) infinity: u8,
+}
+
+impl AffinePoint {
+    /// The identity, the point at infinity.
+    pub const IDENTITY: Self = Self {
+        x: FieldElement::ZERO,
+        y: FieldElement::ZERO,
+        infinity: 1,
+    };
+
+    /// The base point of the curve.
+    pub const GENERATOR: Self = Self {
+        x: FieldElement(Montgomery::new([
+            0x18a9143c, 0x79e730d4, 0x5fedb601, 0x75ba95fc, 0x77622510, 0x79fb732b, 0xa53755c6,
+            0x18905f76,
+        ])),
+        y: FieldElement(Montgomery::new([
+            0xce95560a, 0xddf25357, 0xba19e45c, 0x8b4ab8e4, 0xdd21f325, 0xd2e88688, 0x25885d85,
+            0x8571ff18,
+        ])),
+        infinity: 0,
+    };
+
+    /// Create a point from its coordinates.
+    ///
+    /// Returns `None` if the point is not on the curve.
+    pub fn from_coordinates(x: &FieldElement, y: &FieldElement) -> CtOption<Self> {
+        let on_curve: bool = point_is_on_curve(&x.0, &y.0);
+        let point = Self {
+            x: *x,
+            y: *y,
+            infinity: 0,
+        };
+        CtOption::new(point, Choice::from(on_curve as u8))
+    }
+
+    /// The coordinates of the point.
+    ///
+    /// Returns `None` for the identity, which has no affine coordinates.
+    pub fn coordinates(&self) -> CtOption<(FieldElement, FieldElement)> {
+        CtOption::new((self.x, self.y), !self.is_identity())
+    }
+
+    /// Whether the point is the identity.
+    pub fn is_identity(&self) -> Choice {
+        Choice::from(self.infinity)
+    }
+
+    /// Calculate `-self`.
+    pub fn neg(&self) -> Self {
+        Self {
+            x: self.x,
+            y: FieldElement::conditional_select(&self.y.neg(), &self.y, self.is_identity()),
+            infinity: self.infinity,
+        }
+    }
+}
+
+impl core::ops::Neg for AffinePoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::neg(&self)
+    }
+}
+
+impl core::ops::Mul<Scalar> for AffinePoint {
+    type Output = ProjectivePoint;
+
+    fn mul(self, scalar: Scalar) -> ProjectivePoint {
+        ProjectivePoint::from(self) * scalar
+    }
+}
+
+impl core::ops::Mul<&Scalar> for AffinePoint {
+    type Output = ProjectivePoint;
+
+    fn mul(self, scalar: &Scalar) -> ProjectivePoint {
+        ProjectivePoint::from(self) * scalar
+    }
+}
+
+impl ConditionallySelectable for AffinePoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            x: FieldElement::conditional_select(&a.x, &b.x, choice),
+            y: FieldElement::conditional_select(&a.y, &b.y, choice),
+            infinity: u8::conditional_select(&a.infinity, &b.infinity, choice),
+        }
+    }
+}
+
+impl ConstantTimeEq for AffinePoint {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y) & self.infinity.ct_eq(&other.infinity)
+    }
+}
+
+impl PartialEq for AffinePoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for AffinePoint {}
+
+impl Default for AffinePoint {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
@@ -0,0 +1,115 @@
+//! The [`group`](elliptic_curve::group) and SEC1 encoding traits of [`AffinePoint`].
+
+use super::{FieldBytes, NistP256};
+use crate::{
+    AffinePoint, FieldElement, ProjectivePoint, Scalar, point_to_octet_string_compressed,
+    sys::Montgomery, try_octet_string_to_point,
+};
+use elliptic_curve::{
+    group::{GroupEncoding, prime::PrimeCurveAffine},
+    point::AffineCoordinates,
+    sec1::{CompressedPoint, EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+    subtle::{Choice, CtOption},
+    zeroize::DefaultIsZeroes,
+};
+
+impl AffineCoordinates for AffinePoint {
+    type FieldRepr = FieldBytes;
+
+    fn x(&self) -> FieldBytes {
+        self.x.to_bytes().into()
+    }
+
+    fn y_is_odd(&self) -> Choice {
+        Choice::from((self.y.to_limbs()[0] & 1) as u8)
+    }
+}
+
+impl PrimeCurveAffine for AffinePoint {
+    type Scalar = Scalar;
+    type Curve = ProjectivePoint;
+
+    fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    fn generator() -> Self {
+        Self::GENERATOR
+    }
+
+    fn is_identity(&self) -> Choice {
+        Self::is_identity(self)
+    }
+
+    fn to_curve(&self) -> ProjectivePoint {
+        ProjectivePoint::from(*self)
+    }
+}
+
+impl AffinePoint {
+    /// Decode a point from the SEC1 encoding of a point other than the identity.
+    fn from_sec1(bytes: &[u8]) -> CtOption<Self> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        let valid = try_octet_string_to_point(&mut x, &mut y, bytes).is_ok();
+        let point = Self {
+            x: FieldElement(Montgomery::from(x)),
+            y: FieldElement(Montgomery::from(y)),
+            infinity: 0,
+        };
+        CtOption::new(point, Choice::from(valid as u8))
+    }
+}
+
+/// The compressed SEC1 encoding, with all zeros for the identity.
+impl GroupEncoding for AffinePoint {
+    type Repr = CompressedPoint<NistP256>;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        if bytes.iter().all(|&b| b == 0) {
+            CtOption::new(Self::IDENTITY, Choice::from(1))
+        } else {
+            Self::from_sec1(&bytes[..])
+        }
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_bytes(bytes)
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        let mut out: [u8; 33] = [0; 33];
+        if self.infinity == 0 {
+            point_to_octet_string_compressed(&mut out, &self.x.to_limbs(), &self.y.to_limbs());
+        }
+        let mut repr = Self::Repr::default();
+        repr.copy_from_slice(&out);
+        repr
+    }
+}
+
+impl FromEncodedPoint<NistP256> for AffinePoint {
+    fn from_encoded_point(point: &EncodedPoint<NistP256>) -> CtOption<Self> {
+        if point.is_identity() {
+            CtOption::new(Self::IDENTITY, Choice::from(1))
+        } else {
+            Self::from_sec1(point.as_bytes())
+        }
+    }
+}
+
+impl ToEncodedPoint<NistP256> for AffinePoint {
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint<NistP256> {
+        if self.infinity == 1 {
+            EncodedPoint::<NistP256>::identity()
+        } else {
+            EncodedPoint::<NistP256>::from_affine_coordinates(
+                &self.x.to_bytes().into(),
+                &self.y.to_bytes().into(),
+                compress,
+            )
+        }
+    }
+}
+
+impl DefaultIsZeroes for AffinePoint {}
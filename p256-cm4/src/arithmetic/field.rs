@@ -0,0 +1,89 @@
+//! The [`ff`] traits of [`FieldElement`].
+
+use super::FieldBytes;
+use crate::{FieldElement, sys::Montgomery};
+use elliptic_curve::{
+    ff::{self, Field, PrimeField},
+    rand_core::RngCore,
+    subtle::{Choice, CtOption},
+    zeroize::DefaultIsZeroes,
+};
+
+impl Field for FieldElement {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+
+    fn random(mut rng: impl RngCore) -> Self {
+        // Rejection sampling, the probability of a retry is about 2^-32.
+        let mut bytes = FieldBytes::default();
+        loop {
+            rng.fill_bytes(&mut bytes);
+            if let Some(fe) = Self::from_bytes(&bytes.into()).into() {
+                return fe;
+            }
+        }
+    }
+
+    fn square(&self) -> Self {
+        Self::square(self)
+    }
+
+    fn double(&self) -> Self {
+        Self::double(self)
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        Self::invert(self)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        Self::sqrt(self)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl PrimeField for FieldElement {
+    type Repr = FieldBytes;
+
+    const MODULUS: &'static str =
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+    const NUM_BITS: u32 = 256;
+    const CAPACITY: u32 = 255;
+    const TWO_INV: Self = Self(Montgomery::new([
+        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x80000000,
+    ]));
+    const MULTIPLICATIVE_GENERATOR: Self = Self(Montgomery::new([
+        0x00000006, 0x00000000, 0x00000000, 0xfffffffa, 0xffffffff, 0xffffffff, 0xfffffff9,
+        0x00000005,
+    ]));
+    const S: u32 = 1;
+    // p - 1
+    const ROOT_OF_UNITY: Self = Self(Montgomery::new([
+        0xfffffffe, 0xffffffff, 0xffffffff, 0x00000001, 0x00000000, 0x00000000, 0x00000002,
+        0xfffffffe,
+    ]));
+    const ROOT_OF_UNITY_INV: Self = Self::ROOT_OF_UNITY;
+    // 36
+    const DELTA: Self = Self(Montgomery::new([
+        0x00000024, 0x00000000, 0x00000000, 0xffffffdc, 0xffffffff, 0xffffffff, 0xffffffdb,
+        0x00000023,
+    ]));
+
+    fn from_repr(bytes: FieldBytes) -> CtOption<Self> {
+        Self::from_bytes(&bytes.into())
+    }
+
+    fn to_repr(&self) -> FieldBytes {
+        self.to_bytes().into()
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.to_limbs()[0] & 1) as u8)
+    }
+}
+
+impl DefaultIsZeroes for FieldElement {}
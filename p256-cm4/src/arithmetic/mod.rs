@@ -0,0 +1,27 @@
+//! Implementations of the RustCrypto [`elliptic_curve`] arithmetic traits.
+//!
+//! The types in this module are backed by the same routines as the functions in the crate root,
+//! so generic code written against [`CurveArithmetic`] (ECDH, hash-to-curve, ...) runs on the
+//! assembly.  [`FieldElement`], [`Scalar`], [`AffinePoint`], [`ProjectivePoint`] and their
+//! inherent arithmetic are available without this feature, only the trait implementations are
+//! defined here.
+
+mod affine;
+mod field;
+mod projective;
+mod scalar;
+
+pub(crate) use crate::curve::{FieldBytes, NistP256};
+
+use crate::{AffinePoint, ProjectivePoint, Scalar};
+use elliptic_curve::{CurveArithmetic, PrimeCurveArithmetic};
+
+impl CurveArithmetic for NistP256 {
+    type AffinePoint = AffinePoint;
+    type ProjectivePoint = ProjectivePoint;
+    type Scalar = Scalar;
+}
+
+impl PrimeCurveArithmetic for NistP256 {
+    type CurveGroup = ProjectivePoint;
+}
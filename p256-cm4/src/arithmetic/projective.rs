@@ -0,0 +1,82 @@
+//! The [`group`](elliptic_curve::group) traits of [`ProjectivePoint`].
+
+use super::NistP256;
+use crate::{AffinePoint, ProjectivePoint, Scalar};
+use elliptic_curve::{
+    ff::Field,
+    group::{self, Group, GroupEncoding},
+    ops::{LinearCombination, MulByGenerator},
+    rand_core::RngCore,
+    sec1::CompressedPoint,
+    subtle::{Choice, CtOption},
+    zeroize::DefaultIsZeroes,
+};
+
+impl Group for ProjectivePoint {
+    type Scalar = Scalar;
+
+    fn random(rng: impl RngCore) -> Self {
+        Self::mul_by_generator(&Scalar::random(rng))
+    }
+
+    fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    fn generator() -> Self {
+        Self::GENERATOR
+    }
+
+    fn is_identity(&self) -> Choice {
+        Self::is_identity(self)
+    }
+
+    fn double(&self) -> Self {
+        Self::double(self)
+    }
+}
+
+impl group::Curve for ProjectivePoint {
+    type AffineRepr = AffinePoint;
+
+    fn to_affine(&self) -> AffinePoint {
+        Self::to_affine(self)
+    }
+
+    fn batch_normalize(p: &[Self], q: &mut [AffinePoint]) {
+        Self::batch_to_affine(p, q)
+    }
+}
+
+impl group::prime::PrimeGroup for ProjectivePoint {}
+
+impl group::prime::PrimeCurve for ProjectivePoint {
+    type Affine = AffinePoint;
+}
+
+/// The compressed SEC1 encoding, with all zeros for the identity.
+impl GroupEncoding for ProjectivePoint {
+    type Repr = CompressedPoint<NistP256>;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        AffinePoint::from_bytes(bytes).map(Self::from)
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        AffinePoint::from_bytes_unchecked(bytes).map(Self::from)
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        self.to_affine().to_bytes()
+    }
+}
+
+impl LinearCombination for ProjectivePoint {}
+
+impl MulByGenerator for ProjectivePoint {
+    fn mul_by_generator(scalar: &Scalar) -> Self {
+        Self::mul_by_generator(scalar)
+    }
+}
+
+impl DefaultIsZeroes for ProjectivePoint {}
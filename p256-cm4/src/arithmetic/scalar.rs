@@ -0,0 +1,183 @@
+//! The [`ff`] and [`elliptic_curve`] scalar traits of [`Scalar`].
+
+use super::{FieldBytes, NistP256};
+use crate::{Scalar, types::from_be_bytes};
+use elliptic_curve::{
+    ScalarPrimitive,
+    bigint::{ArrayEncoding, U256},
+    ff::{self, Field, PrimeField},
+    ops::{Invert, Reduce},
+    rand_core::RngCore,
+    scalar::{FromUintUnchecked, IsHigh},
+    subtle::{Choice, CtOption},
+    zeroize::DefaultIsZeroes,
+};
+
+/// `(n - 1) / 2`
+const FRAC_MODULUS_2: [u32; 8] = [
+    0x7e3192a8, 0x79dce561, 0xd38bcf42, 0xde737d56, 0xffffffff, 0x7fffffff, 0x80000000, 0x7fffffff,
+];
+
+impl Field for Scalar {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+
+    fn random(mut rng: impl RngCore) -> Self {
+        // Rejection sampling, the probability of a retry is about 2^-32.
+        let mut bytes = FieldBytes::default();
+        loop {
+            rng.fill_bytes(&mut bytes);
+            if let Some(scalar) = Self::from_bytes(&bytes.into()).into() {
+                return scalar;
+            }
+        }
+    }
+
+    fn square(&self) -> Self {
+        Self::square(self)
+    }
+
+    fn double(&self) -> Self {
+        Self::double(self)
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        Self::invert(self)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        // (t - 1) / 2, where n - 1 = 2^S * t
+        ff::helpers::sqrt_tonelli_shanks(
+            self,
+            [
+                0x279dce5617e3192a,
+                0xfde737d56d38bcf4,
+                0x07ffffffffffffff,
+                0x07fffffff8000000,
+            ],
+        )
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl PrimeField for Scalar {
+    type Repr = FieldBytes;
+
+    const MODULUS: &'static str =
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+    const NUM_BITS: u32 = 256;
+    const CAPACITY: u32 = 255;
+    const TWO_INV: Self = Self([
+        0x7e3192a9, 0x79dce561, 0xd38bcf42, 0xde737d56, 0xffffffff, 0x7fffffff, 0x80000000,
+        0x7fffffff,
+    ]);
+    const MULTIPLICATIVE_GENERATOR: Self = Self([7, 0, 0, 0, 0, 0, 0, 0]);
+    const S: u32 = 4;
+    // 7^t
+    const ROOT_OF_UNITY: Self = Self([
+        0xb41e6602, 0x0592d7fb, 0x04378daf, 0x1546cad0, 0x842a3dfc, 0xba807ace, 0x2a770992,
+        0xffc97f06,
+    ]);
+    const ROOT_OF_UNITY_INV: Self = Self([
+        0x57c73764, 0x379c7f06, 0x794c4137, 0xe3ac117c, 0x58131cae, 0xc645fa04, 0x62d46f2a,
+        0xa0a66a55,
+    ]);
+    // 7^(2^S)
+    const DELTA: Self = Self([0xa5057d81, 0x00001e39, 0, 0, 0, 0, 0, 0]);
+
+    fn from_repr(bytes: FieldBytes) -> CtOption<Self> {
+        Self::from_bytes(&bytes.into())
+    }
+
+    fn to_repr(&self) -> FieldBytes {
+        self.to_bytes().into()
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.0[0] & 1) as u8)
+    }
+}
+
+impl AsRef<Scalar> for Scalar {
+    fn as_ref(&self) -> &Scalar {
+        self
+    }
+}
+
+impl From<Scalar> for FieldBytes {
+    fn from(scalar: Scalar) -> Self {
+        scalar.to_bytes().into()
+    }
+}
+
+impl From<Scalar> for U256 {
+    fn from(scalar: Scalar) -> Self {
+        U256::from_be_byte_array(scalar.to_bytes().into())
+    }
+}
+
+impl From<ScalarPrimitive<NistP256>> for Scalar {
+    fn from(scalar: ScalarPrimitive<NistP256>) -> Self {
+        // `ScalarPrimitive` is always in range
+        Self(from_be_bytes(&scalar.to_bytes().into()))
+    }
+}
+
+impl From<Scalar> for ScalarPrimitive<NistP256> {
+    fn from(scalar: Scalar) -> Self {
+        // cannot fail, the scalar is in range
+        ScalarPrimitive::from_bytes(&scalar.to_bytes().into()).unwrap()
+    }
+}
+
+impl FromUintUnchecked for Scalar {
+    type Uint = U256;
+
+    fn from_uint_unchecked(uint: U256) -> Self {
+        Self(from_be_bytes(&uint.to_be_byte_array().into()))
+    }
+}
+
+impl Reduce<U256> for Scalar {
+    type Bytes = FieldBytes;
+
+    fn reduce(n: U256) -> Self {
+        Self::reduce_bytes(&n.to_be_byte_array())
+    }
+
+    fn reduce_bytes(bytes: &FieldBytes) -> Self {
+        Self::from_bytes_reduced(&(*bytes).into())
+    }
+}
+
+impl Invert for Scalar {
+    type Output = CtOption<Self>;
+
+    fn invert(&self) -> CtOption<Self> {
+        Self::invert(self)
+    }
+}
+
+impl IsHigh for Scalar {
+    fn is_high(&self) -> Choice {
+        // `self > (n - 1) / 2` if and only if `(n - 1) / 2 - self` borrows
+        let borrow = (0..8).fold(0u32, |borrow, i| {
+            let (t, b1) = FRAC_MODULUS_2[i].overflowing_sub(self.0[i]);
+            let (_, b2) = t.overflowing_sub(borrow);
+            (b1 | b2) as u32
+        });
+        Choice::from(borrow as u8)
+    }
+}
+
+impl core::ops::ShrAssign<usize> for Scalar {
+    fn shr_assign(&mut self, rhs: usize) {
+        let value: U256 = (*self).into();
+        *self = Self::from_uint_unchecked(value >> rhs);
+    }
+}
+
+impl DefaultIsZeroes for Scalar {}
@@ -0,0 +1,213 @@
+//! Pointer based wrappers around the register based field arithmetic routines,
+//! callable from rust.
+
+use core::arch::naked_asm;
+
+use super::Montgomery;
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a * b mod p`.
+///
+/// # Inputs
+/// `r0` shall contain a valid [`*mut Montgomery`](Montgomery).
+///
+/// `r1` shall contain `a`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r2` shall contain `b`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r0` and `r1` and/or `r2` may overlap.
+///
+/// # Return
+/// On return, the dereference of the input value of `r0` will contain the result of the computation.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
+#[unsafe(naked)]
+#[unsafe(no_mangle)]
+pub(in crate::sys) unsafe extern "C" fn P256_mul_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    naked_asm!(
+        "
+            push {{r0, r4-r11, lr}}
+            // frame push {{r4-r11, lr}}
+            // frame address sp, 40
+            bl {P256_mulmod}
+            pop {{r8}}
+            // frame address sp, 36
+            stm r8, {{r0-r7}}
+            pop {{r4-r11, pc}}
+        ",
+        P256_mulmod = sym super::P256_mulmod,
+    )
+}
+
+/// Given input `a` in montgomery form, calculate `a^2 mod p`.
+///
+/// # Inputs
+/// `r0` shall contain a valid [`*mut Montgomery`](Montgomery).
+///
+/// `r1` shall contain `a`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r0` and `r1` may overlap.
+///
+/// # Return
+/// On return, the dereference of the input value of `r0` will contain the result of the computation.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
+#[unsafe(naked)]
+#[unsafe(no_mangle)]
+pub(in crate::sys) unsafe extern "C" fn P256_sqr_mod_p(res: *mut Montgomery, a: *const Montgomery) {
+    naked_asm!(
+        "
+            push {{r0, r4-r11, lr}}
+            // frame push {{r4-r11, lr}}
+            // frame address sp, 40
+            ldm r1, {{r0-r7}}
+            bl {P256_sqrmod}
+            pop {{r8}}
+            // frame address sp, 36
+            stm r8, {{r0-r7}}
+            pop {{r4-r11, pc}}
+        ",
+        P256_sqrmod = sym super::P256_sqrmod,
+    )
+}
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a + b mod p`.
+///
+/// # Inputs
+/// `r0` shall contain a valid [`*mut Montgomery`](Montgomery).
+///
+/// `r1` shall contain `a`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r2` shall contain `b`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r0` and `r1` and/or `r2` may overlap.
+///
+/// # Return
+/// On return, the dereference of the input value of `r0` will contain the result of the computation.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
+#[unsafe(naked)]
+#[unsafe(no_mangle)]
+pub(in crate::sys) unsafe extern "C" fn P256_add_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    naked_asm!(
+        "
+            push {{r0, r4-r11, lr}}
+            // frame push {{r4-r11, lr}}
+            // frame address sp, 40
+            bl {P256_addmod}
+            pop {{r8}}
+            // frame address sp, 36
+            stm r8, {{r0-r7}}
+            pop {{r4-r11, pc}}
+        ",
+        P256_addmod = sym super::P256_addmod,
+    )
+}
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a - b mod p`.
+///
+/// # Inputs
+/// `r0` shall contain a valid [`*mut Montgomery`](Montgomery).
+///
+/// `r1` shall contain `a`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r2` shall contain `b`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r0` and `r1` and/or `r2` may overlap.
+///
+/// # Return
+/// On return, the dereference of the input value of `r0` will contain the result of the computation.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
+#[unsafe(naked)]
+#[unsafe(no_mangle)]
+pub(in crate::sys) unsafe extern "C" fn P256_sub_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    naked_asm!(
+        "
+            push {{r0, r4-r11, lr}}
+            // frame push {{r4-r11, lr}}
+            // frame address sp, 40
+            bl {P256_submod}
+            pop {{r8}}
+            // frame address sp, 36
+            stm r8, {{r0-r7}}
+            pop {{r4-r11, pc}}
+        ",
+        P256_submod = sym super::P256_submod,
+    )
+}
+
+/// For input `a` in montgomery form and mode `mode`:
+/// 1. If `mode == modinv`, calculate `a^-1 mod p`
+/// 2. If `mode == sqrt`, calculate a candidate for `sqrt(a) mod p`
+///
+/// If `a` has no square root, the result of the sqrt mode is the square root of `-a`,
+/// so the caller must check the result by squaring it.
+///
+/// # Inputs
+/// `r0` shall contain a valid [`*mut Montgomery`](Montgomery).
+///
+/// `r1` shall contain `a`, a valid [`*const Montgomery`](Montgomery).
+///
+/// `r2` shall contain `mode`, where `0 = modinv` and `1 = sqrt`.
+///
+/// `r0` and `r1` may overlap.
+///
+/// # Return
+/// On return, the dereference of the input value of `r0` will contain the result of the computation.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
+#[unsafe(naked)]
+#[unsafe(no_mangle)]
+pub(in crate::sys) unsafe extern "C" fn P256_modinv_sqrt_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    mode: u32,
+) {
+    naked_asm!(
+        "
+            push {{r0, r4-r11, lr}}
+            // frame push {{r4-r11, lr}}
+            // frame address sp, 40
+            mov r8, r2
+            ldm r1, {{r0-r7}}
+            bl {P256_modinv_sqrt}
+            pop {{r8}}
+            // frame address sp, 36
+            stm r8, {{r0-r7}}
+            pop {{r4-r11, pc}}
+        ",
+        P256_modinv_sqrt = sym super::P256_modinv_sqrt,
+    )
+}
@@ -6,6 +6,8 @@ mod sqrmod;
 pub(crate) use sqrmod::P256_sqrmod;
 
 pub mod add_sub;
+
+pub(crate) mod field;
 pub(crate) use add_sub::{P256_addmod, P256_submod};
 
 pub(crate) mod jacobian;
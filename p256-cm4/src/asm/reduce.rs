@@ -96,21 +96,26 @@ pub(in crate::sys) unsafe extern "C" fn P256_reduce_mod_n_32bytes(
     )
 }
 
-/// Given a number `a`, do something. TODO: figure out what the heck this actually does.
+/// Given a number `a < 2^512`, compute `a mod n`, where `n` is the P256 order,
+/// using Barrett reduction.
 ///
 /// # Inputs
-/// `r0` shall contain a valid `*mut [u8; 8]`.
+/// `r0` shall contain a valid `*mut [u32; 8]`.
 ///
-/// `r1` shall contain `a`, a valid `*const [u8; 9]`.
+/// `r1` shall contain `a`, a valid `*const [u32; 16]`.
 ///
 /// # Return
 /// On return, the dereference of the input value of `r0` shall contain the result of the operation.
 ///
-/// > **Note**: this function clobbers all registers.
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration of the function call,
+/// and that `res` is valid for writes.
+///
+/// > **Note**: This function adheres to the ARM calling convention.
 #[unsafe(link_section = ".p256-cortex-m4")]
 #[unsafe(naked)]
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn P256_reduce_mod_n_64bytes() {
+pub unsafe extern "C" fn P256_reduce_mod_n_64bytes(res: *mut [u32; 8], a: *const [u32; 16]) {
     naked_asm!(
         "
             push {{r0, r4-r11, lr}}
@@ -0,0 +1,86 @@
+//! Base64 of RFC 4648, with the standard alphabet for PEM and the URL safe alphabet for JWK.
+
+#[cfg(feature = "pem")]
+pub(crate) const STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+#[cfg(feature = "jwk")]
+pub(crate) const URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Length of the base64 encoding of "len" bytes, with or without padding.
+pub(crate) const fn encoded_len(len: usize, padded: bool) -> usize {
+    if padded {
+        len.div_ceil(3) * 4
+    } else {
+        (len * 4).div_ceil(3)
+    }
+}
+
+/// Encodes "data" into "out", which must be `encoded_len(data.len(), padded)` bytes long.
+pub(crate) fn encode(out: &mut [u8], data: &[u8], alphabet: &[u8; 64], padded: bool) {
+    data.chunks(3)
+        .zip(out.chunks_mut(4))
+        .for_each(|(chunk, quantum)| {
+            let b: [u8; 3] = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n: u32 = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+            // one byte is two characters, two bytes are three characters
+            let chars: usize = chunk.len() + 1;
+            quantum.iter_mut().enumerate().for_each(|(i, c)| {
+                *c = if i < chars {
+                    alphabet[(n >> (18 - 6 * i)) as usize & 0x3f]
+                } else {
+                    debug_assert!(padded);
+                    b'='
+                };
+            });
+        });
+}
+
+/// Decodes "input" into "out", returns the decoded data.
+///
+/// Returns `None` for characters outside of the alphabet, non-canonical encodings, missing or
+/// unexpected padding, and if the decoded data does not fit in "out".
+pub(crate) fn decode<'a>(
+    out: &'a mut [u8],
+    input: impl Iterator<Item = u8>,
+    alphabet: &[u8; 64],
+    padded: bool,
+) -> Option<&'a [u8]> {
+    let mut len: usize = 0;
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut data_chars: usize = 0;
+    let mut padding: usize = 0;
+    for c in input {
+        if c == b'=' && padded {
+            padding += 1;
+            continue;
+        }
+        // padding is only allowed at the end
+        if padding != 0 {
+            return None;
+        }
+        let value: usize = alphabet.iter().position(|&a| a == c)?;
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        data_chars += 1;
+        if bits >= 8 {
+            bits -= 8;
+            *out.get_mut(len)? = (acc >> bits) as u8;
+            len += 1;
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // a single character in the last quantum does not encode a byte, and the unused bits of
+    // the last character must be zero
+    let expected_padding: usize = if padded { (4 - data_chars % 4) % 4 } else { 0 };
+    if data_chars % 4 == 1 || padding != expected_padding || acc != 0 {
+        return None;
+    }
+    Some(&out[..len])
+}
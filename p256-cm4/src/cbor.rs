@@ -0,0 +1,189 @@
+//! The subset of CBOR, RFC 8949, used by COSE.
+//!
+//! Only definite lengths are supported, indefinite length items are rejected when reading.
+
+use crate::Error;
+
+pub(crate) const MAJOR_UNSIGNED: u8 = 0;
+pub(crate) const MAJOR_NEGATIVE: u8 = 1;
+pub(crate) const MAJOR_BYTES: u8 = 2;
+pub(crate) const MAJOR_TEXT: u8 = 3;
+pub(crate) const MAJOR_ARRAY: u8 = 4;
+pub(crate) const MAJOR_MAP: u8 = 5;
+pub(crate) const MAJOR_TAG: u8 = 6;
+
+pub(crate) const FALSE: u8 = 0xf4;
+pub(crate) const TRUE: u8 = 0xf5;
+
+// Nesting limit of skipped items, which bounds the recursion.
+const MAX_DEPTH: usize = 8;
+
+/// The shortest head of an item, returns the buffer and the number of bytes used.
+pub(crate) fn head(major: u8, value: u64) -> ([u8; 9], usize) {
+    let mut out: [u8; 9] = [0; 9];
+    let len: usize = match value {
+        0..=23 => {
+            out[0] = (major << 5) | value as u8;
+            return (out, 1);
+        }
+        24..=0xff => 1,
+        0x100..=0xffff => 2,
+        0x1_0000..=0xffff_ffff => 4,
+        _ => 8,
+    };
+    // 24, 25, 26 and 27 for 1, 2, 4 and 8 byte arguments
+    out[0] = (major << 5) | (24 + len.trailing_zeros() as u8);
+    out[1..1 + len].copy_from_slice(&value.to_be_bytes()[8 - len..]);
+    (out, 1 + len)
+}
+
+/// Length of the shortest head of an item.
+pub(crate) const fn head_len(value: u64) -> usize {
+    match value {
+        0..=23 => 1,
+        24..=0xff => 2,
+        0x100..=0xffff => 3,
+        0x1_0000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+/// Writes CBOR into a buffer.
+pub(crate) struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Writer<'a> {
+    pub(crate) fn new(out: &'a mut [u8]) -> Self {
+        Self { out, pos: 0 }
+    }
+
+    pub(crate) fn raw(&mut self, bytes: &[u8]) {
+        self.out[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+    }
+
+    pub(crate) fn head(&mut self, major: u8, value: u64) {
+        let (head, len) = head(major, value);
+        self.raw(&head[..len]);
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.head(MAJOR_BYTES, bytes.len() as u64);
+        self.raw(bytes);
+    }
+
+    pub(crate) fn int(&mut self, value: i64) {
+        if value < 0 {
+            self.head(MAJOR_NEGATIVE, !value as u64);
+        } else {
+            self.head(MAJOR_UNSIGNED, value as u64);
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+/// Reads CBOR items from the front of the input.
+#[derive(Clone, Copy)]
+pub(crate) struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.input.len() < len {
+            return Err(Error::InvalidCose);
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    /// The first byte of the next item, without consuming it.
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.input.first().copied()
+    }
+
+    /// Reads the head of an item, returns the major type and the argument.
+    pub(crate) fn head(&mut self) -> Result<(u8, u64), Error> {
+        let initial: u8 = self.take(1)?[0];
+        let major: u8 = initial >> 5;
+        let value: u64 = match initial & 0x1f {
+            value @ 0..=23 => value.into(),
+            // 24, 25, 26 and 27 for 1, 2, 4 and 8 byte arguments
+            additional @ 24..=27 => {
+                let len: usize = 1 << (additional - 24);
+                let mut bytes: [u8; 8] = [0; 8];
+                bytes[8 - len..].copy_from_slice(self.take(len)?);
+                u64::from_be_bytes(bytes)
+            }
+            // reserved, or indefinite length
+            _ => return Err(Error::InvalidCose),
+        };
+        Ok((major, value))
+    }
+
+    /// Reads a head of the given major type, returns the argument.
+    pub(crate) fn expect(&mut self, major: u8) -> Result<u64, Error> {
+        match self.head()? {
+            (m, value) if m == major => Ok(value),
+            _ => Err(Error::InvalidCose),
+        }
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len: u64 = self.expect(MAJOR_BYTES)?;
+        self.take(usize::try_from(len).map_err(|_| Error::InvalidCose)?)
+    }
+
+    /// Reads an integer that fits in an `i64`.
+    pub(crate) fn int(&mut self) -> Result<i64, Error> {
+        let (major, value) = self.head()?;
+        let value: i64 = i64::try_from(value).map_err(|_| Error::InvalidCose)?;
+        match major {
+            MAJOR_UNSIGNED => Ok(value),
+            MAJOR_NEGATIVE => Ok(-1 - value),
+            _ => Err(Error::InvalidCose),
+        }
+    }
+
+    /// Skips an item, including all items nested in it.
+    pub(crate) fn skip(&mut self) -> Result<(), Error> {
+        self.skip_nested(0)
+    }
+
+    fn skip_nested(&mut self, depth: usize) -> Result<(), Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::InvalidCose);
+        }
+        let (major, value) = self.head()?;
+        match major {
+            MAJOR_BYTES | MAJOR_TEXT => {
+                self.take(usize::try_from(value).map_err(|_| Error::InvalidCose)?)?;
+            }
+            MAJOR_ARRAY | MAJOR_MAP => {
+                let items: u64 = if major == MAJOR_MAP { 2 * value } else { value };
+                // every item takes at least a byte
+                if items > self.input.len() as u64 {
+                    return Err(Error::InvalidCose);
+                }
+                (0..items).try_for_each(|_| self.skip_nested(depth + 1))?;
+            }
+            MAJOR_TAG => self.skip_nested(depth + 1)?,
+            _ => (),
+        }
+        Ok(())
+    }
+}
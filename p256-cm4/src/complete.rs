@@ -0,0 +1,130 @@
+//! Complete point addition and doubling in homogeneous projective coordinates, where the
+//! point (X, Y, Z) has the affine coordinates (X/Z, Y/Z) and the point at infinity is (0, 1, 0).
+//!
+//! Unlike the Jacobian routines of the assembly, these formulas have no exceptional cases for the
+//! point at infinity or for equal operands, so their execution time does not depend on the
+//! points.  They are algorithms 4, 5 and 6 for `a = -3` of Renes, Costello and Batina,
+//! "Complete addition formulas for prime order elliptic curves",
+//! <https://eprint.iacr.org/2015/1060>.
+
+use crate::{FieldElement, sys::Montgomery};
+
+/// A point in homogeneous projective coordinates.
+pub(crate) type Projective = [FieldElement; 3];
+
+/// The point at infinity.
+pub(crate) const IDENTITY: Projective = [FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO];
+
+/// The curve parameter `b`, in montgomery form.
+const B: FieldElement = FieldElement(Montgomery::new([
+    0x29c4bddf, 0xd89cdf62, 0x78843090, 0xacf005cd, 0xf7212ed6, 0xe5a220ab, 0x04874834, 0xdc30061d,
+]));
+
+/// Calculates `a + b`.
+pub(crate) fn add(a: &Projective, b: &Projective) -> Projective {
+    let [x1, y1, z1] = a;
+    let [x2, y2, z2] = b;
+
+    // 1-3
+    let xx = x1 * x2;
+    let yy = y1 * y2;
+    let zz = z1 * z2;
+    // 4-18
+    let xy_pairs = (x1 + y1) * (x2 + y2) - (xx + yy);
+    let yz_pairs = (y1 + z1) * (y2 + z2) - (yy + zz);
+    let xz_pairs = (x1 + z1) * (x2 + z2) - (xx + zz);
+
+    // 19-24
+    let bzz_part = xz_pairs - B * zz;
+    let bzz3_part = bzz_part.double() + bzz_part;
+    let yy_m_bzz3 = yy - bzz3_part;
+    let yy_p_bzz3 = yy + bzz3_part;
+
+    // 25-34
+    let zz3 = zz.double() + zz;
+    let bxz_part = B * xz_pairs - (zz3 + xx);
+    let bxz3_part = bxz_part.double() + bxz_part;
+    let xx3_m_zz3 = xx.double() + xx - zz3;
+
+    // 35-43
+    [
+        yy_p_bzz3 * xy_pairs - yz_pairs * bxz3_part,
+        yy_p_bzz3 * yy_m_bzz3 + xx3_m_zz3 * bxz3_part,
+        yy_m_bzz3 * yz_pairs + xy_pairs * xx3_m_zz3,
+    ]
+}
+
+/// Calculates `a + (x, y)`, for a point `(x, y)` in affine coordinates, which is not the point at
+/// infinity.
+pub(crate) fn add_affine(a: &Projective, b: &[Montgomery; 2]) -> Projective {
+    let [x1, y1, z1] = a;
+    let (x2, y2) = (FieldElement(b[0]), FieldElement(b[1]));
+
+    // 1-7
+    let xx = x1 * x2;
+    let yy = y1 * y2;
+    let xy_pairs = (x1 + y1) * (x2 + y2) - (xx + yy);
+    // 8-11
+    let yz_pairs = y2 * z1 + y1;
+    let xz_pairs = x2 * z1 + x1;
+
+    // 12-17
+    let bz_part = xz_pairs - B * z1;
+    let bz3_part = bz_part.double() + bz_part;
+    let yy_m_bzz3 = yy - bz3_part;
+    let yy_p_bzz3 = yy + bz3_part;
+
+    // 18-27
+    let z3 = z1.double() + z1;
+    let bxz_part = B * xz_pairs - (z3 + xx);
+    let bxz3_part = bxz_part.double() + bxz_part;
+    let xx3_m_zz3 = xx.double() + xx - z3;
+
+    // 28-36
+    [
+        yy_p_bzz3 * xy_pairs - yz_pairs * bxz3_part,
+        yy_p_bzz3 * yy_m_bzz3 + xx3_m_zz3 * bxz3_part,
+        yy_m_bzz3 * yz_pairs + xy_pairs * xx3_m_zz3,
+    ]
+}
+
+/// Calculates `2 * a`.
+pub(crate) fn double(a: &Projective) -> Projective {
+    let [x, y, z] = a;
+
+    // 1-7
+    let xx = x.square();
+    let yy = y.square();
+    let zz = z.square();
+    let xy2 = (x * y).double();
+    let xz2 = (x * z).double();
+
+    // 8-15
+    let bzz_part = B * zz - xz2;
+    let bzz3_part = bzz_part.double() + bzz_part;
+    let yy_m_bzz3 = yy - bzz3_part;
+    let yy_p_bzz3 = yy + bzz3_part;
+    let y_frag = yy_p_bzz3 * yy_m_bzz3;
+    let x_frag = yy_m_bzz3 * xy2;
+
+    // 16-25
+    let zz3 = zz.double() + zz;
+    let bxz2_part = B * xz2 - (zz3 + xx);
+    let bxz6_part = bxz2_part.double() + bxz2_part;
+    let xx3_m_zz3 = xx.double() + xx - zz3;
+
+    // 26-34
+    let yz2 = (y * z).double();
+    [
+        x_frag - bxz6_part * yz2,
+        y_frag + xx3_m_zz3 * bxz6_part,
+        (yz2 * yy).double().double(),
+    ]
+}
+
+/// Converts a point to Jacobian coordinates, (X*Z, Y*Z^2, Z), which keeps Z = 0 for the point at
+/// infinity.
+pub(crate) fn to_jacobian(a: &Projective) -> [Montgomery; 3] {
+    let [x, y, z] = a;
+    [(x * z).0, (y * z.square()).0, z.0]
+}
@@ -0,0 +1,346 @@
+//! COSE of RFC 9052 and RFC 9053, the `COSE_Key` of a P-256 public key and `COSE_Sign1`
+//! messages signed with ES256.
+//!
+//! ```text
+//! COSE_Key = { 1 => 2, -1 => 1, -2 => x : bstr, -3 => y : bstr / bool }
+//!
+//! COSE_Sign1 = [
+//!     protected : bstr .cbor { 1 => -7 },
+//!     unprotected : { * label => values },
+//!     payload : bstr,
+//!     signature : bstr .size 64,
+//! ]
+//! ```
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error,
+    cbor::{
+        self, MAJOR_ARRAY, MAJOR_BYTES, MAJOR_MAP, MAJOR_NEGATIVE, MAJOR_TAG, MAJOR_TEXT,
+        MAJOR_UNSIGNED, Reader, Writer,
+    },
+    try_octet_string_to_point, try_sign, try_verify,
+    types::{from_be_bytes, to_be_bytes},
+};
+
+// Labels and values of COSE_Key, from the IANA COSE registries.
+const LABEL_KTY: i64 = 1;
+const LABEL_ALG: i64 = 3;
+const LABEL_CRV: i64 = -1;
+const LABEL_X: i64 = -2;
+const LABEL_Y: i64 = -3;
+const KTY_EC2: i64 = 2;
+const CRV_P256: i64 = 1;
+
+// Header parameter labels.
+const HEADER_ALG: i64 = 1;
+const HEADER_CRIT: i64 = 2;
+
+const ALG_ES256: i64 = -7;
+
+const TAG_COSE_SIGN1: u64 = 18;
+
+// The serialized protected header of signed messages, `{ 1 => -7 }`.
+const PROTECTED: [u8; 3] = [0xa1, 0x01, 0x26];
+
+const SIGNATURE1_CONTEXT: &[u8] = b"Signature1";
+
+/// Length of a `COSE_Key` encoded public key.
+pub const COSE_KEY_LEN: usize = 75;
+
+/// Length of a tagged `COSE_Sign1` message with a payload of "payload_len" bytes.
+pub const fn cose_sign1_len(payload_len: usize) -> usize {
+    // tag, array, protected header, empty unprotected header
+    let header_len: usize = 1 + 1 + 1 + PROTECTED.len() + 1;
+    header_len + cbor::head_len(payload_len as u64) + payload_len + 2 + 64
+}
+
+/// Encodes a public key as a `COSE_Key` with an uncompressed point.
+///
+/// The map contains the key type, curve and coordinates, in the deterministic encoding of
+/// RFC 8949.
+pub fn point_to_cose_key(out: &mut [u8; COSE_KEY_LEN], x: &[u32; 8], y: &[u32; 8]) {
+    let mut writer: Writer = Writer::new(out);
+    writer.head(MAJOR_MAP, 4);
+    writer.int(LABEL_KTY);
+    writer.int(KTY_EC2);
+    writer.int(LABEL_CRV);
+    writer.int(CRV_P256);
+    writer.int(LABEL_X);
+    writer.bytes(&to_be_bytes(x));
+    writer.int(LABEL_Y);
+    writer.bytes(&to_be_bytes(y));
+    debug_assert_eq!(writer.position(), COSE_KEY_LEN);
+}
+
+// Reads a map key, returns `None` for keys that are not integers after skipping the key and
+// its value.
+fn integer_label(reader: &mut Reader<'_>) -> Result<Option<i64>, Error> {
+    match reader.peek().map(|initial| initial >> 5) {
+        Some(MAJOR_UNSIGNED | MAJOR_NEGATIVE) => reader.int().map(Some),
+        _ => {
+            reader.skip()?;
+            reader.skip()?;
+            Ok(None)
+        }
+    }
+}
+
+// Reads a 32 byte big-endian integer.
+fn read_integer(reader: &mut Reader<'_>) -> Result<[u8; 32], Error> {
+    reader.bytes()?.try_into().map_err(|_| Error::InvalidCose)
+}
+
+// The y coordinate, or its sign bit for compressed points.
+enum Y {
+    Coordinate([u8; 32]),
+    SignBit(bool),
+}
+
+fn decode_cose_key(x: &mut [u32; 8], y: &mut [u32; 8], cose_key: &[u8]) -> Result<(), Error> {
+    let mut reader: Reader = Reader::new(cose_key);
+    let mut kty: Option<i64> = None;
+    let mut crv: Option<i64> = None;
+    let mut alg: Option<i64> = None;
+    let mut x_bytes: Option<[u8; 32]> = None;
+    let mut y_bytes: Option<Y> = None;
+
+    let entries: u64 = reader.expect(MAJOR_MAP)?;
+    for _ in 0..entries {
+        let Some(label) = integer_label(&mut reader)? else {
+            continue;
+        };
+        let duplicate: bool = match label {
+            LABEL_KTY => kty.replace(reader.int()?).is_some(),
+            LABEL_CRV => crv.replace(reader.int()?).is_some(),
+            LABEL_ALG => alg.replace(reader.int()?).is_some(),
+            LABEL_X => x_bytes.replace(read_integer(&mut reader)?).is_some(),
+            LABEL_Y => {
+                let value: Y = match reader.peek() {
+                    Some(initial @ (cbor::FALSE | cbor::TRUE)) => {
+                        reader.skip()?;
+                        Y::SignBit(initial == cbor::TRUE)
+                    }
+                    _ => Y::Coordinate(read_integer(&mut reader)?),
+                };
+                y_bytes.replace(value).is_some()
+            }
+            _ => {
+                reader.skip()?;
+                false
+            }
+        };
+        if duplicate {
+            return Err(Error::InvalidCose);
+        }
+    }
+    if !reader.is_empty()
+        || kty != Some(KTY_EC2)
+        || crv != Some(CRV_P256)
+        || alg.is_some_and(|alg| alg != ALG_ES256)
+    {
+        return Err(Error::InvalidCose);
+    }
+    let (Some(x_bytes), Some(y_bytes)) = (x_bytes, y_bytes) else {
+        return Err(Error::InvalidCose);
+    };
+
+    // the range and curve checks of the SEC1 encodings
+    match y_bytes {
+        Y::Coordinate(y_bytes) => {
+            let mut point: [u8; 65] = [0x04; 65];
+            point[1..33].copy_from_slice(&x_bytes);
+            point[33..].copy_from_slice(&y_bytes);
+            try_octet_string_to_point(x, y, &point)
+        }
+        Y::SignBit(odd) => {
+            let mut point: [u8; 33] = [0x02 + u8::from(odd); 33];
+            point[1..].copy_from_slice(&x_bytes);
+            try_octet_string_to_point(x, y, &point)
+        }
+    }
+}
+
+/// Decodes a `COSE_Key` into the affine coordinates of the public key.
+///
+/// The key type must be EC2 and the curve P-256.  The y coordinate may be given as its sign bit
+/// for a compressed point.  The algorithm must be ES256 if present, and other parameters, such
+/// as a private key, are ignored.
+#[must_use]
+pub fn cose_key_to_point(x: &mut [u32; 8], y: &mut [u32; 8], cose_key: &[u8]) -> bool {
+    try_cose_key_to_point(x, y, cose_key).is_ok()
+}
+
+/// Same as [`cose_key_to_point`], but returns the reason for a failure.
+pub fn try_cose_key_to_point(
+    x: &mut [u32; 8],
+    y: &mut [u32; 8],
+    cose_key: &[u8],
+) -> Result<(), Error> {
+    let result: Result<(), Error> = decode_cose_key(x, y, cose_key);
+    if result.is_err() {
+        x.fill(0);
+        y.fill(0);
+    }
+    result
+}
+
+// Hashes the `Sig_structure` of a `COSE_Sign1`, without serializing it to a buffer.
+fn sig_structure_hash(protected: &[u8], external_aad: &[u8], payload: &[u8]) -> [u8; 32] {
+    let mut sha: Sha256 = Sha256::new();
+    let (head, len) = cbor::head(MAJOR_ARRAY, 4);
+    sha.update(&head[..len]);
+    [
+        (MAJOR_TEXT, SIGNATURE1_CONTEXT),
+        (MAJOR_BYTES, protected),
+        (MAJOR_BYTES, external_aad),
+        (MAJOR_BYTES, payload),
+    ]
+    .iter()
+    .for_each(|(major, value)| {
+        let (head, len) = cbor::head(*major, value.len() as u64);
+        sha.update(&head[..len]);
+        sha.update(value);
+    });
+    sha.finalize().into()
+}
+
+/// Creates a tagged `COSE_Sign1` message of "payload", signed with ES256.
+///
+/// The message is written to the start of "out", and is [`cose_sign1_len`] bytes long.  The
+/// protected header contains the algorithm, and the unprotected header is empty.  "external_aad"
+/// is authenticated but not included in the message, use an empty slice if there is none.
+///
+/// "k" is used as in [`sign`](crate::sign), and "out" is only written if signing succeeds.
+#[must_use]
+pub fn cose_sign1(
+    out: &mut [u8],
+    payload: &[u8],
+    external_aad: &[u8],
+    private_key: &[u32; 8],
+    k: &[u32; 8],
+) -> bool {
+    try_cose_sign1(out, payload, external_aad, private_key, k).is_ok()
+}
+
+/// Same as [`cose_sign1`], but returns the reason for a failure.
+pub fn try_cose_sign1(
+    out: &mut [u8],
+    payload: &[u8],
+    external_aad: &[u8],
+    private_key: &[u32; 8],
+    k: &[u32; 8],
+) -> Result<(), Error> {
+    if out.len() < cose_sign1_len(payload.len()) {
+        return Err(Error::BufferTooSmall);
+    }
+    let hash: [u8; 32] = sig_structure_hash(&PROTECTED, external_aad, payload);
+    let mut r: [u32; 8] = [0; 8];
+    let mut s: [u32; 8] = [0; 8];
+    try_sign(&mut r, &mut s, &hash, private_key, k)?;
+
+    let mut signature: [u8; 64] = [0; 64];
+    signature[..32].copy_from_slice(&to_be_bytes(&r));
+    signature[32..].copy_from_slice(&to_be_bytes(&s));
+
+    let mut writer: Writer = Writer::new(out);
+    writer.head(MAJOR_TAG, TAG_COSE_SIGN1);
+    writer.head(MAJOR_ARRAY, 4);
+    writer.bytes(&PROTECTED);
+    writer.head(MAJOR_MAP, 0);
+    writer.bytes(payload);
+    writer.bytes(&signature);
+    debug_assert_eq!(writer.position(), cose_sign1_len(payload.len()));
+    Ok(())
+}
+
+// Reads a header map, and records the algorithm.  Critical headers are not supported.
+fn read_header(reader: &mut Reader<'_>, alg: &mut Option<i64>) -> Result<(), Error> {
+    let entries: u64 = reader.expect(MAJOR_MAP)?;
+    for _ in 0..entries {
+        let invalid: bool = match integer_label(reader)? {
+            Some(HEADER_ALG) => alg.replace(reader.int()?).is_some(),
+            Some(HEADER_CRIT) => true,
+            Some(_) => {
+                reader.skip()?;
+                false
+            }
+            None => false,
+        };
+        if invalid {
+            return Err(Error::InvalidCose);
+        }
+    }
+    Ok(())
+}
+
+fn decode_sign1<'a>(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    cose_sign1: &'a [u8],
+    external_aad: &[u8],
+) -> Result<&'a [u8], Error> {
+    let mut reader: Reader = Reader::new(cose_sign1);
+    if reader.peek().map(|initial| initial >> 5) == Some(MAJOR_TAG)
+        && reader.expect(MAJOR_TAG)? != TAG_COSE_SIGN1
+    {
+        return Err(Error::InvalidCose);
+    }
+    if reader.expect(MAJOR_ARRAY)? != 4 {
+        return Err(Error::InvalidCose);
+    }
+
+    let mut alg: Option<i64> = None;
+    let protected: &[u8] = reader.bytes()?;
+    // an empty protected header may be encoded as a zero length string
+    if !protected.is_empty() {
+        let mut protected_reader: Reader = Reader::new(protected);
+        read_header(&mut protected_reader, &mut alg)?;
+        if !protected_reader.is_empty() {
+            return Err(Error::InvalidCose);
+        }
+    }
+    read_header(&mut reader, &mut alg)?;
+    if alg != Some(ALG_ES256) {
+        return Err(Error::InvalidCose);
+    }
+
+    // detached payloads are not supported
+    let payload: &[u8] = reader.bytes()?;
+    let signature: &[u8; 64] = reader.bytes()?.try_into().map_err(|_| Error::InvalidCose)?;
+    if !reader.is_empty() {
+        return Err(Error::InvalidCose);
+    }
+
+    let r: [u32; 8] = from_be_bytes(signature[..32].try_into().unwrap());
+    let s: [u32; 8] = from_be_bytes(signature[32..].try_into().unwrap());
+    let hash: [u8; 32] = sig_structure_hash(protected, external_aad, payload);
+    try_verify(public_key_x, public_key_y, &hash, &r, &s)?;
+    Ok(payload)
+}
+
+/// Verifies a `COSE_Sign1` message signed with ES256.
+///
+/// The message may be tagged or untagged, the algorithm must be ES256 and given in exactly one
+/// of the headers, and the payload must be included in the message.
+#[must_use = "The return value indicates if the message is authentic"]
+pub fn cose_sign1_verify(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    cose_sign1: &[u8],
+    external_aad: &[u8],
+) -> bool {
+    try_cose_sign1_verify(public_key_x, public_key_y, cose_sign1, external_aad).is_ok()
+}
+
+/// Same as [`cose_sign1_verify`], but returns the authenticated payload, or the reason for a
+/// failure.
+pub fn try_cose_sign1_verify<'a>(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    cose_sign1: &'a [u8],
+    external_aad: &[u8],
+) -> Result<&'a [u8], Error> {
+    decode_sign1(public_key_x, public_key_y, cose_sign1, external_aad)
+}
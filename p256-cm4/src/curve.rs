@@ -0,0 +1,25 @@
+//! The curve type of the RustCrypto integrations.
+//!
+//! [`NistP256`] is shared by the `arithmetic` and `rustcrypto` features, so the arithmetic types
+//! and the `ecdsa` signatures of this crate refer to the same curve.
+
+use elliptic_curve::{Curve, FieldBytesEncoding, PrimeCurve, bigint::U256, consts::U32};
+
+/// Big-endian encoding of a field element or scalar.
+pub type FieldBytes = elliptic_curve::FieldBytes<NistP256>;
+
+/// The NIST P-256 elliptic curve, with the arithmetic of this crate.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NistP256;
+
+impl Curve for NistP256 {
+    type FieldBytesSize = U32;
+    type Uint = U256;
+
+    const ORDER: U256 =
+        U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+}
+
+impl PrimeCurve for NistP256 {}
+
+impl FieldBytesEncoding<NistP256> for U256 {}
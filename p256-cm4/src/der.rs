@@ -0,0 +1,151 @@
+//! DER encoding of ECDSA signatures, the `ECDSA-Sig-Value` of RFC 3279:
+//!
+//! ```text
+//! ECDSA-Sig-Value ::= SEQUENCE {
+//!     r INTEGER,
+//!     s INTEGER
+//! }
+//! ```
+
+use crate::{Error, check_range_n, convert_endianness, u32x8_to_u8x32, u32x8_to_u8x32_mut};
+
+pub(crate) const TAG_SEQUENCE: u8 = 0x30;
+pub(crate) const TAG_INTEGER: u8 = 0x02;
+
+/// Maximum length of a DER encoded signature.
+///
+/// Both integers take at most 33 bytes, 32 bytes and a leading zero if the most significant
+/// bit is set, plus 2 bytes for their tag and length and 2 bytes for the sequence.
+pub const MAX_DER_SIGNATURE_LEN: usize = 72;
+
+// Writes the tag, length and minimal big-endian value of a positive integer,
+// returns the number of bytes written.
+fn encode_integer(out: &mut [u8], value: &[u32; 8]) -> usize {
+    let mut be: [u8; 32] = [0; 32];
+    convert_endianness(&mut be, u32x8_to_u8x32(value));
+
+    // r and s are not zero, so at least one byte remains
+    let leading_zeros: usize = be.iter().take_while(|&&b| b == 0).count().min(31);
+    let value: &[u8] = &be[leading_zeros..];
+    // a set most significant bit would make the integer negative
+    let pad: usize = (value[0] >> 7) as usize;
+    let len: usize = pad + value.len();
+
+    out[0] = TAG_INTEGER;
+    out[1] = len as u8;
+    out[2] = 0;
+    out[2 + pad..2 + len].copy_from_slice(value);
+    2 + len
+}
+
+/// Encodes a signature as DER.
+///
+/// Returns the length of the encoding, which is written to the start of "out".
+pub fn signature_to_der(
+    out: &mut [u8; MAX_DER_SIGNATURE_LEN],
+    r: &[u32; 8],
+    s: &[u32; 8],
+) -> usize {
+    let r_len: usize = encode_integer(&mut out[2..], r);
+    let s_len: usize = encode_integer(&mut out[2 + r_len..], s);
+    out[0] = TAG_SEQUENCE;
+    out[1] = (r_len + s_len) as u8;
+    2 + r_len + s_len
+}
+
+/// A DER encoded signature, stored in a fixed size buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DerSignature {
+    bytes: [u8; MAX_DER_SIGNATURE_LEN],
+    len: usize,
+}
+
+impl DerSignature {
+    /// Encode a signature as DER.
+    pub(crate) fn new(r: &[u32; 8], s: &[u32; 8]) -> Self {
+        let mut bytes: [u8; MAX_DER_SIGNATURE_LEN] = [0; MAX_DER_SIGNATURE_LEN];
+        let len: usize = signature_to_der(&mut bytes, r, s);
+        Self { bytes, len }
+    }
+
+    /// The DER encoding.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for DerSignature {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Reads a tag and its length, returns the content and the remaining input.
+// Only lengths below 256 are supported, which is enough for the structures in this crate.
+pub(crate) fn decode_tlv(input: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    let (len, rest): (usize, &[u8]) = match input {
+        [t, len @ 0x00..=0x7f, rest @ ..] if *t == tag => (*len as usize, rest),
+        // the long form is only minimal for lengths that don't fit in the short form
+        [t, 0x81, len @ 0x80..=0xff, rest @ ..] if *t == tag => (*len as usize, rest),
+        _ => return Err(Error::InvalidDer),
+    };
+    if rest.len() < len {
+        return Err(Error::InvalidDer);
+    }
+    Ok(rest.split_at(len))
+}
+
+// Decodes a positive integer of at most 256 bits with a minimal encoding,
+// returns the remaining input.
+fn decode_integer<'a>(value: &mut [u32; 8], input: &'a [u8]) -> Result<&'a [u8], Error> {
+    let (content, rest) = decode_tlv(input, TAG_INTEGER)?;
+    let content: &[u8] = match content {
+        // empty, or negative
+        [] | [0x80..=0xff, ..] => return Err(Error::InvalidDer),
+        // a leading zero is only allowed if the next byte has the most significant bit set
+        [0x00, next, ..] if *next < 0x80 => return Err(Error::InvalidDer),
+        [0x00, value @ ..] if !value.is_empty() => value,
+        _ => content,
+    };
+    if content.len() > 32 {
+        return Err(Error::InvalidDer);
+    }
+
+    let mut be: [u8; 32] = [0; 32];
+    be[32 - content.len()..].copy_from_slice(content);
+    convert_endianness(u32x8_to_u8x32_mut(value), &be);
+    Ok(rest)
+}
+
+/// Decodes a DER encoded signature into "r" and "s", for use with [`verify`](crate::verify).
+///
+/// The decoding is strict, non-minimal lengths and integers, negative integers and trailing
+/// data are rejected, and "r" and "s" must be in the range `1..=n-1`.
+#[must_use]
+pub fn signature_from_der(r: &mut [u32; 8], s: &mut [u32; 8], der: &[u8]) -> bool {
+    try_signature_from_der(r, s, der).is_ok()
+}
+
+/// Same as [`signature_from_der`], but returns the reason for a failure.
+pub fn try_signature_from_der(r: &mut [u32; 8], s: &mut [u32; 8], der: &[u8]) -> Result<(), Error> {
+    let result: Result<(), Error> = decode_signature(r, s, der);
+    if result.is_err() {
+        r.fill(0);
+        s.fill(0);
+    }
+    result
+}
+
+fn decode_signature(r: &mut [u32; 8], s: &mut [u32; 8], der: &[u8]) -> Result<(), Error> {
+    let (sequence, []) = decode_tlv(der, TAG_SEQUENCE)? else {
+        return Err(Error::InvalidDer);
+    };
+    let rest: &[u8] = decode_integer(r, sequence)?;
+    let [] = decode_integer(s, rest)? else {
+        return Err(Error::InvalidDer);
+    };
+    if !check_range_n(r) || !check_range_n(s) {
+        return Err(Error::SignatureOutOfRange);
+    }
+    Ok(())
+}
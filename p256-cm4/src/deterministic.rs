@@ -0,0 +1,140 @@
+//! ECDSA signatures with `k` derived according to RFC 6979, optionally hedged with
+//! additional randomness.
+
+use crate::{
+    Error, SignPrecomp, check_range_n, hash_to_z,
+    sys::reduce_mod_n_32bytes_in_place,
+    try_sign_step1, try_sign_step2,
+    types::{from_be_bytes, to_be_bytes},
+    u32x8_to_u8x32_mut,
+};
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+use rfc6979::HmacDrbg;
+use sha2::{
+    Sha256,
+    digest::{Digest, FixedOutputReset, core_api::BlockSizeUser},
+};
+use zeroize::Zeroize;
+
+/// Creates an ECDSA signature, with "k" derived deterministically from the private key and
+/// message hash according to RFC 6979, using HMAC-SHA-256.
+///
+/// No random number generator is required, signing the same message hash with the same private
+/// key results in the same signature.  The message hash should be computed with SHA-256, use
+/// [`try_sign_deterministic_with_digest`] for other hash functions.
+///
+/// Returns false if the private key is not in the range `1..=n-1`.  Unlike [`sign`](crate::sign)
+/// this does not fail for an unsuitable "k", the next "k" of the RFC 6979 sequence is used instead.
+#[must_use]
+pub fn sign_deterministic(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+) -> bool {
+    try_sign_deterministic(r, s, hash, private_key).is_ok()
+}
+
+/// Same as [`sign_deterministic`], but returns the reason for a failure.
+pub fn try_sign_deterministic(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+) -> Result<(), Error> {
+    try_sign_deterministic_with_digest::<Sha256>(r, s, hash, private_key)
+}
+
+/// Same as [`try_sign_deterministic`], but uses the HMAC of the hash function `D` to derive "k".
+///
+/// RFC 6979 uses the same hash function for the message hash and for the derivation of "k".
+pub fn try_sign_deterministic_with_digest<D>(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+) -> Result<(), Error>
+where
+    D: Digest + BlockSizeUser + FixedOutputReset,
+{
+    sign_with_hmac_drbg::<D>(r, s, hash, private_key, &[])
+}
+
+/// Creates an ECDSA signature, with "k" derived from the private key, message hash and 32 bytes
+/// from a random number generator, according to the variant of RFC 6979 with additional data
+/// described in section 3.6, using HMAC-SHA-256.
+///
+/// This protects against fault attacks that recover the private key from two signatures of the
+/// same message hash with a deterministic "k", while the signature remains secure if the random
+/// number generator is weak.  Signing the same message hash twice results in different signatures.
+///
+/// Returns false if the private key is not in the range `1..=n-1`.
+#[cfg(feature = "rand_core")]
+#[must_use]
+pub fn sign_hedged(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    rng: &mut impl CryptoRngCore,
+) -> bool {
+    try_sign_hedged(r, s, hash, private_key, rng).is_ok()
+}
+
+/// Same as [`sign_hedged`], but returns the reason for a failure.
+#[cfg(feature = "rand_core")]
+pub fn try_sign_hedged(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    rng: &mut impl CryptoRngCore,
+) -> Result<(), Error> {
+    let mut additional_data: [u8; 32] = [0; 32];
+    rng.fill_bytes(&mut additional_data);
+    sign_with_hmac_drbg::<Sha256>(r, s, hash, private_key, &additional_data)
+}
+
+// Signs with the sequence of "k" generated by the RFC 6979 HMAC_DRBG,
+// seeded with the private key, the message hash and the additional data.
+// Each "k" is zeroized after use, the same as with sign_step1_with_rng.
+// The HMAC_DRBG of the rfc6979 crate does not implement Zeroize, and its state is left on the stack.
+fn sign_with_hmac_drbg<D>(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    additional_data: &[u8],
+) -> Result<(), Error>
+where
+    D: Digest + BlockSizeUser + FixedOutputReset,
+{
+    if !check_range_n(private_key) {
+        r.fill(0);
+        s.fill(0);
+        return Err(Error::ScalarOutOfRange);
+    }
+
+    // bits2octets, the hash is truncated to 256 bits and reduced modulo n
+    let mut z: [u32; 8] = [0; 8];
+    hash_to_z(u32x8_to_u8x32_mut(&mut z), hash);
+    reduce_mod_n_32bytes_in_place(&mut z);
+
+    let mut hmac_drbg: HmacDrbg<D> =
+        HmacDrbg::new(&to_be_bytes(private_key), &to_be_bytes(&z), additional_data);
+    let mut k: [u8; 32] = [0; 32];
+    let mut sign_precomp: SignPrecomp = Default::default();
+    loop {
+        hmac_drbg.fill_bytes(&mut k);
+        let mut k_limbs: [u32; 8] = from_be_bytes(&k);
+        k.zeroize();
+        // The private key is in range, so this only fails if k is not in the range `1..=n-1`,
+        // or if r or s is zero.  Either way RFC 6979 continues with the next k.
+        let step1: Result<(), Error> = try_sign_step1(&mut sign_precomp, &k_limbs);
+        k_limbs.zeroize();
+        if step1.is_ok() && try_sign_step2(r, s, hash, private_key, &mut sign_precomp).is_ok() {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/// Reason for a failed operation.
+///
+/// Returned by the `try_` variants of the functions in the crate root,
+/// which return `false` for all of these cases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+    /// The encoded point has an unknown prefix, the wrong length for its prefix,
+    /// or a hybrid prefix that does not match the parity of y.
+    InvalidEncoding,
+    /// A coordinate is not in the range `0..=p-1`.
+    CoordinateOutOfRange,
+    /// The point does not lie on the curve.
+    NotOnCurve,
+    /// A scalar, private key or `k` is not in the range `1..=n-1`.
+    ScalarOutOfRange,
+    /// `r` of the signature is zero for the given `k`, sign again with a new `k`.
+    RIsZero,
+    /// `s` of the signature is zero for the given `k`, sign again with a new `k`.
+    SIsZero,
+    /// The sign precomputation state is not the result of a successful `sign_step1`.
+    InvalidPrecomp,
+    /// `r` or `s` of the signature is not in the range `1..=n-1`.
+    SignatureOutOfRange,
+    /// The signature is not valid for the message hash and public key.
+    InvalidSignature,
+    /// The DER encoding is malformed, not minimal, followed by trailing data, or describes a
+    /// key for a different algorithm or curve.
+    InvalidDer,
+    /// The public key embedded with a private key does not belong to the private key.
+    PublicKeyMismatch,
+    /// The PEM armor is malformed, has an unexpected label, or contains invalid base64.
+    InvalidPem,
+    /// The JWK is malformed JSON, is not an `EC` key on the `P-256` curve, or a member is
+    /// missing or not a 32 byte base64url integer.
+    InvalidJwk,
+    /// The CBOR is malformed, or is not a P-256 `COSE_Key` or an ES256 `COSE_Sign1`.
+    InvalidCose,
+    /// The output buffer is too small for the encoding, or the scratch space is too small.
+    BufferTooSmall,
+    /// The result is the point at infinity, which has no affine coordinates.
+    PointAtInfinity,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Error::InvalidEncoding => "invalid point encoding",
+            Error::CoordinateOutOfRange => "coordinate out of range",
+            Error::NotOnCurve => "point not on curve",
+            Error::ScalarOutOfRange => "scalar out of range",
+            Error::RIsZero => "signature r is zero",
+            Error::SIsZero => "signature s is zero",
+            Error::InvalidPrecomp => "invalid sign precomputation",
+            Error::SignatureOutOfRange => "signature out of range",
+            Error::InvalidSignature => "invalid signature",
+            Error::InvalidDer => "invalid DER encoding",
+            Error::PublicKeyMismatch => "public key does not match private key",
+            Error::InvalidPem => "invalid PEM encoding",
+            Error::InvalidJwk => "invalid JWK",
+            Error::InvalidCose => "invalid COSE encoding",
+            Error::BufferTooSmall => "buffer too small",
+            Error::PointAtInfinity => "point at infinity",
+        })
+    }
+}
+
+impl core::error::Error for Error {}
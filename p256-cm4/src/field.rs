@@ -0,0 +1,155 @@
+//! Field arithmetic modulo `p = 2^256 - 2^224 + 2^192 + 2^96 - 1`.
+
+use crate::{
+    check_range_p,
+    ops::impl_field_ops,
+    sys::{Montgomery, add_mod_p, inv_mod_p, mul_mod_p, sqr_mod_p, sqrt_mod_p, sub_mod_p},
+    types::{from_be_bytes, to_be_bytes},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// An element of the base field, in montgomery form.
+///
+/// The arithmetic is available as inherent methods and operators, and constant time
+/// selection and comparison through the [`ConditionallySelectable`] and [`ConstantTimeEq`]
+/// traits.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FieldElement(pub(crate) Montgomery);
+
+impl FieldElement {
+    /// Zero.
+    pub const ZERO: Self = Self(Montgomery::zero());
+
+    /// One.
+    pub const ONE: Self = Self(Montgomery::one());
+
+    /// Decode a field element from a big-endian integer.
+    ///
+    /// Returns `None` if the value is not in the range `0..=p-1`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+        let limbs: [u32; 8] = from_be_bytes(bytes);
+        CtOption::new(
+            Self(Montgomery::from(limbs)),
+            Choice::from(check_range_p(&limbs) as u8),
+        )
+    }
+
+    /// The field element as a big-endian integer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_be_bytes(&self.0.into())
+    }
+
+    /// Decode a field element from little-endian limbs, in normal form, as used by the
+    /// functions in the crate root.
+    ///
+    /// Returns `None` if the value is not in the range `0..=p-1`.
+    pub fn from_limbs(limbs: &[u32; 8]) -> CtOption<Self> {
+        CtOption::new(
+            Self(Montgomery::from(*limbs)),
+            Choice::from(check_range_p(limbs) as u8),
+        )
+    }
+
+    /// The field element as little-endian limbs, in normal form.
+    pub fn to_limbs(&self) -> [u32; 8] {
+        self.0.into()
+    }
+
+    /// Calculate `self + rhs mod p`.
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut res = Montgomery::zero();
+        add_mod_p(&mut res, &self.0, &rhs.0);
+        Self(res)
+    }
+
+    /// Calculate `self - rhs mod p`.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        let mut res = Montgomery::zero();
+        sub_mod_p(&mut res, &self.0, &rhs.0);
+        Self(res)
+    }
+
+    /// Calculate `-self mod p`.
+    pub fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    /// Calculate `self * rhs mod p`.
+    pub fn multiply(&self, rhs: &Self) -> Self {
+        let mut res = Montgomery::zero();
+        mul_mod_p(&mut res, &self.0, &rhs.0);
+        Self(res)
+    }
+
+    /// Calculate `self^2 mod p`.
+    pub fn square(&self) -> Self {
+        let mut res = Montgomery::zero();
+        sqr_mod_p(&mut res, &self.0);
+        Self(res)
+    }
+
+    /// Calculate `2 * self mod p`.
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// Calculate `self^-1 mod p`.
+    ///
+    /// Returns `None` if `self` is zero.
+    pub fn invert(&self) -> CtOption<Self> {
+        let mut res = Montgomery::zero();
+        inv_mod_p(&mut res, &self.0);
+        CtOption::new(Self(res), !self.ct_eq(&Self::ZERO))
+    }
+
+    /// Calculate a square root of `self mod p`.
+    ///
+    /// Returns `None` if `self` is not a square.  Which of the two roots is returned is
+    /// unspecified.
+    pub fn sqrt(&self) -> CtOption<Self> {
+        let mut res = Montgomery::zero();
+        sqrt_mod_p(&mut res, &self.0);
+        let res = Self(res);
+        CtOption::new(res, res.square().ct_eq(self))
+    }
+}
+
+impl_field_ops!(FieldElement);
+
+impl From<u64> for FieldElement {
+    fn from(value: u64) -> Self {
+        Self(Montgomery::from([
+            value as u32,
+            (value >> 32) as u32,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        ]))
+    }
+}
+
+impl ConditionallySelectable for FieldElement {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(Montgomery::new(core::array::from_fn(|i| {
+            u32::conditional_select(&a.0[i], &b.0[i], choice)
+        })))
+    }
+}
+
+impl ConstantTimeEq for FieldElement {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // both values are fully reduced, so the representation is unique
+        (0..8).fold(Choice::from(1), |acc, i| acc & self.0[i].ct_eq(&other.0[i]))
+    }
+}
+
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for FieldElement {}
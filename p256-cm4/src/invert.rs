@@ -0,0 +1,193 @@
+//! Batch inversion with Montgomery's trick, which replaces the inversions of many values by a
+//! single inversion and 3 multiplications per value.
+
+use crate::{
+    Error, FieldElement, check_range_n, mod_n_inv,
+    sys::{Montgomery, inv_mod_p, mul_mod_n, mul_mod_p},
+};
+
+const ONE: [u32; 8] = [1, 0, 0, 0, 0, 0, 0, 0];
+
+// Calculates *slot(&mut out[i]) = value(i)^-1 for all i, where mul and inv multiply and invert
+// values. All values must be non-zero.
+fn montgomery_trick<S, T: Copy>(
+    out: &mut [S],
+    slot: fn(&mut S) -> &mut T,
+    value: impl Fn(usize) -> T,
+    mul: impl Fn(&T, &T) -> T,
+    inv: impl Fn(&T) -> T,
+) {
+    let Some(last) = out.len().checked_sub(1) else {
+        return;
+    };
+
+    // out[i] = value(0) * value(1) * ... * value(i)
+    *slot(&mut out[0]) = value(0);
+    (1..out.len()).for_each(|i| {
+        let [cur, prev] = out.get_disjoint_mut([i, i - 1]).unwrap();
+        *slot(cur) = mul(slot(prev), &value(i));
+    });
+
+    // acc = (value(0) * value(1) * ... * value(i))^-1, going down from the last value
+    let mut acc: T = inv(slot(&mut out[last]));
+    (1..out.len()).rev().for_each(|i| {
+        let [cur, prev] = out.get_disjoint_mut([i, i - 1]).unwrap();
+        *slot(cur) = mul(slot(prev), &acc);
+        acc = mul(&acc, &value(i));
+    });
+    *slot(&mut out[0]) = acc;
+}
+
+fn itself<T>(value: &mut T) -> &mut T {
+    value
+}
+
+fn mul_n(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    let mut res: [u32; 8] = [0; 8];
+    mul_mod_n(&mut res, a, b);
+    res
+}
+
+fn inv_n(a: &[u32; 8]) -> [u32; 8] {
+    let mut res: [u32; 8] = [0; 8];
+    mod_n_inv(&mut res, a);
+    res
+}
+
+fn mul_p(a: &Montgomery, b: &Montgomery) -> Montgomery {
+    let mut res = Montgomery::zero();
+    mul_mod_p(&mut res, a, b);
+    res
+}
+
+fn inv_p(a: &Montgomery) -> Montgomery {
+    let mut res = Montgomery::zero();
+    inv_mod_p(&mut res, a);
+    res
+}
+
+fn is_zero(a: &Montgomery) -> bool {
+    (0..8).all(|i| a[i] == 0)
+}
+
+// Calculates out[i] = value(i)^-1 mod n for all i. All values must be in the range 1..=n-1.
+pub(crate) fn batch_inv_mod_n(out: &mut [[u32; 8]], value: impl Fn(usize) -> [u32; 8]) {
+    montgomery_trick(out, itself, value, mul_n, inv_n);
+}
+
+// Converts points in Jacobian coordinates to affine coordinates with a single inversion, the
+// same as jacobian_to_affine for each point. x and y select the coordinates of the outputs, and x
+// is also used to store the intermediate products.
+pub(crate) fn batch_jacobian_to_affine<S>(
+    points: impl Fn(usize) -> [Montgomery; 3],
+    out: &mut [S],
+    x: fn(&mut S) -> &mut Montgomery,
+    y: fn(&mut S) -> &mut Montgomery,
+) {
+    // Invert all Z, with 1 in place of the points at infinity.
+    let z = |i: usize| {
+        let [_, _, z] = points(i);
+        if is_zero(&z) { Montgomery::one() } else { z }
+    };
+    montgomery_trick(out, x, z, mul_p, inv_p);
+
+    out.iter_mut().enumerate().for_each(|(i, out)| {
+        let [px, py, pz] = points(i);
+        if is_zero(&pz) {
+            // the same result as jacobian_to_affine, which inverts zero to zero
+            *x(out) = Montgomery::zero();
+            *y(out) = Montgomery::zero();
+        } else {
+            // (X/Z^2, Y/Z^3)
+            let z_inv: Montgomery = *x(out);
+            let z_inv2: Montgomery = mul_p(&z_inv, &z_inv);
+            *x(out) = mul_p(&px, &z_inv2);
+            *y(out) = mul_p(&py, &mul_p(&z_inv2, &z_inv));
+        }
+    });
+}
+
+/// Inverts many integers modulo n, the order of the elliptic curve, at the cost of about one
+/// inversion.
+///
+/// Each value in `values` is replaced by its inverse, as little-endian limbs.  Zero has no inverse
+/// and is left as zero.  `scratch` must be at least as long as `values`.
+///
+/// This function validates that all values lie in the range 0 to n-1, and that `scratch` is long
+/// enough.  If validation succeeds, true is returned.  Otherwise false is returned and `values` is
+/// left unchanged.
+#[must_use]
+pub fn batch_invert_mod_n(values: &mut [[u32; 8]], scratch: &mut [[u32; 8]]) -> bool {
+    try_batch_invert_mod_n(values, scratch).is_ok()
+}
+
+/// Same as [`batch_invert_mod_n`], but returns the reason for a failure.
+pub fn try_batch_invert_mod_n(
+    values: &mut [[u32; 8]],
+    scratch: &mut [[u32; 8]],
+) -> Result<(), Error> {
+    let scratch = scratch
+        .get_mut(..values.len())
+        .ok_or(Error::BufferTooSmall)?;
+    // zero is allowed, it is left as zero
+    if !values
+        .iter()
+        .all(|value| check_range_n(value) || *value == [0; 8])
+    {
+        return Err(Error::ScalarOutOfRange);
+    }
+
+    batch_inv_mod_n(
+        scratch,
+        |i| if values[i] == [0; 8] { ONE } else { values[i] },
+    );
+    values
+        .iter_mut()
+        .zip(scratch.iter())
+        .filter(|(value, _)| **value != [0; 8])
+        .for_each(|(value, inverse)| *value = *inverse);
+    Ok(())
+}
+
+/// Inverts many field elements at the cost of about one inversion.
+///
+/// Each value in `values` is replaced by its inverse.  Zero has no inverse and is left as zero.
+/// The values stay in montgomery form, so this needs no conversions.  `scratch` must be at least
+/// as long as `values`.
+///
+/// Returns true on success, or false if `scratch` is too short, in which case `values` is left
+/// unchanged.
+#[must_use]
+pub fn batch_invert_mod_p(values: &mut [FieldElement], scratch: &mut [FieldElement]) -> bool {
+    try_batch_invert_mod_p(values, scratch).is_ok()
+}
+
+/// Same as [`batch_invert_mod_p`], but returns the reason for a failure.
+pub fn try_batch_invert_mod_p(
+    values: &mut [FieldElement],
+    scratch: &mut [FieldElement],
+) -> Result<(), Error> {
+    let scratch = scratch
+        .get_mut(..values.len())
+        .ok_or(Error::BufferTooSmall)?;
+
+    montgomery_trick(
+        scratch,
+        |inverse| &mut inverse.0,
+        |i| {
+            if is_zero(&values[i].0) {
+                Montgomery::one()
+            } else {
+                values[i].0
+            }
+        },
+        mul_p,
+        inv_p,
+    );
+    values
+        .iter_mut()
+        .zip(scratch.iter())
+        .filter(|(value, _)| !is_zero(&value.0))
+        .for_each(|(value, inverse)| *value = *inverse);
+    Ok(())
+}
@@ -0,0 +1,305 @@
+//! JSON Web Key encoding of RFC 7517 and RFC 7518 for P-256 keys:
+//!
+//! ```text
+//! {"kty":"EC","crv":"P-256","x":"<base64url x>","y":"<base64url y>","d":"<base64url d>"}
+//! ```
+//!
+//! The coordinates and the private key are 32 byte big-endian integers, base64url encoded
+//! without padding.  Decoding accepts any member order, whitespace and additional members.
+
+use crate::{
+    Error, base64, check_range_n, check_range_p, keygen,
+    private_key::{clear_on_error, keygen_or_clear},
+    sys::{Montgomery, point_is_on_curve},
+    types::{from_be_bytes, to_be_bytes},
+};
+
+// Length of a base64url encoded 32 byte integer.
+const INTEGER_LEN: usize = base64::encoded_len(32, false);
+
+const PREFIX: &[u8] = br#"{"kty":"EC","crv":"P-256","x":""#;
+const Y: &[u8] = br#"","y":""#;
+const D: &[u8] = br#"","d":""#;
+const SUFFIX: &[u8] = br#""}"#;
+
+/// Length of a JWK encoded public key.
+pub const PUBLIC_KEY_JWK_LEN: usize =
+    PREFIX.len() + INTEGER_LEN + Y.len() + INTEGER_LEN + SUFFIX.len();
+
+/// Length of a JWK encoded private key, with the public key.
+pub const PRIVATE_KEY_JWK_LEN: usize = PUBLIC_KEY_JWK_LEN + D.len() + INTEGER_LEN;
+
+// Nesting limit of skipped values, which bounds the recursion.
+const MAX_DEPTH: usize = 8;
+
+fn put(out: &mut [u8], pos: &mut usize, bytes: &[u8]) {
+    out[*pos..*pos + bytes.len()].copy_from_slice(bytes);
+    *pos += bytes.len();
+}
+
+fn put_integer(out: &mut [u8], pos: &mut usize, value: &[u32; 8]) {
+    base64::encode(
+        &mut out[*pos..*pos + INTEGER_LEN],
+        &to_be_bytes(value),
+        base64::URL_SAFE,
+        false,
+    );
+    *pos += INTEGER_LEN;
+}
+
+// Writes the public key members, and the private key if given.
+fn encode(out: &mut [u8], x: &[u32; 8], y: &[u32; 8], private_key: Option<&[u32; 8]>) {
+    let mut pos: usize = 0;
+    put(out, &mut pos, PREFIX);
+    put_integer(out, &mut pos, x);
+    put(out, &mut pos, Y);
+    put_integer(out, &mut pos, y);
+    if let Some(private_key) = private_key {
+        put(out, &mut pos, D);
+        put_integer(out, &mut pos, private_key);
+    }
+    put(out, &mut pos, SUFFIX);
+}
+
+// The members of a JWK used by this crate, as raw string contents.
+#[derive(Default)]
+struct Members<'a> {
+    kty: Option<&'a [u8]>,
+    crv: Option<&'a [u8]>,
+    x: Option<&'a [u8]>,
+    y: Option<&'a [u8]>,
+    d: Option<&'a [u8]>,
+}
+
+fn skip_whitespace(input: &[u8]) -> &[u8] {
+    input.trim_ascii_start()
+}
+
+fn expect(input: &[u8], c: u8) -> Result<&[u8], Error> {
+    match skip_whitespace(input) {
+        [first, rest @ ..] if *first == c => Ok(rest),
+        _ => Err(Error::InvalidJwk),
+    }
+}
+
+// Reads a string, returns its contents without unescaping and the remaining input.
+fn parse_string(input: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let input: &[u8] = expect(input, b'"')?;
+    let mut i: usize = 0;
+    loop {
+        match input.get(i) {
+            Some(b'"') => return Ok((&input[..i], &input[i + 1..])),
+            Some(b'\\') => i += 2,
+            Some(0x20..) => i += 1,
+            // end of input or a control character
+            _ => return Err(Error::InvalidJwk),
+        }
+    }
+}
+
+// Skips any value, returns the remaining input.
+fn skip_value(input: &[u8], depth: usize) -> Result<&[u8], Error> {
+    if depth > MAX_DEPTH {
+        return Err(Error::InvalidJwk);
+    }
+    let input: &[u8] = skip_whitespace(input);
+    match input.first() {
+        Some(b'"') => parse_string(input).map(|(_, rest)| rest),
+        Some(b'{') => skip_sequence(&input[1..], b'}', |input| {
+            let (_, rest) = parse_string(input)?;
+            skip_value(expect(rest, b':')?, depth + 1)
+        }),
+        Some(b'[') => skip_sequence(&input[1..], b']', |input| skip_value(input, depth + 1)),
+        Some(b'-' | b'0'..=b'9') => {
+            let len: usize = input
+                .iter()
+                .take_while(|c| matches!(c, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+                .count();
+            Ok(&input[len..])
+        }
+        _ => [&b"true"[..], b"false", b"null"]
+            .iter()
+            .find_map(|literal| input.strip_prefix(*literal))
+            .ok_or(Error::InvalidJwk),
+    }
+}
+
+// Skips the elements of an object or array up to and including the closing character.
+fn skip_sequence(
+    input: &[u8],
+    close: u8,
+    element: impl Fn(&[u8]) -> Result<&[u8], Error>,
+) -> Result<&[u8], Error> {
+    if let Ok(rest) = expect(input, close) {
+        return Ok(rest);
+    }
+    let mut input: &[u8] = input;
+    loop {
+        input = skip_whitespace(element(input)?);
+        match input {
+            [b',', rest @ ..] => input = rest,
+            [c, rest @ ..] if *c == close => return Ok(rest),
+            _ => return Err(Error::InvalidJwk),
+        }
+    }
+}
+
+fn parse(jwk: &[u8]) -> Result<Members<'_>, Error> {
+    let mut members: Members = Members::default();
+    let mut input: &[u8] = expect(jwk, b'{')?;
+    if let Ok(rest) = expect(input, b'}') {
+        input = rest;
+    } else {
+        loop {
+            let (name, rest) = parse_string(input)?;
+            let rest: &[u8] = expect(rest, b':')?;
+            let member: Option<&mut Option<&[u8]>> = match name {
+                b"kty" => Some(&mut members.kty),
+                b"crv" => Some(&mut members.crv),
+                b"x" => Some(&mut members.x),
+                b"y" => Some(&mut members.y),
+                b"d" => Some(&mut members.d),
+                _ => None,
+            };
+            input = match member {
+                // duplicate members are ambiguous
+                Some(Some(_)) => return Err(Error::InvalidJwk),
+                Some(member) => {
+                    let (value, rest) = parse_string(rest)?;
+                    *member = Some(value);
+                    rest
+                }
+                None => skip_value(rest, 1)?,
+            };
+            match skip_whitespace(input) {
+                [b',', rest @ ..] => input = rest,
+                [b'}', rest @ ..] => {
+                    input = rest;
+                    break;
+                }
+                _ => return Err(Error::InvalidJwk),
+            }
+        }
+    }
+    if !skip_whitespace(input).is_empty() {
+        return Err(Error::InvalidJwk);
+    }
+    if members.kty != Some(b"EC") || members.crv != Some(b"P-256") {
+        return Err(Error::InvalidJwk);
+    }
+    Ok(members)
+}
+
+// Decodes a base64url encoded 32 byte integer.
+fn decode_integer(value: Option<&[u8]>) -> Result<[u32; 8], Error> {
+    let value: &[u8] = value.ok_or(Error::InvalidJwk)?;
+    let mut bytes: [u8; 32] = [0; 32];
+    match base64::decode(&mut bytes, value.iter().copied(), base64::URL_SAFE, false) {
+        Some(decoded) if decoded.len() == 32 => Ok(from_be_bytes(&bytes)),
+        _ => Err(Error::InvalidJwk),
+    }
+}
+
+fn decode_point(x: &mut [u32; 8], y: &mut [u32; 8], members: &Members<'_>) -> Result<(), Error> {
+    *x = decode_integer(members.x)?;
+    *y = decode_integer(members.y)?;
+    if !check_range_p(x) || !check_range_p(y) {
+        Err(Error::CoordinateOutOfRange)
+    } else if !point_is_on_curve(&Montgomery::from(*x), &Montgomery::from(*y)) {
+        Err(Error::NotOnCurve)
+    } else {
+        Ok(())
+    }
+}
+
+fn decode_private_key(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    jwk: &[u8],
+) -> Result<(), Error> {
+    let members: Members = parse(jwk)?;
+    *private_key = decode_integer(members.d)?;
+    if !check_range_n(private_key) {
+        return Err(Error::ScalarOutOfRange);
+    }
+    let mut x: [u32; 8] = [0; 8];
+    let mut y: [u32; 8] = [0; 8];
+    decode_point(&mut x, &mut y, &members)?;
+
+    // cannot fail, the private key is in range
+    let _ = keygen(public_key_x, public_key_y, private_key);
+    if (x, y) != (*public_key_x, *public_key_y) {
+        return Err(Error::PublicKeyMismatch);
+    }
+    Ok(())
+}
+
+/// Encodes a public key as a JWK.
+pub fn point_to_jwk(out: &mut [u8; PUBLIC_KEY_JWK_LEN], x: &[u32; 8], y: &[u32; 8]) {
+    encode(out, x, y, None);
+}
+
+/// Decodes a JWK into the affine coordinates of the public key.
+///
+/// The key type must be `EC` and the curve `P-256`.  The coordinates must be 32 bytes, in the
+/// range `0..=p-1`, and lie on the curve.  A private key in the JWK is ignored.
+#[must_use]
+pub fn jwk_to_point(x: &mut [u32; 8], y: &mut [u32; 8], jwk: &[u8]) -> bool {
+    try_jwk_to_point(x, y, jwk).is_ok()
+}
+
+/// Same as [`jwk_to_point`], but returns the reason for a failure.
+pub fn try_jwk_to_point(x: &mut [u32; 8], y: &mut [u32; 8], jwk: &[u8]) -> Result<(), Error> {
+    let result: Result<(), Error> = parse(jwk).and_then(|members| decode_point(x, y, &members));
+    if result.is_err() {
+        x.fill(0);
+        y.fill(0);
+    }
+    result
+}
+
+/// Encodes a private key as a JWK, with the public key.
+///
+/// Returns false if the private key is not in the range `1..=n-1`.
+#[must_use]
+pub fn private_key_to_jwk(out: &mut [u8; PRIVATE_KEY_JWK_LEN], private_key: &[u32; 8]) -> bool {
+    try_private_key_to_jwk(out, private_key).is_ok()
+}
+
+/// Same as [`private_key_to_jwk`], but returns the reason for a failure.
+pub fn try_private_key_to_jwk(
+    out: &mut [u8; PRIVATE_KEY_JWK_LEN],
+    private_key: &[u32; 8],
+) -> Result<(), Error> {
+    let mut public_key_x: [u32; 8] = [0; 8];
+    let mut public_key_y: [u32; 8] = [0; 8];
+    keygen_or_clear(&mut public_key_x, &mut public_key_y, private_key, out)?;
+    encode(out, &public_key_x, &public_key_y, Some(private_key));
+    Ok(())
+}
+
+/// Decodes a JWK with a private key, and calculates the public key.
+///
+/// The private key must be 32 bytes and in the range `1..=n-1`, and the public key in the JWK
+/// must belong to it.
+#[must_use]
+pub fn private_key_from_jwk(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    jwk: &[u8],
+) -> bool {
+    try_private_key_from_jwk(private_key, public_key_x, public_key_y, jwk).is_ok()
+}
+
+/// Same as [`private_key_from_jwk`], but returns the reason for a failure.
+pub fn try_private_key_from_jwk(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    jwk: &[u8],
+) -> Result<(), Error> {
+    let result = decode_private_key(private_key, public_key_x, public_key_y, jwk);
+    clear_on_error(result, private_key, public_key_x, public_key_y)
+}
@@ -1,8 +1,104 @@
 #![no_std]
 #![allow(clippy::missing_safety_doc)]
 
+mod affine;
+#[cfg(feature = "arithmetic")]
+mod arithmetic;
+#[cfg(any(feature = "jwk", feature = "pem"))]
+mod base64;
+#[cfg(feature = "rand_core")]
+mod batch;
+#[cfg(feature = "cose")]
+mod cbor;
+mod complete;
+#[cfg(feature = "cose")]
+mod cose;
+#[cfg(any(feature = "arithmetic", feature = "rustcrypto"))]
+mod curve;
+mod der;
+#[cfg(feature = "rfc6979")]
+mod deterministic;
+mod error;
+mod field;
+mod invert;
+#[cfg(feature = "jwk")]
+mod jwk;
+mod multiscalar;
+mod ops;
+#[cfg(feature = "pem")]
+mod pem;
+mod prepared;
+mod private_key;
+mod projective;
+mod public_key;
+#[cfg(feature = "rand_core")]
+mod rng;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
+mod scalar;
 mod sys;
-
+mod types;
+
+pub use affine::AffinePoint;
+#[cfg(feature = "rand_core")]
+pub use batch::{BatchSignature, try_verify_batch, verify_batch};
+#[cfg(feature = "cose")]
+pub use cose::{
+    COSE_KEY_LEN, cose_key_to_point, cose_sign1, cose_sign1_len, cose_sign1_verify,
+    point_to_cose_key, try_cose_key_to_point, try_cose_sign1, try_cose_sign1_verify,
+};
+#[cfg(any(feature = "arithmetic", feature = "rustcrypto"))]
+pub use curve::{FieldBytes, NistP256};
+pub use der::{
+    DerSignature, MAX_DER_SIGNATURE_LEN, signature_from_der, signature_to_der,
+    try_signature_from_der,
+};
+#[cfg(feature = "rfc6979")]
+pub use deterministic::{
+    sign_deterministic, try_sign_deterministic, try_sign_deterministic_with_digest,
+};
+#[cfg(all(feature = "rfc6979", feature = "rand_core"))]
+pub use deterministic::{sign_hedged, try_sign_hedged};
+#[cfg(any(feature = "arithmetic", feature = "rustcrypto"))]
+pub use elliptic_curve;
+pub use error::Error;
+pub use field::FieldElement;
+pub use invert::{
+    batch_invert_mod_n, batch_invert_mod_p, try_batch_invert_mod_n, try_batch_invert_mod_p,
+};
+#[cfg(feature = "jwk")]
+pub use jwk::{
+    PRIVATE_KEY_JWK_LEN, PUBLIC_KEY_JWK_LEN, jwk_to_point, point_to_jwk, private_key_from_jwk,
+    private_key_to_jwk, try_jwk_to_point, try_private_key_from_jwk, try_private_key_to_jwk,
+};
+pub use multiscalar::{MultiscalarScratch, multiscalar_mul, try_multiscalar_mul};
+#[cfg(feature = "p256")]
+pub use p256;
+pub use prepared::PreparedPublicKey;
+pub use private_key::{
+    PKCS8_PRIVATE_KEY_DER_LEN, SEC1_PRIVATE_KEY_DER_LEN, private_key_from_pkcs8_der,
+    private_key_from_sec1_der, private_key_to_pkcs8_der, private_key_to_sec1_der,
+    try_private_key_from_pkcs8_der, try_private_key_from_sec1_der, try_private_key_to_pkcs8_der,
+    try_private_key_to_sec1_der,
+};
+pub use projective::ProjectivePoint;
+pub use public_key::{
+    SPKI_COMPRESSED_DER_LEN, SPKI_UNCOMPRESSED_DER_LEN, point_to_spki_der_compressed,
+    point_to_spki_der_uncompressed, spki_der_to_point, try_spki_der_to_point,
+};
+#[cfg(feature = "pem")]
+pub use public_key::{
+    SPKI_COMPRESSED_PEM_LEN, SPKI_UNCOMPRESSED_PEM_LEN, point_to_spki_pem_compressed,
+    point_to_spki_pem_uncompressed, spki_pem_to_point, try_spki_pem_to_point,
+};
+#[cfg(feature = "rand_core")]
+pub use rand_core;
+#[cfg(feature = "rand_core")]
+pub use rng::{generate_keypair, sign_step1_with_rng, sign_with_rng, try_sign_with_rng};
+#[cfg(feature = "rustcrypto")]
+pub use rustcrypto::{SigningKey, VerifyingKey};
+pub use scalar::Scalar;
+pub use subtle;
 use sys::{
     Montgomery, add_mod_n_in_place, add_sub_j, add_sub_j_affine, decompress_point, divsteps2_31,
     double_j, double_j_inplace, jacobian_to_affine, matrix_mul_fg_9, matrix_mul_mod_n, mul_mod_n,
@@ -10,6 +106,8 @@ use sys::{
     reduce_mod_n_32bytes_in_place, verify_last_step,
 };
 pub use sys::{check_range_n, check_range_p};
+pub use types::{PublicKey, SecretKey, SharedSecret, Signature};
+pub use zeroize;
 
 // This table contains 1G, 3G, 5G, ... 15G in affine coordinates in montgomery form
 #[rustfmt::skip]
@@ -156,64 +254,71 @@ pub fn point_to_octet_string_hybrid(out: &mut [u8; 65], x: &[u32; 8], y: &[u32;
 /// is received from an untrusted party).
 #[must_use]
 pub fn octet_string_to_point(x: &mut [u32; 8], y: &mut [u32; 8], input: &[u8]) -> bool {
-    if let Ok(slice) = input[1..33].try_into() {
-        convert_endianness(u32x8_to_u8x32_mut(x), slice);
-        if !check_range_p(x) {
-            return false;
-        }
+    try_octet_string_to_point(x, y, input).is_ok()
+}
 
-        if (input[0] == 4 || ((input[0] >> 1) == 3)) && input.len() == 65 {
-            convert_endianness(u32x8_to_u8x32_mut(y), input[33..65].try_into().unwrap());
-            if !check_range_p(y) {
-                return false;
-            }
+/// Same as [`octet_string_to_point`], but returns the reason for a failure.
+pub fn try_octet_string_to_point(
+    x: &mut [u32; 8],
+    y: &mut [u32; 8],
+    input: &[u8],
+) -> Result<(), Error> {
+    let Some(Ok(slice)) = input.get(1..33).map(<&[u8; 32]>::try_from) else {
+        return Err(Error::InvalidEncoding);
+    };
+    convert_endianness(u32x8_to_u8x32_mut(x), slice);
 
-            if (input[0] >> 1) == 3 && u32::from(input[0] & 1) != (y[0] & 1) {
-                return false;
-            }
+    if (input[0] == 4 || ((input[0] >> 1) == 3)) && input.len() == 65 {
+        convert_endianness(u32x8_to_u8x32_mut(y), input[33..65].try_into().unwrap());
+        if !check_range_p(x) || !check_range_p(y) {
+            return Err(Error::CoordinateOutOfRange);
+        }
 
-            let x = Montgomery::from(*x);
-            let y = Montgomery::from(*y);
+        if (input[0] >> 1) == 3 && u32::from(input[0] & 1) != (y[0] & 1) {
+            return Err(Error::InvalidEncoding);
+        }
 
-            point_is_on_curve(&x, &y)
-        } else if (input[0] >> 1) == 1 && input.len() == 33 {
-            decompress_point(y, x, u32::from(input[0] & 1) == 1)
+        let x = Montgomery::from(*x);
+        let y = Montgomery::from(*y);
+
+        if point_is_on_curve(&x, &y) {
+            Ok(())
+        } else {
+            Err(Error::NotOnCurve)
+        }
+    } else if (input[0] >> 1) == 1 && input.len() == 33 {
+        if !check_range_p(x) {
+            Err(Error::CoordinateOutOfRange)
+        } else if decompress_point(y, x, u32::from(input[0] & 1) == 1) {
+            Ok(())
         } else {
-            false
+            Err(Error::NotOnCurve)
         }
     } else {
-        false
+        Err(Error::InvalidEncoding)
     }
 }
 
-// Calculates scalar*P in constant time (except for the scalars 2 and n-2, for which the results take a few extra cycles to compute)
-fn scalarmult_variable_base(
-    output_mont_x: &mut Montgomery,
-    output_mont_y: &mut Montgomery,
-    scalar: &[u32; 8],
-) {
-    // Based on https://eprint.iacr.org/2014/130.pdf, Algorithm 1.
-
-    // The algorithm used requires the scalar to be odd. If even, negate the scalar modulo p to make it odd, and later negate the end result.
-    let mut scalar2: [u32; 8] = [0; 8];
-    let even = ((scalar[0]) & 1) == 0;
-    negate_mod_n_if(&mut scalar2, scalar, even);
-
-    // Rewrite the scalar as e[0] + 2^4*e[1] + 2^8*e[2] + ... + 2^252*e[63], where each e[i] is an odd number and -15 <= e[i] <= 15.
+// Rewrites the odd scalar as e[0] + 2^4*e[1] + 2^8*e[2] + ... + 2^252*e[63], where each e[i] is an odd number and -15 <= e[i] <= 15.
+// e[63] is never negative.
+fn odd_digits(scalar: &[u32; 8]) -> [i8; 64] {
     let mut e: [i8; 64] = [0; 64];
-    e[0] = (scalar2[0] & 0xf) as i8;
+    e[0] = (scalar[0] & 0xf) as i8;
     (1..64).for_each(|i| {
         // Extract 4 bits
-        e[i] = ((scalar2[i / 8] >> ((i % 8) * 4)) & 0xf) as u8 as i8;
+        e[i] = ((scalar[i / 8] >> ((i % 8) * 4)) & 0xf) as u8 as i8;
         // If even, subtract 2^4 from e[i - 1] and add 1 to e[i]
         e[i - 1] -= ((e[i] & 1) ^ 1) << 4;
         e[i] |= 1;
     });
+    e
+}
 
-    // Create a table of P, 3P, 5P, ... 15P.
+// Creates a table of P, 3P, 5P, ..., 15P in Jacobian coordinates, where P = (x, y).
+fn odd_multiples(x: &Montgomery, y: &Montgomery) -> [[Montgomery; 3]; 8] {
     let mut table = [[Montgomery::zero(); 3]; 8];
-    table[0][0] = *output_mont_x;
-    table[0][1] = *output_mont_y;
+    table[0][0] = *x;
+    table[0][1] = *y;
     table[0][2] = Montgomery::one();
     let [seven, zero] = table.get_disjoint_mut([7, 0]).unwrap();
     double_j(seven, zero);
@@ -222,6 +327,26 @@ fn scalarmult_variable_base(
         let [i, i_min_one] = table.get_disjoint_mut([i, i - 1]).unwrap();
         add_sub_j(i, i_min_one, false);
     });
+    table
+}
+
+// Calculates scalar*P in constant time (except for the scalars 2 and n-2, for which the results take a few extra cycles to compute)
+fn scalarmult_variable_base(
+    output_mont_x: &mut Montgomery,
+    output_mont_y: &mut Montgomery,
+    scalar: &[u32; 8],
+) {
+    // Based on https://eprint.iacr.org/2014/130.pdf, Algorithm 1.
+
+    // The algorithm used requires the scalar to be odd. If even, negate the scalar modulo p to make it odd, and later negate the end result.
+    let mut scalar2: [u32; 8] = [0; 8];
+    let even = ((scalar[0]) & 1) == 0;
+    negate_mod_n_if(&mut scalar2, scalar, even);
+
+    let e: [i8; 64] = odd_digits(&scalar2);
+
+    // Create a table of P, 3P, 5P, ... 15P.
+    let table = odd_multiples(output_mont_x, output_mont_y);
 
     // Calculate the result as (((((((((e[63]*G)*2^4)+e[62])*2^4)+e[61])*2^4)...)+e[1])*2^4)+e[0] = (2^252*e[63] + 2^248*e[62] + ... + e[0])*G.
 
@@ -253,25 +378,24 @@ fn scalarmult_variable_base(
     negate_mod_p_if_in_place(output_mont_y, even);
 }
 
-#[must_use]
 fn scalarmult_generic_no_scalar_check(
     output_mont_x: &mut Montgomery,
     output_mont_y: &mut Montgomery,
     scalar: &[u32; 8],
     in_x: &[u32; 8],
     in_y: &[u32; 8],
-) -> bool {
+) -> Result<(), Error> {
     if !check_range_p(in_x) || !check_range_p(in_y) {
-        false
+        Err(Error::CoordinateOutOfRange)
     } else {
         output_mont_x.read(in_x);
         output_mont_y.read(in_y);
 
         if !point_is_on_curve(output_mont_x, output_mont_y) {
-            false
+            Err(Error::NotOnCurve)
         } else {
             scalarmult_variable_base(output_mont_x, output_mont_y, scalar);
-            true
+            Ok(())
         }
     }
 }
@@ -291,18 +415,27 @@ pub fn scalarmult_generic(
     in_x: &[u32; 8],
     in_y: &[u32; 8],
 ) -> bool {
+    try_scalarmult_generic(result_x, result_y, scalar, in_x, in_y).is_ok()
+}
+
+/// Same as [`scalarmult_generic`], but returns the reason for a failure.
+pub fn try_scalarmult_generic(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    scalar: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+) -> Result<(), Error> {
     let mut x = Montgomery::zero();
     let mut y = Montgomery::zero();
 
-    if !check_range_n(scalar)
-        || !scalarmult_generic_no_scalar_check(&mut x, &mut y, scalar, in_x, in_y)
-    {
-        false
-    } else {
-        x.write(result_x);
-        x.write(result_y);
-        true
+    if !check_range_n(scalar) {
+        return Err(Error::ScalarOutOfRange);
     }
+    scalarmult_generic_no_scalar_check(&mut x, &mut y, scalar, in_x, in_y)?;
+    x.write(result_x);
+    y.write(result_y);
+    Ok(())
 }
 
 /// Generates the shared secret according to the ECDH standard.
@@ -321,22 +454,35 @@ pub fn ecdh_calc_shared_secret(
     others_public_key_x: &[u32; 8],
     others_public_key_y: &[u32; 8],
 ) -> bool {
+    try_ecdh_calc_shared_secret(
+        shared_secret,
+        private_key,
+        others_public_key_x,
+        others_public_key_y,
+    )
+    .is_ok()
+}
+
+/// Same as [`ecdh_calc_shared_secret`], but returns the reason for a failure.
+pub fn try_ecdh_calc_shared_secret(
+    shared_secret: &mut [u8; 32],
+    private_key: &[u32; 8],
+    others_public_key_x: &[u32; 8],
+    others_public_key_y: &[u32; 8],
+) -> Result<(), Error> {
     let mut result_x = Montgomery::zero();
     let mut result_y = Montgomery::zero();
-    if !scalarmult_generic_no_scalar_check(
+    scalarmult_generic_no_scalar_check(
         &mut result_x,
         &mut result_y,
         private_key,
         others_public_key_x,
         others_public_key_y,
-    ) {
-        false
-    } else {
-        let mut out_x = [0u32; 8];
-        result_x.write(&mut out_x);
-        convert_endianness(shared_secret, u32x8_to_u8x32(&out_x));
-        true
-    }
+    )?;
+    let mut out_x = [0u32; 8];
+    result_x.write(&mut out_x);
+    convert_endianness(shared_secret, u32x8_to_u8x32(&out_x));
+    Ok(())
 }
 
 /// Calculates the public key from a given private key for use by either ECDSA or ECDH.
@@ -346,6 +492,7 @@ pub fn ecdh_calc_shared_secret(
 /// lies in the accepted range 1 to n-1, where n is the order of the elliptic curve, and returns true only if
 /// this validation succeeds. If random value is out of that range, false is returned and in this case a new
 /// random value needs to be generated and this function MUST be called again until true is returned.
+/// With the `rand_core` feature, `generate_keypair` performs this loop with a random number generator.
 ///
 /// The public key is created by performing a scalar multiplication of the private key and the base point of
 /// the curve.
@@ -357,7 +504,16 @@ pub fn keygen(
     public_key_y: &mut [u32; 8],
     private_key: &[u32; 8],
 ) -> bool {
-    scalarmult_base(public_key_x, public_key_y, private_key)
+    try_keygen(public_key_x, public_key_y, private_key).is_ok()
+}
+
+/// Same as [`keygen`], but returns the reason for a failure.
+pub fn try_keygen(
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    private_key: &[u32; 8],
+) -> Result<(), Error> {
+    try_scalarmult_base(public_key_x, public_key_y, private_key)
 }
 
 macro_rules! get_bit {
@@ -434,8 +590,17 @@ pub fn scalarmult_base(
     result_y: &mut [u32; 8],
     scalar: &[u32; 8],
 ) -> bool {
-    if check_range_n(scalar) {
-        false
+    try_scalarmult_base(result_x, result_y, scalar).is_ok()
+}
+
+/// Same as [`scalarmult_base`], but returns the reason for a failure.
+pub fn try_scalarmult_base(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    scalar: &[u32; 8],
+) -> Result<(), Error> {
+    if !check_range_n(scalar) {
+        Err(Error::ScalarOutOfRange)
     } else {
         let mut x = Montgomery::zero();
         let mut y = Montgomery::zero();
@@ -445,10 +610,191 @@ pub fn scalarmult_base(
         x.write(result_x);
         y.write(result_y);
 
-        true
+        Ok(())
+    }
+}
+
+// Calculates a*G + b*P in constant time, where P = (x, y). The points are added with the complete
+// formulas, so intermediate sums that hit the point at infinity or equal the added point, as for P = ±G,
+// take the same time as any other sum.
+// The result is in Jacobian coordinates, with Z = 0 for the point at infinity.
+fn double_scalarmult(
+    a: &[u32; 8],
+    b: &[u32; 8],
+    x: &Montgomery,
+    y: &Montgomery,
+) -> [Montgomery; 3] {
+    // Both scalars are made odd as in scalarmult_variable_base. Since there is no common result to
+    // negate afterwards, each selected point is negated instead, using (n-a)*(-G) = a*G.
+    let mut a2: [u32; 8] = [0; 8];
+    let mut b2: [u32; 8] = [0; 8];
+    let a_even = (a[0] & 1) == 0;
+    let b_even = (b[0] & 1) == 0;
+    negate_mod_n_if(&mut a2, a, a_even);
+    negate_mod_n_if(&mut b2, b, b_even);
+
+    let e_a: [i8; 64] = odd_digits(&a2);
+    let e_b: [i8; 64] = odd_digits(&b2);
+
+    // P, 3P, 5P, ..., 15P in homogeneous projective coordinates.
+    let mut table: [complete::Projective; 8] = [complete::IDENTITY; 8];
+    table[0] = [FieldElement(*x), FieldElement(*y), FieldElement::ONE];
+    let two_p: complete::Projective = complete::double(&table[0]);
+    (1..8).for_each(|i| table[i] = complete::add(&table[i - 1], &two_p));
+
+    // The same fixed window as in scalarmult_variable_base, with one addition of a multiple of G
+    // and one of a multiple of P per window.
+    let mut current_point: complete::Projective = complete::IDENTITY;
+
+    (0..64).rev().for_each(|i| {
+        if i != 63 {
+            (0..4).for_each(|_| current_point = complete::double(&current_point));
+        }
+
+        let mut selected_point: complete::Projective = table[(abs_int(e_b[i]) >> 1) as u8 as usize];
+        negate_mod_p_if_in_place(
+            &mut selected_point[1].0,
+            (((e_b[i] as u8) >> 7) == 1) ^ b_even,
+        );
+        current_point = complete::add(&current_point, &selected_point);
+
+        let mut selected_point: [Montgomery; 2] =
+            P256_BASEPOINT_PRECOMP[(abs_int(e_a[i]) >> 1) as u8 as usize];
+        negate_mod_p_if_in_place(
+            &mut selected_point[1],
+            (((e_a[i] as u8) >> 7) == 1) ^ a_even,
+        );
+        current_point = complete::add_affine(&current_point, &selected_point);
+    });
+
+    complete::to_jacobian(&current_point)
+}
+
+// Whether a point in Jacobian coordinates is the point at infinity, with Z = 0.
+fn is_infinity(jacobian: &[Montgomery; 3]) -> bool {
+    (0..8).all(|i| jacobian[2][i] == 0)
+}
+
+// Converts a point in Jacobian coordinates to affine coordinates, failing for the point at infinity.
+fn jacobian_to_affine_checked(
+    output_x: &mut Montgomery,
+    output_y: &mut Montgomery,
+    jacobian: &[Montgomery; 3],
+) -> Result<(), Error> {
+    if is_infinity(jacobian) {
+        Err(Error::PointAtInfinity)
+    } else {
+        jacobian_to_affine(output_x, output_y, jacobian);
+        Ok(())
     }
 }
 
+fn double_scalar_mul_inner(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    a: &[u32; 8],
+    b: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+    constant_time: bool,
+) -> Result<(), Error> {
+    if !check_range_n(a) || !check_range_n(b) {
+        return Err(Error::ScalarOutOfRange);
+    }
+    if !check_range_p(in_x) || !check_range_p(in_y) {
+        return Err(Error::CoordinateOutOfRange);
+    }
+
+    let mut x = Montgomery::zero();
+    let mut y = Montgomery::zero();
+    x.read(in_x);
+    y.read(in_y);
+
+    if !point_is_on_curve(&x, &y) {
+        return Err(Error::NotOnCurve);
+    }
+
+    let jacobian = if constant_time {
+        double_scalarmult(a, b, &x, &y)
+    } else {
+        let table = odd_multiples(&x, &y);
+        double_scalarmult_vartime::<5>(a, b, |cp, i, is_sub| add_sub_j(cp, &table[i], is_sub))
+    };
+    // The conversion inverts zero to zero for the point at infinity, which is only rejected
+    // afterwards so that the time does not depend on the result either.
+    jacobian_to_affine(&mut x, &mut y, &jacobian);
+    if is_infinity(&jacobian) {
+        return Err(Error::PointAtInfinity);
+    }
+
+    x.write(result_x);
+    y.write(result_y);
+    Ok(())
+}
+
+/// Raw double scalar multiplication `a*G + b*P`, where G is the base point of the elliptic curve and P
+/// is any point on the curve, in constant time.
+///
+/// This is faster than two separate scalar multiplications, and is suitable for secret scalars, as
+/// used by e.g. EC-JPAKE and Schnorr-style proofs.  Use [`double_scalar_mul_vartime`] if both
+/// scalars are public.
+///
+/// This function validates that both scalars lie in the range 1 to n-1, where n is the order of the
+/// elliptic curve, and that the input point lies on the curve.  It also fails if the result is the
+/// point at infinity, which has no affine coordinates.  If validation succeeds, true is returned.
+/// Otherwise false is returned.
+#[must_use]
+pub fn double_scalar_mul(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    a: &[u32; 8],
+    b: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+) -> bool {
+    try_double_scalar_mul(result_x, result_y, a, b, in_x, in_y).is_ok()
+}
+
+/// Same as [`double_scalar_mul`], but returns the reason for a failure.
+pub fn try_double_scalar_mul(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    a: &[u32; 8],
+    b: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+) -> Result<(), Error> {
+    double_scalar_mul_inner(result_x, result_y, a, b, in_x, in_y, true)
+}
+
+/// Same as [`double_scalar_mul`], but in variable time using the sliding window of [`verify`], which
+/// is faster.
+///
+/// The execution time depends on the scalars, so this function MUST only be used with public scalars.
+#[must_use]
+pub fn double_scalar_mul_vartime(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    a: &[u32; 8],
+    b: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+) -> bool {
+    try_double_scalar_mul_vartime(result_x, result_y, a, b, in_x, in_y).is_ok()
+}
+
+/// Same as [`double_scalar_mul_vartime`], but returns the reason for a failure.
+pub fn try_double_scalar_mul_vartime(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    a: &[u32; 8],
+    b: &[u32; 8],
+    in_x: &[u32; 8],
+    in_y: &[u32; 8],
+) -> Result<(), Error> {
+    double_scalar_mul_inner(result_x, result_y, a, b, in_x, in_y, false)
+}
+
 /// Sign precomputation state.
 ///
 /// The content shall be treated as opaque to the API user and shall not be inspected or modified.
@@ -471,7 +817,9 @@ pub struct SignPrecomp {
 ///
 /// As an alternative to using a random "k", "k" might be derived deterministically from the input, using a
 /// sophisticated hash construction such as RFC 6979, or e.g. by hashing the private key, message hash and a
-/// retry counter, using a secure hash function such as SHA-256.
+/// retry counter, using a secure hash function such as SHA-256.  With the `rfc6979` feature,
+/// `sign_deterministic` derives "k" according to RFC 6979 and handles the retries.  With the
+/// `rand_core` feature, `sign_with_rng` draws "k" from a random number generator and handles the retries.
 #[must_use]
 pub fn sign(
     r: &mut [u32; 8],
@@ -480,13 +828,24 @@ pub fn sign(
     private_key: &[u32; 8],
     k: &[u32; 8],
 ) -> bool {
+    try_sign(r, s, hash, private_key, k).is_ok()
+}
+
+/// Same as [`sign`], but returns the reason for a failure.
+pub fn try_sign(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    k: &[u32; 8],
+) -> Result<(), Error> {
     let mut t: SignPrecomp = Default::default();
-    if !sign_step1(&mut t, k) {
+    if let Err(e) = try_sign_step1(&mut t, k) {
         r.fill(0);
         s.fill(0);
-        false
+        Err(e)
     } else {
-        sign_step2(r, s, hash, private_key, &mut t)
+        try_sign_step2(r, s, hash, private_key, &mut t)
     }
 }
 
@@ -514,9 +873,14 @@ pub fn sign(
 /// A result state MUST NOT be reused for generating multiple signatures.
 #[must_use]
 pub fn sign_step1(result: &mut SignPrecomp, k: &[u32; 8]) -> bool {
-    'check: {
+    try_sign_step1(result, k).is_ok()
+}
+
+/// Same as [`sign_step1`], but returns the reason for a failure.
+pub fn try_sign_step1(result: &mut SignPrecomp, k: &[u32; 8]) -> Result<(), Error> {
+    let error: Error = 'check: {
         if !check_range_n(k) {
-            break 'check;
+            break 'check Error::ScalarOutOfRange;
         }
 
         let mut output_x = Montgomery::zero();
@@ -531,15 +895,15 @@ pub fn sign_step1(result: &mut SignPrecomp, k: &[u32; 8]) -> bool {
         let r_sum: u32 = (0..8).fold(0, |r_sum, i| r_sum | result.r[i]);
 
         if r_sum == 0 {
-            break 'check;
+            break 'check Error::RIsZero;
         }
 
-        return true;
-    }
+        return Ok(());
+    };
 
     result.r.fill(0);
     result.k_inv.fill(0);
-    false
+    Err(error)
 }
 
 // Takes the leftmost 256 bits in hash (treated as big endian),
@@ -573,10 +937,21 @@ pub fn sign_step2(
     private_key: &[u32; 8],
     sign_precomp: &mut SignPrecomp,
 ) -> bool {
-    'check: {
+    try_sign_step2(r, s, hash, private_key, sign_precomp).is_ok()
+}
+
+/// Same as [`sign_step2`], but returns the reason for a failure.
+pub fn try_sign_step2(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    sign_precomp: &mut SignPrecomp,
+) -> Result<(), Error> {
+    let error: Error = 'check: {
         // just make sure user did not input an obviously invalid precomp
         if !check_range_n(&sign_precomp.k_inv) || !check_range_n(&sign_precomp.r) {
-            break 'check;
+            break 'check Error::InvalidPrecomp;
         }
         hash_to_z(u32x8_to_u8x32_mut(r), hash);
         mul_mod_n(s, &sign_precomp.r, private_key);
@@ -587,23 +962,26 @@ pub fn sign_step2(
 
         let s_sum: u32 = s.iter().fold(0, |s_sum, s| s_sum | s);
         if s_sum == 0 {
-            break 'check;
+            break 'check Error::SIsZero;
         }
         sign_precomp.r.fill(0);
         sign_precomp.k_inv.fill(0);
-        return true;
-    }
+        return Ok(());
+    };
 
+    sign_precomp.r.fill(0);
+    sign_precomp.k_inv.fill(0);
     r.fill(0);
     s.fill(0);
-    false
+    Err(error)
 }
 
 // Creates a representation of a (little endian integer),
 // so that r[0] + 2*r[1] + 2^2*r[2] + 2^3*r[3] + ... = a,
-// where each r[i] is -15, -13, ..., 11, 13, 15 or 0.
-// Only around 1/5.5 of the r[i] will be non-zero.
-fn slide_257(a: &[u8; 32]) -> [i8; 257] {
+// where each r[i] is an odd integer -(2^(W-1)-1) <= r[i] <= 2^(W-1)-1 or 0, e.g. -15, -13, ..., 13, 15 or 0 for W = 5.
+// Only around 1/(W+0.5) of the r[i] will be non-zero. W must be at most 7 for the sums to fit an i8.
+fn slide_257<const W: usize>(a: &[u8; 32]) -> [i8; 257] {
+    let max: i8 = (1 << (W - 1)) - 1;
     let mut r: [i8; 257] = [0; 257];
     (0..256).for_each(|i| {
         r[i] = (1 & (a[i >> 3] >> (i & 7))) as i8;
@@ -613,12 +991,12 @@ fn slide_257(a: &[u8; 32]) -> [i8; 257] {
     (0..256).for_each(|i| {
         if r[i] != 0 {
             let mut b: usize = 1;
-            while b <= 4 && i + b < 256 {
+            while b < W && i + b < 256 {
                 if r[i + b] != 0 {
-                    if r[i] + (r[i + b] << b) <= 15 {
+                    if r[i] + (r[i + b] << b) <= max {
                         r[i] += r[i + b] << b;
                         r[i + b] = 0;
-                    } else if r[i] - (r[i + b] << b) >= -15 {
+                    } else if r[i] - (r[i + b] << b) >= -max {
                         r[i] -= r[i + b] << b;
                         loop {
                             r[i + b] = 0;
@@ -641,59 +1019,19 @@ fn slide_257(a: &[u8; 32]) -> [i8; 257] {
     r
 }
 
-/// Verifies an ECDSA signature.
-///
-/// Returns true if the signature is valid for the given input, otherwise false.
-#[must_use = "The return value indicates if the message is authentic"]
-pub fn verify(
-    public_key_x: &[u32; 8],
-    public_key_y: &[u32; 8],
-    hash: &[u8],
-    r: &[u32; 8],
-    s: &[u32; 8],
-) -> bool {
-    if !check_range_n(r) || !check_range_n(s) {
-        return false;
-    }
-
-    if !check_range_p(public_key_x) || !check_range_p(public_key_y) {
-        return false;
-    }
-
-    let mut pk_table = [[Montgomery::zero(); 3]; 8];
-    pk_table[0][0].read(public_key_x);
-    pk_table[0][1].read(public_key_y);
-    pk_table[0][2] = Montgomery::one();
-
-    if !point_is_on_curve(&pk_table[0][0], &pk_table[0][1]) {
-        return false;
-    }
+// Calculates u1*G + u2*P in variable time, where add_sub_pk(cp, i, is_sub) adds or subtracts (2*i+1)*P
+// to or from cp, for the odd multiples up to (2^(W-1)-1)*P.
+// The result is in Jacobian coordinates, with Z = 0 for the point at infinity.
+fn double_scalarmult_vartime<const W: usize>(
+    u1: &[u32; 8],
+    u2: &[u32; 8],
+    add_sub_pk: impl Fn(&mut [Montgomery; 3], usize, bool),
+) -> [Montgomery; 3] {
+    // Each value in these arrays will be an odd integer v, so that -15 <= v <= 15 for the base point,
+    // and -(2^(W-1)-1) <= v <= 2^(W-1)-1 for the public key, or 0.
 
-    // Create a table of P, 3P, 5P, ..., 15P, where P is the public key.
-    let [seven, zero] = pk_table.get_disjoint_mut([7, 0]).unwrap();
-    double_j(seven, zero);
-    (1..8).for_each(|i| {
-        pk_table.copy_within(7..8, i);
-        let [i, i_min_one] = pk_table.get_disjoint_mut([i, i - 1]).unwrap();
-        add_sub_j(i, i_min_one, false);
-    });
-
-    let mut z: [u32; 8] = [0; 8];
-    hash_to_z(u32x8_to_u8x32_mut(&mut z), hash);
-
-    let mut w: [u32; 8] = [0; 8];
-    mod_n_inv(&mut w, s);
-
-    let mut u1: [u32; 8] = [0; 8];
-    mul_mod_n(&mut u1, &z, &w);
-    let mut u2: [u32; 8] = [0; 8];
-    mul_mod_n(&mut u2, r, &w);
-
-    // Each value in these arrays will be an odd integer v, so that -15 <= v <= 15.
-    // Around 1/5.5 of them will be non-zero.
-
-    let slide_bp: [i8; 257] = slide_257(u32x8_to_u8x32(&u1));
-    let slide_pk: [i8; 257] = slide_257(u32x8_to_u8x32(&u2));
+    let slide_bp: [i8; 257] = slide_257::<5>(u32x8_to_u8x32(u1));
+    let slide_pk: [i8; 257] = slide_257::<W>(u32x8_to_u8x32(u2));
 
     let mut cp = [Montgomery::zero(); 3];
 
@@ -726,12 +1064,97 @@ pub fn verify(
             };
 
             if let Some((pk_idx, is_sub)) = pk_op {
-                let pk_table = &pk_table[pk_idx as usize];
-                add_sub_j(&mut cp, pk_table, is_sub);
+                add_sub_pk(&mut cp, pk_idx as usize, is_sub);
             }
         });
 
-    verify_last_step(r, &cp)
+    cp
+}
+
+// Calculates u1 = z*w and u2 = r*w for w = s^-1, where z is the hash truncated to an integer,
+// so that u1*G + u2*P has the x coordinate r mod n for a valid signature.
+fn verify_scalars(hash: &[u8], r: &[u32; 8], w: &[u32; 8]) -> ([u32; 8], [u32; 8]) {
+    let mut z: [u32; 8] = [0; 8];
+    hash_to_z(u32x8_to_u8x32_mut(&mut z), hash);
+
+    let mut u1: [u32; 8] = [0; 8];
+    mul_mod_n(&mut u1, &z, w);
+    let mut u2: [u32; 8] = [0; 8];
+    mul_mod_n(&mut u2, r, w);
+
+    (u1, u2)
+}
+
+/// Verifies an ECDSA signature.
+///
+/// Returns true if the signature is valid for the given input, otherwise false.
+#[must_use = "The return value indicates if the message is authentic"]
+pub fn verify(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    hash: &[u8],
+    r: &[u32; 8],
+    s: &[u32; 8],
+) -> bool {
+    try_verify(public_key_x, public_key_y, hash, r, s).is_ok()
+}
+
+/// Same as [`verify`], but returns the reason for a failure.
+///
+/// Returns `Ok(())` if the signature is valid for the given input.
+#[must_use = "The return value indicates if the message is authentic"]
+pub fn try_verify(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    hash: &[u8],
+    r: &[u32; 8],
+    s: &[u32; 8],
+) -> Result<(), Error> {
+    if !check_range_n(r) || !check_range_n(s) {
+        return Err(Error::SignatureOutOfRange);
+    }
+
+    let mut w: [u32; 8] = [0; 8];
+    mod_n_inv(&mut w, s);
+
+    verify_with_s_inv(public_key_x, public_key_y, hash, r, &w)
+}
+
+// The rest of try_verify after the range check of r and s, given w = s^-1.
+fn verify_with_s_inv(
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    hash: &[u8],
+    r: &[u32; 8],
+    w: &[u32; 8],
+) -> Result<(), Error> {
+    if !check_range_p(public_key_x) || !check_range_p(public_key_y) {
+        return Err(Error::CoordinateOutOfRange);
+    }
+
+    let mut x = Montgomery::zero();
+    let mut y = Montgomery::zero();
+    x.read(public_key_x);
+    y.read(public_key_y);
+
+    if !point_is_on_curve(&x, &y) {
+        return Err(Error::NotOnCurve);
+    }
+
+    // Create a table of P, 3P, 5P, ..., 15P, where P is the public key.
+    let pk_table = odd_multiples(&x, &y);
+
+    let (u1, u2) = verify_scalars(hash, r, w);
+
+    let cp = double_scalarmult_vartime::<5>(&u1, &u2, |cp, i, is_sub| {
+        add_sub_j(cp, &pk_table[i], is_sub)
+    });
+
+    if verify_last_step(r, &cp) {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
 }
 
 #[repr(C)]
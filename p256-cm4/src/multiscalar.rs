@@ -0,0 +1,208 @@
+//! Variable time multi-scalar multiplication, for sums of many scalar-point products.
+
+use crate::{
+    Error, check_range_n, check_range_p, is_infinity, jacobian_to_affine_checked, odd_multiples,
+    slide_257,
+    sys::{Montgomery, add_sub_j, add_sub_j_affine, double_j_inplace, point_is_on_curve},
+    u32x8_to_u8x32,
+};
+
+/// From this number of terms on the bucket method is used, below it the interleaved sliding
+/// windows.  The sliding windows cost about 50 additions per term, the buckets about 30
+/// additions per term and a fixed 2500 to 4500 additions for combining the buckets.
+const PIPPENGER_THRESHOLD: usize = 128;
+
+/// Scratch space for one term of [`multiscalar_mul`].
+///
+/// The content shall be treated as opaque to the API user and shall not be inspected or modified.
+#[derive(Debug, Copy, Clone)]
+pub struct MultiscalarScratch {
+    point: [Montgomery; 2],
+    digits: [i8; 257],
+    table: [[Montgomery; 3]; 8],
+}
+
+impl MultiscalarScratch {
+    /// Create zeroed scratch space, for use in array initializers.
+    pub const fn new() -> Self {
+        Self {
+            point: [Montgomery::zero(); 2],
+            digits: [0; 257],
+            table: [[Montgomery::zero(); 3]; 8],
+        }
+    }
+}
+
+impl Default for MultiscalarScratch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Raw multi-scalar multiplication `s[0]*P[0] + s[1]*P[1] + ... + s[k-1]*P[k-1]`, in variable time.
+///
+/// Each term is given as `(scalar, x, y)`, and needs one element of `scratch`, so `scratch` must
+/// be at least as long as `terms`.  This is much faster than separate scalar multiplications,
+/// using interleaved sliding windows as in [`verify`](crate::verify) for few terms, and the bucket
+/// method of Pippenger for many terms.
+///
+/// The execution time depends on the scalars, so this function MUST only be used with public scalars.
+///
+/// This function validates that the scalars lie in the range 0 to n-1, where n is the order of the
+/// elliptic curve, and that the input points lie on the curve.  It also fails if `scratch` is too
+/// short, or if the result is the point at infinity, which has no affine coordinates.  If validation
+/// succeeds, true is returned.  Otherwise false is returned.
+#[must_use]
+pub fn multiscalar_mul(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    terms: &[([u32; 8], [u32; 8], [u32; 8])],
+    scratch: &mut [MultiscalarScratch],
+) -> bool {
+    try_multiscalar_mul(result_x, result_y, terms, scratch).is_ok()
+}
+
+/// Same as [`multiscalar_mul`], but returns the reason for a failure.
+pub fn try_multiscalar_mul(
+    result_x: &mut [u32; 8],
+    result_y: &mut [u32; 8],
+    terms: &[([u32; 8], [u32; 8], [u32; 8])],
+    scratch: &mut [MultiscalarScratch],
+) -> Result<(), Error> {
+    let scratch = scratch
+        .get_mut(..terms.len())
+        .ok_or(Error::BufferTooSmall)?;
+
+    for ((scalar, x, y), s) in terms.iter().zip(scratch.iter_mut()) {
+        // zero is allowed, the term is skipped
+        if !check_range_n(scalar) && *scalar != [0; 8] {
+            return Err(Error::ScalarOutOfRange);
+        }
+        if !check_range_p(x) || !check_range_p(y) {
+            return Err(Error::CoordinateOutOfRange);
+        }
+        s.point[0].read(x);
+        s.point[1].read(y);
+        if !point_is_on_curve(&s.point[0], &s.point[1]) {
+            return Err(Error::NotOnCurve);
+        }
+    }
+
+    let jacobian = if terms.len() < PIPPENGER_THRESHOLD {
+        straus(terms, scratch)
+    } else {
+        pippenger(terms, scratch)
+    };
+
+    let mut x = Montgomery::zero();
+    let mut y = Montgomery::zero();
+    jacobian_to_affine_checked(&mut x, &mut y, &jacobian)?;
+    x.write(result_x);
+    y.write(result_y);
+    Ok(())
+}
+
+// Interleaved sliding windows, the same as in verify with a table of P, 3P, 5P, ..., 15P for every term.
+fn straus(
+    terms: &[([u32; 8], [u32; 8], [u32; 8])],
+    scratch: &mut [MultiscalarScratch],
+) -> [Montgomery; 3] {
+    terms
+        .iter()
+        .zip(scratch.iter_mut())
+        .for_each(|((scalar, _, _), s)| {
+            s.digits = slide_257::<5>(u32x8_to_u8x32(scalar));
+            s.table = odd_multiples(&s.point[0], &s.point[1]);
+        });
+
+    let mut cp = [Montgomery::zero(); 3];
+    (0..257).rev().for_each(|i| {
+        double_j_inplace(&mut cp);
+
+        scratch.iter().for_each(|s| {
+            let digit: i8 = s.digits[i];
+            if digit != 0 {
+                let table = &s.table[(digit.unsigned_abs() / 2) as usize];
+                add_sub_j(&mut cp, table, digit < 0);
+            }
+        });
+    });
+
+    cp
+}
+
+// Bits start..start + width of the scalar, where bits above 255 are 0.
+fn window(scalar: &[u32; 8], start: usize, width: usize) -> u32 {
+    let limb = |i: usize| scalar.get(i).copied().unwrap_or(0) as u64;
+    let bits: u64 = limb(start / 32) | (limb(start / 32 + 1) << 32);
+    ((bits >> (start % 32)) as u32) & ((1 << width) - 1)
+}
+
+// The window width with the least additions for the given number of terms, counting
+// (256 / width + 1) windows of one addition per term and two per bucket.
+fn pippenger_width(terms: usize) -> usize {
+    (4..=8)
+        .min_by_key(|width| (256 / width + 1) * (terms + (1 << width)))
+        .unwrap()
+}
+
+// The bucket method of Pippenger with signed digits, where the buckets are stored in the tables of the
+// scratch space, which has room for 8 buckets per term.
+fn pippenger(
+    terms: &[([u32; 8], [u32; 8], [u32; 8])],
+    scratch: &mut [MultiscalarScratch],
+) -> [Montgomery; 3] {
+    let width: usize = pippenger_width(terms.len());
+    let windows: usize = 256 / width + 1;
+    let buckets: usize = 1 << (width - 1);
+    debug_assert!(buckets <= 8 * scratch.len());
+
+    // Rewrite each scalar as d[0] + 2^width*d[1] + 2^(2*width)*d[2] + ..., where -2^(width-1) <= d[i] < 2^(width-1).
+    terms
+        .iter()
+        .zip(scratch.iter_mut())
+        .for_each(|((scalar, _, _), s)| {
+            let mut carry: u32 = 0;
+            (0..windows).for_each(|i| {
+                let digit: u32 = window(scalar, i * width, width) + carry;
+                carry = (digit + (1 << (width - 1))) >> width;
+                s.digits[i] = (digit as i32 - ((carry as i32) << width)) as i8;
+            });
+        });
+
+    let mut cp = [Montgomery::zero(); 3];
+    (0..windows).rev().for_each(|i| {
+        (0..width).for_each(|_| double_j_inplace(&mut cp));
+
+        (0..buckets).for_each(|b| scratch[b / 8].table[b % 8] = [Montgomery::zero(); 3]);
+
+        // Sort the points into the buckets, bucket b collects the points with a digit of +-(b + 1).
+        (0..scratch.len()).for_each(|j| {
+            let digit: i8 = scratch[j].digits[i];
+            if digit != 0 {
+                let point = scratch[j].point;
+                let b = (digit.unsigned_abs() - 1) as usize;
+                add_sub_j_affine(&mut scratch[b / 8].table[b % 8], &point, digit < 0);
+            }
+        });
+
+        // Sum up (b + 1) times bucket b, by adding up the running sums of the buckets from the top.
+        let mut running = [Montgomery::zero(); 3];
+        let mut sum = [Montgomery::zero(); 3];
+        (0..buckets).rev().for_each(|b| {
+            let bucket = &scratch[b / 8].table[b % 8];
+            if !is_infinity(bucket) {
+                add_sub_j(&mut running, bucket, false);
+            }
+            if !is_infinity(&running) {
+                add_sub_j(&mut sum, &running, false);
+            }
+        });
+
+        if !is_infinity(&sum) {
+            add_sub_j(&mut cp, &sum, false);
+        }
+    });
+
+    cp
+}
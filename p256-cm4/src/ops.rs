@@ -0,0 +1,111 @@
+//! Operator implementations for the arithmetic types, in terms of their inherent methods.
+
+/// Implement a binary operator and its assigning variant for all combinations of owned
+/// and borrowed operands, in terms of a function `fn(&$lhs, &$rhs) -> $lhs`.
+macro_rules! impl_binop {
+    ($lhs:ty, $rhs:ty, $op:ident::$op_fn:ident, $assign:ident::$assign_fn:ident, $func:expr) => {
+        impl core::ops::$op<$rhs> for $lhs {
+            type Output = $lhs;
+
+            fn $op_fn(self, rhs: $rhs) -> $lhs {
+                $func(&self, &rhs)
+            }
+        }
+
+        impl core::ops::$op<&$rhs> for $lhs {
+            type Output = $lhs;
+
+            fn $op_fn(self, rhs: &$rhs) -> $lhs {
+                $func(&self, rhs)
+            }
+        }
+
+        impl core::ops::$op<$rhs> for &$lhs {
+            type Output = $lhs;
+
+            fn $op_fn(self, rhs: $rhs) -> $lhs {
+                $func(self, &rhs)
+            }
+        }
+
+        impl core::ops::$op<&$rhs> for &$lhs {
+            type Output = $lhs;
+
+            fn $op_fn(self, rhs: &$rhs) -> $lhs {
+                $func(self, rhs)
+            }
+        }
+
+        impl core::ops::$assign<$rhs> for $lhs {
+            fn $assign_fn(&mut self, rhs: $rhs) {
+                *self = $func(self, &rhs);
+            }
+        }
+
+        impl core::ops::$assign<&$rhs> for $lhs {
+            fn $assign_fn(&mut self, rhs: &$rhs) {
+                *self = $func(self, rhs);
+            }
+        }
+    };
+}
+
+/// Implement [`Neg`](core::ops::Neg), [`Sum`](core::iter::Sum) and the additive operators
+/// for a type with `add`, `sub` and `neg` methods, and the additive identity `$zero`.
+macro_rules! impl_additive_ops {
+    ($ty:ty, $zero:expr) => {
+        $crate::ops::impl_binop!($ty, $ty, Add::add, AddAssign::add_assign, <$ty>::add);
+        $crate::ops::impl_binop!($ty, $ty, Sub::sub, SubAssign::sub_assign, <$ty>::sub);
+
+        impl core::ops::Neg for $ty {
+            type Output = $ty;
+
+            fn neg(self) -> $ty {
+                <$ty>::neg(&self)
+            }
+        }
+
+        impl core::ops::Neg for &$ty {
+            type Output = $ty;
+
+            fn neg(self) -> $ty {
+                <$ty>::neg(self)
+            }
+        }
+
+        impl core::iter::Sum for $ty {
+            fn sum<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                iter.fold($zero, |acc, x| <$ty>::add(&acc, &x))
+            }
+        }
+
+        impl<'a> core::iter::Sum<&'a $ty> for $ty {
+            fn sum<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                iter.fold($zero, |acc, x| <$ty>::add(&acc, x))
+            }
+        }
+    };
+}
+
+/// Implement [`Product`](core::iter::Product) and the multiplicative operators for a field
+/// type with a `multiply` method, in addition to the additive ones.
+macro_rules! impl_field_ops {
+    ($ty:ty) => {
+        $crate::ops::impl_additive_ops!($ty, <$ty>::ZERO);
+        $crate::ops::impl_binop!($ty, $ty, Mul::mul, MulAssign::mul_assign, <$ty>::multiply);
+
+        impl core::iter::Product for $ty {
+            fn product<I: Iterator<Item = $ty>>(iter: I) -> $ty {
+                iter.fold(<$ty>::ONE, |acc, x| acc.multiply(&x))
+            }
+        }
+
+        impl<'a> core::iter::Product<&'a $ty> for $ty {
+            fn product<I: Iterator<Item = &'a $ty>>(iter: I) -> $ty {
+                iter.fold(<$ty>::ONE, |acc, x| acc.multiply(x))
+            }
+        }
+    };
+}
+
+pub(crate) use {impl_additive_ops, impl_binop, impl_field_ops};
@@ -0,0 +1,60 @@
+//! PEM armor of RFC 7468, base64 encoded DER between `-----BEGIN <label>-----` and
+//! `-----END <label>-----` lines, without allocating.
+
+use crate::{Error, base64};
+
+// Line length of the base64 text, as written by openssl.
+const LINE_LEN: usize = 64;
+
+/// Length of the PEM armor of "der_len" bytes with the label "label_len" bytes long,
+/// with `\n` line endings, including the line ending after the end line.
+pub(crate) const fn pem_len(label_len: usize, der_len: usize) -> usize {
+    let base64_len: usize = base64::encoded_len(der_len, true);
+    // "-----BEGIN " label "-----\n", and "-----END " label "-----\n"
+    (11 + label_len + 6) + base64_len + base64_len.div_ceil(LINE_LEN) + (9 + label_len + 6)
+}
+
+fn put(out: &mut [u8], pos: &mut usize, bytes: &[u8]) {
+    out[*pos..*pos + bytes.len()].copy_from_slice(bytes);
+    *pos += bytes.len();
+}
+
+/// Writes the PEM armor of "der" to "out", which must be `pem_len(label.len(), der.len())`
+/// bytes long.
+pub(crate) fn encode(out: &mut [u8], label: &str, der: &[u8]) {
+    let mut pos: usize = 0;
+    put(out, &mut pos, b"-----BEGIN ");
+    put(out, &mut pos, label.as_bytes());
+    put(out, &mut pos, b"-----\n");
+
+    // 48 bytes of DER are 64 bytes of base64
+    der.chunks(LINE_LEN / 4 * 3).for_each(|line| {
+        let len: usize = base64::encoded_len(line.len(), true);
+        base64::encode(&mut out[pos..pos + len], line, base64::STANDARD, true);
+        pos += len;
+        put(out, &mut pos, b"\n");
+    });
+
+    put(out, &mut pos, b"-----END ");
+    put(out, &mut pos, label.as_bytes());
+    put(out, &mut pos, b"-----\n");
+}
+
+/// Decodes the PEM armor with the given label into "out", returns the decoded DER.
+///
+/// Whitespace around the armor and between the base64 characters is ignored, the padding
+/// must be canonical, and the decoded data must fit in "out".
+pub(crate) fn decode<'a>(out: &'a mut [u8], label: &str, pem: &[u8]) -> Result<&'a [u8], Error> {
+    let body: &[u8] = pem
+        .trim_ascii()
+        .strip_prefix(b"-----BEGIN ")
+        .and_then(|rest| rest.strip_prefix(label.as_bytes()))
+        .and_then(|rest| rest.strip_prefix(b"-----"))
+        .and_then(|rest| rest.strip_suffix(b"-----"))
+        .and_then(|rest| rest.strip_suffix(label.as_bytes()))
+        .and_then(|rest| rest.strip_suffix(b"-----END "))
+        .ok_or(Error::InvalidPem)?;
+
+    let chars = body.iter().copied().filter(|c| !c.is_ascii_whitespace());
+    base64::decode(out, chars, base64::STANDARD, true).ok_or(Error::InvalidPem)
+}
@@ -0,0 +1,100 @@
+//! Public keys with a precomputed table, for verifying many signatures with the same key.
+
+use crate::{
+    Error, check_range_n, check_range_p, double_scalarmult_vartime,
+    invert::batch_jacobian_to_affine,
+    mod_n_inv,
+    sys::{Montgomery, add_sub_j, add_sub_j_affine, double_j, point_is_on_curve, verify_last_step},
+    verify_scalars,
+};
+
+/// Width of the sliding window for the public key, [`verify`](crate::verify) uses 5.
+const WINDOW: usize = 7;
+
+/// Number of odd multiples of the public key in the table, P, 3P, 5P, ..., 63P.
+const TABLE_LEN: usize = 1 << (WINDOW - 2);
+
+/// A public key with a precomputed table of its multiples, for verifying many signatures with
+/// the same key, such as a firmware update signing key.
+///
+/// [`verify`](crate::verify) creates a table of 8 multiples of the public key in Jacobian
+/// coordinates for every signature.  This table is created once, holds 32 multiples in affine
+/// coordinates, and needs fewer and cheaper point additions per verification.  It takes 2 KiB,
+/// and is not `Copy` so that it is not duplicated by accident.
+///
+/// The multiplication of the base point is the same as in [`verify`](crate::verify), so the
+/// saving is limited to the additions of the public key, about 10 to 15% of the time of a
+/// verification with the portable backend.  The `basic` testsuite binary prints the cycle counts
+/// of both on the Cortex-M4.
+#[derive(Debug, Clone)]
+pub struct PreparedPublicKey {
+    table: [[Montgomery; 2]; TABLE_LEN],
+}
+
+impl PreparedPublicKey {
+    /// Precompute the table for the public key `(public_key_x, public_key_y)`.
+    ///
+    /// Fails with the same errors as [`try_verify`](crate::try_verify) for an invalid public key.
+    pub fn new(public_key_x: &[u32; 8], public_key_y: &[u32; 8]) -> Result<Self, Error> {
+        if !check_range_p(public_key_x) || !check_range_p(public_key_y) {
+            return Err(Error::CoordinateOutOfRange);
+        }
+
+        let mut x = Montgomery::zero();
+        let mut y = Montgomery::zero();
+        x.read(public_key_x);
+        y.read(public_key_y);
+
+        if !point_is_on_curve(&x, &y) {
+            return Err(Error::NotOnCurve);
+        }
+
+        // Create a table of P, 3P, 5P, ..., 63P by repeatedly adding 2P.
+        let mut jacobian = [[Montgomery::zero(); 3]; TABLE_LEN];
+        jacobian[0] = [x, y, Montgomery::one()];
+        let mut two = [Montgomery::zero(); 3];
+        double_j(&mut two, &jacobian[0]);
+        (1..TABLE_LEN).for_each(|i| {
+            jacobian[i] = jacobian[i - 1];
+            add_sub_j(&mut jacobian[i], &two, false);
+        });
+
+        let mut table = [[Montgomery::zero(); 2]; TABLE_LEN];
+        batch_jacobian_to_affine(|i| jacobian[i], &mut table, |p| &mut p[0], |p| &mut p[1]);
+
+        Ok(Self { table })
+    }
+
+    /// Verifies an ECDSA signature, the same as [`verify`](crate::verify).
+    ///
+    /// Returns true if the signature is valid for the given input, otherwise false.
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify(&self, hash: &[u8], r: &[u32; 8], s: &[u32; 8]) -> bool {
+        self.try_verify(hash, r, s).is_ok()
+    }
+
+    /// Same as [`PreparedPublicKey::verify`], but returns the reason for a failure.
+    ///
+    /// Returns `Ok(())` if the signature is valid for the given input.
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn try_verify(&self, hash: &[u8], r: &[u32; 8], s: &[u32; 8]) -> Result<(), Error> {
+        if !check_range_n(r) || !check_range_n(s) {
+            return Err(Error::SignatureOutOfRange);
+        }
+
+        let mut w: [u32; 8] = [0; 8];
+        mod_n_inv(&mut w, s);
+
+        let (u1, u2) = verify_scalars(hash, r, &w);
+
+        let cp = double_scalarmult_vartime::<WINDOW>(&u1, &u2, |cp, i, is_sub| {
+            add_sub_j_affine(cp, &self.table[i], is_sub)
+        });
+
+        if verify_last_step(r, &cp) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! DER encodings of private keys.
+//!
+//! The SEC1 `ECPrivateKey` of RFC 5915:
+//!
+//! ```text
+//! ECPrivateKey ::= SEQUENCE {
+//!     version        INTEGER { ecPrivkeyVer1(1) },
+//!     privateKey     OCTET STRING,
+//!     parameters [0] ECParameters {{ NamedCurve }} OPTIONAL,
+//!     publicKey  [1] BIT STRING OPTIONAL
+//! }
+//! ```
+//!
+//! And the PKCS#8 `PrivateKeyInfo` of RFC 5208, wrapping an `ECPrivateKey`:
+//!
+//! ```text
+//! PrivateKeyInfo ::= SEQUENCE {
+//!     version             INTEGER { v1(0) },
+//!     privateKeyAlgorithm AlgorithmIdentifier { id-ecPublicKey, prime256v1 },
+//!     privateKey          OCTET STRING
+//! }
+//! ```
+
+use crate::{
+    Error, check_range_n, convert_endianness,
+    der::{TAG_SEQUENCE, decode_tlv},
+    keygen, point_to_octet_string_uncompressed, try_octet_string_to_point, u32x8_to_u8x32,
+    u32x8_to_u8x32_mut,
+};
+
+const TAG_OCTET_STRING: u8 = 0x04;
+pub(crate) const TAG_BIT_STRING: u8 = 0x03;
+const TAG_PARAMETERS: u8 = 0xa0;
+const TAG_PUBLIC_KEY: u8 = 0xa1;
+
+/// DER encoded object identifier of the prime256v1 curve, 1.2.840.10045.3.1.7
+const OID_PRIME256V1: [u8; 10] = [0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+/// DER encoded algorithm identifier of id-ecPublicKey, 1.2.840.10045.2.1, with the
+/// prime256v1 curve as parameters.
+pub(crate) const ALGORITHM_EC_PRIME256V1: [u8; 21] = [
+    0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48,
+    0xce, 0x3d, 0x03, 0x01, 0x07,
+];
+
+/// Length of a SEC1 DER encoded private key, with the curve parameters and public key.
+pub const SEC1_PRIVATE_KEY_DER_LEN: usize = 121;
+
+/// Length of a PKCS#8 DER encoded private key, with the public key.
+pub const PKCS8_PRIVATE_KEY_DER_LEN: usize = 138;
+
+// Writes an `ECPrivateKey` with the public key, and the curve parameters if requested.
+// Returns the number of bytes written.
+fn encode_ec_private_key(
+    out: &mut [u8],
+    private_key: &[u32; 8],
+    public_key_x: &[u32; 8],
+    public_key_y: &[u32; 8],
+    with_parameters: bool,
+) -> usize {
+    let parameters_len: usize = if with_parameters {
+        2 + OID_PRIME256V1.len()
+    } else {
+        0
+    };
+    // version, private key and public key
+    let content_len: usize = 3 + 34 + 70 + parameters_len;
+
+    out[..7].copy_from_slice(&[
+        TAG_SEQUENCE,
+        content_len as u8,
+        0x02,
+        0x01,
+        0x01,
+        TAG_OCTET_STRING,
+        32,
+    ]);
+    convert_endianness(
+        (&mut out[7..39]).try_into().unwrap(),
+        u32x8_to_u8x32(private_key),
+    );
+    let mut pos: usize = 39;
+    if with_parameters {
+        out[pos..pos + 2].copy_from_slice(&[TAG_PARAMETERS, OID_PRIME256V1.len() as u8]);
+        out[pos + 2..pos + parameters_len].copy_from_slice(&OID_PRIME256V1);
+        pos += parameters_len;
+    }
+    // the bit string starts with the number of unused bits
+    out[pos..pos + 5].copy_from_slice(&[TAG_PUBLIC_KEY, 68, TAG_BIT_STRING, 66, 0]);
+    point_to_octet_string_uncompressed(
+        (&mut out[pos + 5..pos + 70]).try_into().unwrap(),
+        public_key_x,
+        public_key_y,
+    );
+    pos + 70
+}
+
+// Parses an `ECPrivateKey`, and calculates the public key, which is compared with the
+// embedded public key if present.
+fn decode_ec_private_key(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> Result<(), Error> {
+    let (sequence, []) = decode_tlv(der, TAG_SEQUENCE)? else {
+        return Err(Error::InvalidDer);
+    };
+    let [0x02, 0x01, 0x01, rest @ ..] = sequence else {
+        return Err(Error::InvalidDer);
+    };
+    let (scalar, mut rest) = decode_tlv(rest, TAG_OCTET_STRING)?;
+    let scalar: &[u8; 32] = scalar.try_into().map_err(|_| Error::InvalidDer)?;
+    convert_endianness(u32x8_to_u8x32_mut(private_key), scalar);
+    if !check_range_n(private_key) {
+        return Err(Error::ScalarOutOfRange);
+    }
+
+    if let Ok((parameters, remaining)) = decode_tlv(rest, TAG_PARAMETERS) {
+        if parameters != OID_PRIME256V1 {
+            return Err(Error::InvalidDer);
+        }
+        rest = remaining;
+    }
+
+    // cannot fail, the private key is in range
+    let _ = keygen(public_key_x, public_key_y, private_key);
+
+    if let Ok((public_key, remaining)) = decode_tlv(rest, TAG_PUBLIC_KEY) {
+        let ([0x00, point @ ..], []) = decode_tlv(public_key, TAG_BIT_STRING)? else {
+            return Err(Error::InvalidDer);
+        };
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_octet_string_to_point(&mut x, &mut y, point)?;
+        if (x, y) != (*public_key_x, *public_key_y) {
+            return Err(Error::PublicKeyMismatch);
+        }
+        rest = remaining;
+    }
+
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidDer)
+    }
+}
+
+fn decode_pkcs8(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> Result<(), Error> {
+    let (sequence, []) = decode_tlv(der, TAG_SEQUENCE)? else {
+        return Err(Error::InvalidDer);
+    };
+    let [0x02, 0x01, 0x00, rest @ ..] = sequence else {
+        return Err(Error::InvalidDer);
+    };
+    let Some(rest) = rest.strip_prefix(&ALGORITHM_EC_PRIME256V1) else {
+        return Err(Error::InvalidDer);
+    };
+    // attributes are not supported
+    let (ec_private_key, []) = decode_tlv(rest, TAG_OCTET_STRING)? else {
+        return Err(Error::InvalidDer);
+    };
+    decode_ec_private_key(private_key, public_key_x, public_key_y, ec_private_key)
+}
+
+// Zeroes the outputs if decoding failed.
+pub(crate) fn clear_on_error(
+    result: Result<(), Error>,
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+) -> Result<(), Error> {
+    if result.is_err() {
+        private_key.fill(0);
+        public_key_x.fill(0);
+        public_key_y.fill(0);
+    }
+    result
+}
+
+/// Encodes a private key as a SEC1 `ECPrivateKey`, with the curve parameters and public key.
+///
+/// Returns false if the private key is not in the range `1..=n-1`.
+#[must_use]
+pub fn private_key_to_sec1_der(
+    out: &mut [u8; SEC1_PRIVATE_KEY_DER_LEN],
+    private_key: &[u32; 8],
+) -> bool {
+    try_private_key_to_sec1_der(out, private_key).is_ok()
+}
+
+/// Same as [`private_key_to_sec1_der`], but returns the reason for a failure.
+pub fn try_private_key_to_sec1_der(
+    out: &mut [u8; SEC1_PRIVATE_KEY_DER_LEN],
+    private_key: &[u32; 8],
+) -> Result<(), Error> {
+    let mut public_key_x: [u32; 8] = [0; 8];
+    let mut public_key_y: [u32; 8] = [0; 8];
+    keygen_or_clear(&mut public_key_x, &mut public_key_y, private_key, out)?;
+    encode_ec_private_key(out, private_key, &public_key_x, &public_key_y, true);
+    Ok(())
+}
+
+/// Decodes a SEC1 `ECPrivateKey`, and calculates the public key.
+///
+/// The decoding is strict.  The private key must be 32 bytes and in the range `1..=n-1`, the
+/// curve parameters must be prime256v1 if present, and the public key must belong to the
+/// private key if present.
+#[must_use]
+pub fn private_key_from_sec1_der(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> bool {
+    try_private_key_from_sec1_der(private_key, public_key_x, public_key_y, der).is_ok()
+}
+
+/// Same as [`private_key_from_sec1_der`], but returns the reason for a failure.
+pub fn try_private_key_from_sec1_der(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> Result<(), Error> {
+    let result = decode_ec_private_key(private_key, public_key_x, public_key_y, der);
+    clear_on_error(result, private_key, public_key_x, public_key_y)
+}
+
+/// Encodes a private key as a PKCS#8 `PrivateKeyInfo`, with the public key.
+///
+/// Returns false if the private key is not in the range `1..=n-1`.
+#[must_use]
+pub fn private_key_to_pkcs8_der(
+    out: &mut [u8; PKCS8_PRIVATE_KEY_DER_LEN],
+    private_key: &[u32; 8],
+) -> bool {
+    try_private_key_to_pkcs8_der(out, private_key).is_ok()
+}
+
+/// Same as [`private_key_to_pkcs8_der`], but returns the reason for a failure.
+pub fn try_private_key_to_pkcs8_der(
+    out: &mut [u8; PKCS8_PRIVATE_KEY_DER_LEN],
+    private_key: &[u32; 8],
+) -> Result<(), Error> {
+    let mut public_key_x: [u32; 8] = [0; 8];
+    let mut public_key_y: [u32; 8] = [0; 8];
+    keygen_or_clear(&mut public_key_x, &mut public_key_y, private_key, out)?;
+
+    // the curve parameters are in the algorithm identifier
+    let len: usize = PKCS8_PRIVATE_KEY_DER_LEN - 3;
+    out[..6].copy_from_slice(&[TAG_SEQUENCE, 0x81, len as u8, 0x02, 0x01, 0x00]);
+    out[6..27].copy_from_slice(&ALGORITHM_EC_PRIME256V1);
+    out[27..29].copy_from_slice(&[TAG_OCTET_STRING, (PKCS8_PRIVATE_KEY_DER_LEN - 29) as u8]);
+    encode_ec_private_key(
+        &mut out[29..],
+        private_key,
+        &public_key_x,
+        &public_key_y,
+        false,
+    );
+    Ok(())
+}
+
+/// Decodes a PKCS#8 `PrivateKeyInfo` with an `ECPrivateKey` for the prime256v1 curve, and
+/// calculates the public key.
+///
+/// The decoding is strict, the same as [`private_key_from_sec1_der`].  Only version 1 of
+/// PKCS#8 without attributes is supported.
+#[must_use]
+pub fn private_key_from_pkcs8_der(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> bool {
+    try_private_key_from_pkcs8_der(private_key, public_key_x, public_key_y, der).is_ok()
+}
+
+/// Same as [`private_key_from_pkcs8_der`], but returns the reason for a failure.
+pub fn try_private_key_from_pkcs8_der(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    der: &[u8],
+) -> Result<(), Error> {
+    let result = decode_pkcs8(private_key, public_key_x, public_key_y, der);
+    clear_on_error(result, private_key, public_key_x, public_key_y)
+}
+
+// Calculates the public key, or zeroes the output if the private key is out of range.
+pub(crate) fn keygen_or_clear(
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    private_key: &[u32; 8],
+    out: &mut [u8],
+) -> Result<(), Error> {
+    if keygen(public_key_x, public_key_y, private_key) {
+        Ok(())
+    } else {
+        out.fill(0);
+        Err(Error::ScalarOutOfRange)
+    }
+}
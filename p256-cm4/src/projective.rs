@@ -0,0 +1,223 @@
+//! Points in Jacobian coordinates.
+
+use crate::{
+    AffinePoint, FieldElement, Scalar,
+    invert::batch_jacobian_to_affine,
+    ops::{impl_additive_ops, impl_binop},
+    scalarmult_fixed_base, scalarmult_variable_base,
+    sys::{Montgomery, add_sub_j, add_sub_j_affine, double_j, jacobian_to_affine},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// A point on the curve in Jacobian coordinates `(X, Y, Z)`, representing the affine
+/// point `(X/Z^2, Y/Z^3)`, or the identity if `Z` is zero.
+///
+/// Additions are not constant time when an operand is the identity, or when both operands
+/// are the same point, the same as the routines used by the functions in the crate root.
+/// The `arithmetic` feature adds the RustCrypto `group` traits.
+#[derive(Debug, Copy, Clone)]
+pub struct ProjectivePoint(pub(crate) [Montgomery; 3]);
+
+impl ProjectivePoint {
+    /// The identity, the point at infinity.
+    pub const IDENTITY: Self = Self([Montgomery::zero(), Montgomery::one(), Montgomery::zero()]);
+
+    /// The base point of the curve.
+    pub const GENERATOR: Self = Self([
+        AffinePoint::GENERATOR.x.0,
+        AffinePoint::GENERATOR.y.0,
+        Montgomery::one(),
+    ]);
+
+    fn coordinates(&self) -> [FieldElement; 3] {
+        self.0.map(FieldElement)
+    }
+
+    fn add_sub(&self, rhs: &Self, is_sub: bool) -> Self {
+        // the identity is only handled for the first operand
+        let mut res = self.0;
+        add_sub_j(&mut res, &rhs.0, is_sub);
+        Self::conditional_select(&Self(res), self, rhs.is_identity())
+    }
+
+    fn add_sub_affine(&self, rhs: &AffinePoint, is_sub: bool) -> Self {
+        let mut res = self.0;
+        add_sub_j_affine(&mut res, &[rhs.x.0, rhs.y.0], is_sub);
+        Self::conditional_select(&Self(res), self, rhs.is_identity())
+    }
+
+    /// Whether the point is the identity.
+    pub fn is_identity(&self) -> Choice {
+        FieldElement(self.0[2]).ct_eq(&FieldElement::ZERO)
+    }
+
+    /// Calculate `self + rhs`.
+    pub fn add(&self, rhs: &Self) -> Self {
+        self.add_sub(rhs, false)
+    }
+
+    /// Calculate `self - rhs`.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        self.add_sub(rhs, true)
+    }
+
+    /// Calculate `self + rhs` for an affine `rhs`, which is faster than a Jacobian addition.
+    pub fn add_affine(&self, rhs: &AffinePoint) -> Self {
+        self.add_sub_affine(rhs, false)
+    }
+
+    /// Calculate `self - rhs` for an affine `rhs`, which is faster than a Jacobian subtraction.
+    pub fn sub_affine(&self, rhs: &AffinePoint) -> Self {
+        self.add_sub_affine(rhs, true)
+    }
+
+    /// Calculate `-self`.
+    pub fn neg(&self) -> Self {
+        let [x, y, z] = self.coordinates();
+        Self([x.0, y.neg().0, z.0])
+    }
+
+    /// Calculate `2 * self`.
+    pub fn double(&self) -> Self {
+        let mut res = Self::IDENTITY;
+        double_j(&mut res.0, &self.0);
+        res
+    }
+
+    /// Convert the point to affine coordinates, which costs a field inversion.
+    pub fn to_affine(&self) -> AffinePoint {
+        let mut x = Montgomery::zero();
+        let mut y = Montgomery::zero();
+        jacobian_to_affine(&mut x, &mut y, &self.0);
+        let point = AffinePoint {
+            x: FieldElement(x),
+            y: FieldElement(y),
+            infinity: 0,
+        };
+        AffinePoint::conditional_select(&point, &AffinePoint::IDENTITY, self.is_identity())
+    }
+
+    /// Convert many points to affine coordinates, which costs a single field inversion and
+    /// 6 multiplications per point.
+    ///
+    /// Not constant time when a point is the identity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    pub fn batch_to_affine(points: &[Self], out: &mut [AffinePoint]) {
+        assert_eq!(points.len(), out.len());
+        batch_jacobian_to_affine(|i| points[i].0, out, |p| &mut p.x.0, |p| &mut p.y.0);
+        out.iter_mut()
+            .zip(points.iter())
+            .for_each(|(out, point)| out.infinity = point.is_identity().unwrap_u8());
+    }
+
+    /// Calculate `scalar * G`, with the precomputed table of the base point used for signing.
+    pub fn mul_by_generator(scalar: &Scalar) -> Self {
+        let is_zero = scalar.ct_eq(&Scalar::ZERO);
+        let scalar = Scalar::conditional_select(scalar, &Scalar::ONE, is_zero);
+
+        let mut x = Montgomery::zero();
+        let mut y = Montgomery::zero();
+        scalarmult_fixed_base(&mut x, &mut y, &scalar.0);
+        Self::conditional_select(&Self([x, y, Montgomery::one()]), &Self::IDENTITY, is_zero)
+    }
+
+    fn mul(&self, scalar: &Scalar) -> Self {
+        let point = self.to_affine();
+        // the scalar multiplication needs a scalar in 1..=n-1 and a point that is not the
+        // identity, substitute valid inputs and select the identity afterwards
+        let scalar_is_zero = scalar.ct_eq(&Scalar::ZERO);
+        let is_identity = point.is_identity() | scalar_is_zero;
+        let point =
+            AffinePoint::conditional_select(&point, &AffinePoint::GENERATOR, point.is_identity());
+        let scalar = Scalar::conditional_select(scalar, &Scalar::ONE, scalar_is_zero);
+
+        let mut x = point.x.0;
+        let mut y = point.y.0;
+        scalarmult_variable_base(&mut x, &mut y, &scalar.0);
+        Self::conditional_select(
+            &Self([x, y, Montgomery::one()]),
+            &Self::IDENTITY,
+            is_identity,
+        )
+    }
+}
+
+impl_additive_ops!(ProjectivePoint, ProjectivePoint::IDENTITY);
+impl_binop!(
+    ProjectivePoint,
+    AffinePoint,
+    Add::add,
+    AddAssign::add_assign,
+    ProjectivePoint::add_affine
+);
+impl_binop!(
+    ProjectivePoint,
+    AffinePoint,
+    Sub::sub,
+    SubAssign::sub_assign,
+    ProjectivePoint::sub_affine
+);
+impl_binop!(
+    ProjectivePoint,
+    Scalar,
+    Mul::mul,
+    MulAssign::mul_assign,
+    ProjectivePoint::mul
+);
+
+impl From<AffinePoint> for ProjectivePoint {
+    fn from(point: AffinePoint) -> Self {
+        Self::conditional_select(
+            &Self([point.x.0, point.y.0, Montgomery::one()]),
+            &Self::IDENTITY,
+            point.is_identity(),
+        )
+    }
+}
+
+impl From<ProjectivePoint> for AffinePoint {
+    fn from(point: ProjectivePoint) -> Self {
+        point.to_affine()
+    }
+}
+
+impl ConditionallySelectable for ProjectivePoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let (a, b) = (a.coordinates(), b.coordinates());
+        Self(core::array::from_fn(|i| {
+            FieldElement::conditional_select(&a[i], &b[i], choice).0
+        }))
+    }
+}
+
+impl ConstantTimeEq for ProjectivePoint {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // (X1/Z1^2, Y1/Z1^3) == (X2/Z2^2, Y2/Z2^3)
+        let [x1, y1, z1] = self.coordinates();
+        let [x2, y2, z2] = other.coordinates();
+        let z1z1 = z1.square();
+        let z2z2 = z2.square();
+        let same_point =
+            (x1 * z2z2).ct_eq(&(x2 * z1z1)) & (y1 * z2z2 * z2).ct_eq(&(y2 * z1z1 * z1));
+
+        let (id1, id2) = (self.is_identity(), other.is_identity());
+        (id1 & id2) | (!id1 & !id2 & same_point)
+    }
+}
+
+impl PartialEq for ProjectivePoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for ProjectivePoint {}
+
+impl Default for ProjectivePoint {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
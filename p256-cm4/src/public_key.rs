@@ -0,0 +1,157 @@
+//! X.509 `SubjectPublicKeyInfo` encoding of public keys, RFC 5480:
+//!
+//! ```text
+//! SubjectPublicKeyInfo ::= SEQUENCE {
+//!     algorithm        AlgorithmIdentifier { id-ecPublicKey, prime256v1 },
+//!     subjectPublicKey BIT STRING
+//! }
+//! ```
+//!
+//! The bit string holds the point in one of the SEC1 encodings.
+
+#[cfg(feature = "pem")]
+use crate::pem;
+use crate::{
+    Error,
+    der::{TAG_SEQUENCE, decode_tlv},
+    point_to_octet_string_compressed, point_to_octet_string_uncompressed,
+    private_key::{ALGORITHM_EC_PRIME256V1, TAG_BIT_STRING},
+    try_octet_string_to_point,
+};
+
+/// Length of a DER encoded `SubjectPublicKeyInfo` with an uncompressed point.
+pub const SPKI_UNCOMPRESSED_DER_LEN: usize = 91;
+
+/// Length of a DER encoded `SubjectPublicKeyInfo` with a compressed point.
+pub const SPKI_COMPRESSED_DER_LEN: usize = 59;
+
+#[cfg(feature = "pem")]
+const PEM_LABEL: &str = "PUBLIC KEY";
+
+/// Length of a PEM encoded `SubjectPublicKeyInfo` with an uncompressed point.
+#[cfg(feature = "pem")]
+pub const SPKI_UNCOMPRESSED_PEM_LEN: usize =
+    pem::pem_len(PEM_LABEL.len(), SPKI_UNCOMPRESSED_DER_LEN);
+
+/// Length of a PEM encoded `SubjectPublicKeyInfo` with a compressed point.
+#[cfg(feature = "pem")]
+pub const SPKI_COMPRESSED_PEM_LEN: usize = pem::pem_len(PEM_LABEL.len(), SPKI_COMPRESSED_DER_LEN);
+
+// Writes the `SubjectPublicKeyInfo` around an encoded point, "out" must be 26 bytes longer than
+// the point.
+fn encode_spki(out: &mut [u8], point: &[u8]) {
+    // the bit string starts with the number of unused bits
+    let bit_string_len: usize = 1 + point.len();
+    out[0] = TAG_SEQUENCE;
+    out[1] = (ALGORITHM_EC_PRIME256V1.len() + 2 + bit_string_len) as u8;
+    out[2..23].copy_from_slice(&ALGORITHM_EC_PRIME256V1);
+    out[23..26].copy_from_slice(&[TAG_BIT_STRING, bit_string_len as u8, 0]);
+    out[26..].copy_from_slice(point);
+}
+
+fn decode_spki(x: &mut [u32; 8], y: &mut [u32; 8], der: &[u8]) -> Result<(), Error> {
+    let (sequence, []) = decode_tlv(der, TAG_SEQUENCE)? else {
+        return Err(Error::InvalidDer);
+    };
+    let Some(rest) = sequence.strip_prefix(&ALGORITHM_EC_PRIME256V1) else {
+        return Err(Error::InvalidDer);
+    };
+    let ([0x00, point @ ..], []) = decode_tlv(rest, TAG_BIT_STRING)? else {
+        return Err(Error::InvalidDer);
+    };
+    try_octet_string_to_point(x, y, point)
+}
+
+/// Encodes a public key as a DER `SubjectPublicKeyInfo` with an uncompressed point.
+///
+/// This is the format of `openssl ec -pubout -outform DER`.
+pub fn point_to_spki_der_uncompressed(
+    out: &mut [u8; SPKI_UNCOMPRESSED_DER_LEN],
+    x: &[u32; 8],
+    y: &[u32; 8],
+) {
+    let mut point: [u8; 65] = [0; 65];
+    point_to_octet_string_uncompressed(&mut point, x, y);
+    encode_spki(out, &point);
+}
+
+/// Encodes a public key as a DER `SubjectPublicKeyInfo` with a compressed point.
+pub fn point_to_spki_der_compressed(
+    out: &mut [u8; SPKI_COMPRESSED_DER_LEN],
+    x: &[u32; 8],
+    y: &[u32; 8],
+) {
+    let mut point: [u8; 33] = [0; 33];
+    point_to_octet_string_compressed(&mut point, x, y);
+    encode_spki(out, &point);
+}
+
+/// Decodes a DER `SubjectPublicKeyInfo` into the affine coordinates of the public key.
+///
+/// The algorithm must be `id-ecPublicKey` with the prime256v1 curve, and the point may use any
+/// of the encodings supported by [`octet_string_to_point`](crate::octet_string_to_point).
+/// Returns false if the encoding is invalid or the point is not on the curve.
+#[must_use]
+pub fn spki_der_to_point(x: &mut [u32; 8], y: &mut [u32; 8], der: &[u8]) -> bool {
+    try_spki_der_to_point(x, y, der).is_ok()
+}
+
+/// Same as [`spki_der_to_point`], but returns the reason for a failure.
+pub fn try_spki_der_to_point(x: &mut [u32; 8], y: &mut [u32; 8], der: &[u8]) -> Result<(), Error> {
+    let result: Result<(), Error> = decode_spki(x, y, der);
+    if result.is_err() {
+        x.fill(0);
+        y.fill(0);
+    }
+    result
+}
+
+/// Encodes a public key as a PEM `PUBLIC KEY` with an uncompressed point.
+///
+/// This is the format of `openssl ec -pubout`, with `\n` line endings.
+#[cfg(feature = "pem")]
+pub fn point_to_spki_pem_uncompressed(
+    out: &mut [u8; SPKI_UNCOMPRESSED_PEM_LEN],
+    x: &[u32; 8],
+    y: &[u32; 8],
+) {
+    let mut der: [u8; SPKI_UNCOMPRESSED_DER_LEN] = [0; SPKI_UNCOMPRESSED_DER_LEN];
+    point_to_spki_der_uncompressed(&mut der, x, y);
+    pem::encode(out, PEM_LABEL, &der);
+}
+
+/// Encodes a public key as a PEM `PUBLIC KEY` with a compressed point.
+#[cfg(feature = "pem")]
+pub fn point_to_spki_pem_compressed(
+    out: &mut [u8; SPKI_COMPRESSED_PEM_LEN],
+    x: &[u32; 8],
+    y: &[u32; 8],
+) {
+    let mut der: [u8; SPKI_COMPRESSED_DER_LEN] = [0; SPKI_COMPRESSED_DER_LEN];
+    point_to_spki_der_compressed(&mut der, x, y);
+    pem::encode(out, PEM_LABEL, &der);
+}
+
+/// Decodes a PEM `PUBLIC KEY` into the affine coordinates of the public key.
+///
+/// `\n` and `\r\n` line endings are accepted, the contents are decoded the same as
+/// [`spki_der_to_point`].
+#[must_use]
+#[cfg(feature = "pem")]
+pub fn spki_pem_to_point(x: &mut [u32; 8], y: &mut [u32; 8], pem: &[u8]) -> bool {
+    try_spki_pem_to_point(x, y, pem).is_ok()
+}
+
+/// Same as [`spki_pem_to_point`], but returns the reason for a failure.
+#[cfg(feature = "pem")]
+pub fn try_spki_pem_to_point(x: &mut [u32; 8], y: &mut [u32; 8], pem: &[u8]) -> Result<(), Error> {
+    let mut der: [u8; SPKI_UNCOMPRESSED_DER_LEN] = [0; SPKI_UNCOMPRESSED_DER_LEN];
+    match pem::decode(&mut der, PEM_LABEL, pem) {
+        Ok(der) => try_spki_der_to_point(x, y, der),
+        Err(e) => {
+            x.fill(0);
+            y.fill(0);
+            Err(e)
+        }
+    }
+}
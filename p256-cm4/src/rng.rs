@@ -0,0 +1,96 @@
+//! Key generation and signing with a random number generator.
+//!
+//! These functions draw random values until they are valid, instead of returning false and
+//! leaving the retry to the caller.
+
+use crate::{
+    Error, SignPrecomp, check_range_n, keygen, sign_step1, try_sign_step2, u32x8_to_u8x32_mut,
+};
+use rand_core::CryptoRngCore;
+use zeroize::Zeroize;
+
+// Draws random values until one is in the range 1 to n-1.
+// Rejected candidates are zeroized.
+pub(crate) fn random_scalar(scalar: &mut [u32; 8], rng: &mut impl CryptoRngCore) {
+    loop {
+        rng.fill_bytes(u32x8_to_u8x32_mut(scalar));
+        if check_range_n(scalar) {
+            return;
+        }
+        scalar.zeroize();
+    }
+}
+
+/// Generates a private key with a cryptographically secure random number generator, and
+/// calculates the public key belonging to it.
+///
+/// Random values that are not in the range `1..=n-1` are rejected, and another value is drawn.
+///
+/// Only use a keypair for either ECDSA or ECDH, not both, and don't use the private key for any other purposes.
+pub fn generate_keypair(
+    private_key: &mut [u32; 8],
+    public_key_x: &mut [u32; 8],
+    public_key_y: &mut [u32; 8],
+    rng: &mut impl CryptoRngCore,
+) {
+    random_scalar(private_key, rng);
+    // cannot fail, the private key is in range
+    let _ = keygen(public_key_x, public_key_y, private_key);
+}
+
+/// Creates an ECDSA signature, with a random "k" drawn from a cryptographically secure random
+/// number generator.
+///
+/// Unlike [`sign`](crate::sign) this does not fail for an unsuitable "k", a new "k" is drawn
+/// instead.  Returns false if the private key is not in the range `1..=n-1`.
+#[must_use]
+pub fn sign_with_rng(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    rng: &mut impl CryptoRngCore,
+) -> bool {
+    try_sign_with_rng(r, s, hash, private_key, rng).is_ok()
+}
+
+/// Same as [`sign_with_rng`], but returns the reason for a failure.
+pub fn try_sign_with_rng(
+    r: &mut [u32; 8],
+    s: &mut [u32; 8],
+    hash: &[u8],
+    private_key: &[u32; 8],
+    rng: &mut impl CryptoRngCore,
+) -> Result<(), Error> {
+    if !check_range_n(private_key) {
+        r.fill(0);
+        s.fill(0);
+        return Err(Error::ScalarOutOfRange);
+    }
+
+    let mut sign_precomp: SignPrecomp = Default::default();
+    loop {
+        sign_step1_with_rng(&mut sign_precomp, rng);
+        // The private key is in range, so this only fails if s is zero.
+        if try_sign_step2(r, s, hash, private_key, &mut sign_precomp).is_ok() {
+            return Ok(());
+        }
+    }
+}
+
+/// First step of creating an ECDSA signature, with a random "k" drawn from a cryptographically
+/// secure random number generator.
+///
+/// See [`sign_step1`].  Unlike [`sign_step1`] this does not fail, a new "k" is drawn until the
+/// first step succeeds.  "k" is zeroized before returning.
+pub fn sign_step1_with_rng(result: &mut SignPrecomp, rng: &mut impl CryptoRngCore) {
+    let mut k: [u32; 8] = [0; 8];
+    loop {
+        random_scalar(&mut k, rng);
+        let is_ok: bool = sign_step1(result, &k);
+        k.zeroize();
+        if is_ok {
+            return;
+        }
+    }
+}
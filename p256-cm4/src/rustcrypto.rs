@@ -0,0 +1,167 @@
+//! Implementations of the RustCrypto [`signature`] traits.
+//!
+//! Signatures are created with a deterministic `k` according to RFC 6979 using SHA-256,
+//! the same as `p256::ecdsa::SigningKey`.  The traits are implemented for
+//! [`ecdsa::Signature<NistP256>`], with the curve type of this crate, and with the `p256` feature
+//! also for `p256::ecdsa::Signature`, so code written against the signature types of the `p256`
+//! crate can use [`SigningKey`] and [`VerifyingKey`] unchanged.  The `p256` feature also converts
+//! the keys to and from `p256::ecdsa::SigningKey` and `p256::ecdsa::VerifyingKey`.
+
+use crate::{Error, NistP256, PublicKey, SecretKey, Signature, types::to_be_bytes};
+use ecdsa::signature::{
+    self, Signer, Verifier,
+    hazmat::{PrehashSigner, PrehashVerifier},
+};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "p256")]
+use zeroize::Zeroize;
+
+impl From<Error> for signature::Error {
+    fn from(_: Error) -> Self {
+        signature::Error::new()
+    }
+}
+
+/// ECDSA signing key, implementing the RustCrypto signer traits.
+#[derive(Clone, Debug)]
+pub struct SigningKey(SecretKey);
+
+impl SigningKey {
+    /// The verifying key belonging to this signing key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(self.0.public_key())
+    }
+
+    /// The secret key.
+    pub fn as_secret_key(&self) -> &SecretKey {
+        &self.0
+    }
+}
+
+impl From<SecretKey> for SigningKey {
+    fn from(secret_key: SecretKey) -> Self {
+        Self(secret_key)
+    }
+}
+
+/// ECDSA verifying key, implementing the RustCrypto verifier traits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey(PublicKey);
+
+impl VerifyingKey {
+    /// The public key.
+    pub fn as_public_key(&self) -> &PublicKey {
+        &self.0
+    }
+}
+
+impl From<PublicKey> for VerifyingKey {
+    fn from(public_key: PublicKey) -> Self {
+        Self(public_key)
+    }
+}
+
+// Implements the conversions of `Signature` and the signature traits of `SigningKey` and
+// `VerifyingKey` for `ecdsa::Signature<$curve>`.
+macro_rules! impl_signature_traits {
+    ($curve:ty) => {
+        impl From<Signature> for ecdsa::Signature<$curve> {
+            fn from(signature: Signature) -> Self {
+                // cannot fail, `r` and `s` are in range
+                ecdsa::Signature::from_scalars(
+                    to_be_bytes(signature.r()),
+                    to_be_bytes(signature.s()),
+                )
+                .unwrap()
+            }
+        }
+
+        impl TryFrom<&ecdsa::Signature<$curve>> for Signature {
+            type Error = Error;
+
+            fn try_from(signature: &ecdsa::Signature<$curve>) -> Result<Self, Error> {
+                let (r, s) = signature.split_bytes();
+                Signature::from_be_bytes(&r.into(), &s.into())
+            }
+        }
+
+        impl PrehashSigner<ecdsa::Signature<$curve>> for SigningKey {
+            fn sign_prehash(&self, prehash: &[u8]) -> signature::Result<ecdsa::Signature<$curve>> {
+                Ok(self.0.sign_deterministic(prehash).into())
+            }
+        }
+
+        impl Signer<ecdsa::Signature<$curve>> for SigningKey {
+            fn try_sign(&self, msg: &[u8]) -> signature::Result<ecdsa::Signature<$curve>> {
+                self.sign_prehash(&Sha256::digest(msg))
+            }
+        }
+
+        impl PrehashVerifier<ecdsa::Signature<$curve>> for VerifyingKey {
+            fn verify_prehash(
+                &self,
+                prehash: &[u8],
+                signature: &ecdsa::Signature<$curve>,
+            ) -> signature::Result<()> {
+                let signature: Signature = signature.try_into()?;
+                if self.0.verify(prehash, &signature) {
+                    Ok(())
+                } else {
+                    Err(signature::Error::new())
+                }
+            }
+        }
+
+        impl Verifier<ecdsa::Signature<$curve>> for VerifyingKey {
+            fn verify(
+                &self,
+                msg: &[u8],
+                signature: &ecdsa::Signature<$curve>,
+            ) -> signature::Result<()> {
+                self.verify_prehash(&Sha256::digest(msg), signature)
+            }
+        }
+    };
+}
+
+impl_signature_traits!(NistP256);
+#[cfg(feature = "p256")]
+impl_signature_traits!(p256::NistP256);
+
+#[cfg(feature = "p256")]
+impl From<&SigningKey> for p256::ecdsa::SigningKey {
+    fn from(signing_key: &SigningKey) -> Self {
+        let mut bytes: [u8; 32] = signing_key.0.to_be_bytes();
+        // cannot fail, the secret key is in range
+        let signing_key = p256::ecdsa::SigningKey::from_bytes(&bytes.into()).unwrap();
+        bytes.zeroize();
+        signing_key
+    }
+}
+
+#[cfg(feature = "p256")]
+impl From<&p256::ecdsa::SigningKey> for SigningKey {
+    fn from(signing_key: &p256::ecdsa::SigningKey) -> Self {
+        let mut bytes: [u8; 32] = signing_key.to_bytes().into();
+        // cannot fail, the secret key is in range
+        let secret_key = SecretKey::from_be_bytes(&bytes).unwrap();
+        bytes.zeroize();
+        Self(secret_key)
+    }
+}
+
+#[cfg(feature = "p256")]
+impl From<VerifyingKey> for p256::ecdsa::VerifyingKey {
+    fn from(verifying_key: VerifyingKey) -> Self {
+        // cannot fail, the public key is on the curve
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(&verifying_key.0.to_uncompressed()).unwrap()
+    }
+}
+
+#[cfg(feature = "p256")]
+impl From<p256::ecdsa::VerifyingKey> for VerifyingKey {
+    fn from(verifying_key: p256::ecdsa::VerifyingKey) -> Self {
+        // cannot fail, the public key is on the curve and not the point at infinity
+        Self(PublicKey::from_sec1_bytes(verifying_key.to_encoded_point(false).as_bytes()).unwrap())
+    }
+}
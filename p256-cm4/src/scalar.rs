@@ -0,0 +1,168 @@
+//! Scalar arithmetic modulo the curve order
+//! `n = 2^256 - 2^224 + 2^192 - 0x4319055258e8617b0c46353d039cdaaf`.
+
+use crate::{
+    check_range_n, mod_n_inv,
+    ops::impl_field_ops,
+    sys::{
+        add_mod_n_in_place, mul_mod_n, negate_mod_n_if, reduce_mod_n_32bytes_in_place,
+        reduce_mod_n_64bytes,
+    },
+    types::{from_be_bytes, to_be_bytes},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// An integer modulo the curve order, as little-endian limbs in normal form.
+///
+/// Scalars are always fully reduced.  [`Scalar::from_wide_bytes`] derives a scalar from a hash
+/// output without a noticeable bias, and [`Scalar::invert`] uses the same constant time inversion
+/// as signing.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Scalar(pub(crate) [u32; 8]);
+
+impl Scalar {
+    /// Zero.
+    pub const ZERO: Self = Self([0; 8]);
+
+    /// One.
+    pub const ONE: Self = Self([1, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// Decode a scalar from a big-endian integer.
+    ///
+    /// Returns `None` if the value is not in the range `0..=n-1`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+        Self::from_limbs(&from_be_bytes(bytes))
+    }
+
+    /// The scalar as a big-endian integer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_be_bytes(&self.0)
+    }
+
+    /// Decode a scalar from a big-endian integer, reduced modulo `n`.
+    ///
+    /// The result is biased if the input is uniformly random, use [`Scalar::from_wide_bytes`]
+    /// to derive scalars from hash outputs.
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        let mut limbs: [u32; 8] = from_be_bytes(bytes);
+        reduce_mod_n_32bytes_in_place(&mut limbs);
+        Self(limbs)
+    }
+
+    /// Decode a scalar from a 64 byte big-endian integer, reduced modulo `n`.
+    ///
+    /// For a uniformly random input the bias of the result is negligible, as needed for
+    /// hash-to-scalar constructions.
+    pub fn from_wide_bytes(bytes: &[u8; 64]) -> Self {
+        let (high, low) = bytes.split_at(32);
+        let low: [u32; 8] = from_be_bytes(low.try_into().unwrap());
+        let high: [u32; 8] = from_be_bytes(high.try_into().unwrap());
+        let wide: [u32; 16] = core::array::from_fn(|i| if i < 8 { low[i] } else { high[i - 8] });
+        let mut res = [0; 8];
+        reduce_mod_n_64bytes(&mut res, &wide);
+        Self(res)
+    }
+
+    /// Decode a scalar from little-endian limbs, as used by the functions in the crate root.
+    ///
+    /// Returns `None` if the value is not in the range `0..=n-1`.
+    pub fn from_limbs(limbs: &[u32; 8]) -> CtOption<Self> {
+        // `check_range_n` excludes zero, which is a valid scalar
+        let is_zero: Choice = limbs.iter().fold(0, |acc, limb| acc | limb).ct_eq(&0);
+        let in_range: Choice = Choice::from(check_range_n(limbs) as u8) | is_zero;
+        CtOption::new(Self(*limbs), in_range)
+    }
+
+    /// The scalar as little-endian limbs.
+    pub fn to_limbs(&self) -> [u32; 8] {
+        self.0
+    }
+
+    /// Calculate `self + rhs mod n`.
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut res = self.0;
+        add_mod_n_in_place(&mut res, &rhs.0);
+        Self(res)
+    }
+
+    /// Calculate `self - rhs mod n`.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        self.add(&rhs.neg())
+    }
+
+    /// Calculate `-self mod n`.
+    pub fn neg(&self) -> Self {
+        let mut res = [0; 8];
+        negate_mod_n_if(&mut res, &self.0, true);
+        // `n - 0` is not reduced
+        Self::conditional_select(&Self(res), &Self::ZERO, self.ct_eq(&Self::ZERO))
+    }
+
+    /// Calculate `self * rhs mod n`.
+    pub fn multiply(&self, rhs: &Self) -> Self {
+        let mut res = [0; 8];
+        mul_mod_n(&mut res, &self.0, &rhs.0);
+        Self(res)
+    }
+
+    /// Calculate `self^2 mod n`.
+    pub fn square(&self) -> Self {
+        self.multiply(self)
+    }
+
+    /// Calculate `2 * self mod n`.
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// Calculate `self^-1 mod n`.
+    ///
+    /// Returns `None` if `self` is zero.
+    pub fn invert(&self) -> CtOption<Self> {
+        let mut res = [0; 8];
+        mod_n_inv(&mut res, &self.0);
+        CtOption::new(Self(res), !self.ct_eq(&Self::ZERO))
+    }
+}
+
+impl_field_ops!(Scalar);
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        Self([value as u32, (value >> 32) as u32, 0, 0, 0, 0, 0, 0])
+    }
+}
+
+impl ConditionallySelectable for Scalar {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(core::array::from_fn(|i| {
+            u32::conditional_select(&a.0[i], &b.0[i], choice)
+        }))
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (0..8).fold(Choice::from(1), |acc, i| acc & self.0[i].ct_eq(&other.0[i]))
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Scalar {}
+
+impl PartialOrd for Scalar {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scalar {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
@@ -0,0 +1,38 @@
+use super::{
+    P256_PRIME,
+    reduce::P256_reduce_mod_n_once,
+    util::{add, add_mod, sub_mod},
+};
+
+/// Compute `A - B mod p`, assuming that `A, B < p`, and `p` is the `p256` prime.
+pub(super) fn P256_submod(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    sub_mod(a, b, &P256_PRIME)
+}
+
+/// Computes `A + B mod p`, assuming that `A, B < p`, and `p` is the `p256` prime.
+pub(super) fn P256_addmod(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    add_mod(a, b, &P256_PRIME)
+}
+
+/// Given two numbers `a` and `b`, compute `a + b mod n` where `n` is the P256 order.
+///
+/// The pointers `res`, `a` and `b` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_add_mod_n(
+    res: *mut [u32; 8],
+    a: *const [u32; 8],
+    b: *const [u32; 8],
+) {
+    // SAFETY: the caller guarantees that `a` and `b` are valid for reads.
+    let (a, b): ([u32; 8], [u32; 8]) = unsafe { (*a, *b) };
+
+    let (sum, carry) = add(&a, &b);
+    let sum: [u32; 9] = core::array::from_fn(|i| if i < 8 { sum[i] } else { carry });
+    let sum: [u32; 9] = P256_reduce_mod_n_once(&P256_reduce_mod_n_once(&sum));
+
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = core::array::from_fn(|i| sum[i]) };
+}
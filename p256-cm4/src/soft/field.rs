@@ -0,0 +1,95 @@
+//! Pointer based wrappers around the field arithmetic routines, mirroring `asm::field`.
+
+use super::{
+    Montgomery, P256_modinv_sqrt,
+    add_sub::{P256_addmod, P256_submod},
+    mulmod::{P256_mulmod, P256_sqrmod},
+};
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a * b mod p`.
+///
+/// `res` and `a` and/or `b` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_mul_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    // SAFETY: the caller guarantees that `a` and `b` are valid for reads.
+    let (a, b): (Montgomery, Montgomery) = unsafe { (*a, *b) };
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = Montgomery(P256_mulmod(&a.0, &b.0)) };
+}
+
+/// Given input `a` in montgomery form, calculate `a^2 mod p`.
+///
+/// `res` and `a` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_sqr_mod_p(res: *mut Montgomery, a: *const Montgomery) {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: Montgomery = unsafe { *a };
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = Montgomery(P256_sqrmod(&a.0)) };
+}
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a + b mod p`.
+///
+/// `res` and `a` and/or `b` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_add_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    // SAFETY: the caller guarantees that `a` and `b` are valid for reads.
+    let (a, b): (Montgomery, Montgomery) = unsafe { (*a, *b) };
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = Montgomery(P256_addmod(&a.0, &b.0)) };
+}
+
+/// Given inputs `a` and `b` in montgomery form, calculate `a - b mod p`.
+///
+/// `res` and `a` and/or `b` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_sub_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    b: *const Montgomery,
+) {
+    // SAFETY: the caller guarantees that `a` and `b` are valid for reads.
+    let (a, b): (Montgomery, Montgomery) = unsafe { (*a, *b) };
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = Montgomery(P256_submod(&a.0, &b.0)) };
+}
+
+/// For input `a` in montgomery form and mode `mode`:
+/// 1. If `mode == modinv`, calculate `a^-1 mod p`
+/// 2. If `mode == sqrt`, calculate a candidate for `sqrt(a) mod p`
+///
+/// `mode` shall be `0` for modinv and `1` for sqrt. `res` and `a` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_modinv_sqrt_mod_p(
+    res: *mut Montgomery,
+    a: *const Montgomery,
+    mode: u32,
+) {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: Montgomery = unsafe { *a };
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = Montgomery(P256_modinv_sqrt(&a.0, mode)) };
+}
@@ -0,0 +1,240 @@
+use super::{
+    Montgomery, P256_PRIME,
+    add_sub::{P256_addmod, P256_submod},
+    mulmod::{P256_mulmod, P256_sqrmod},
+    sqrt::P256_modinv_sqrt,
+    util::{add, add_sub_helper, is_zero, select},
+};
+
+/// A point in Jacobian form, with integers in montgomery form.
+type Jacobian = [[u32; 8]; 3];
+
+/// Doubles a point in Jacobian form (with integers in Montgomery form).
+///
+/// `jacobian_out` and `jacobian_in` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `jacobian_out` and `jacobian_in` are valid for
+/// the duration of the function, and that `jacobian_out` is valid for writes.
+pub(in crate::sys) unsafe fn P256_double_j(
+    jacobian_out: *mut [Montgomery; 3],
+    jacobian_in: *const [Montgomery; 3],
+) {
+    // SAFETY: the caller guarantees that `jacobian_in` is valid for reads.
+    let a: Jacobian = unsafe { (*jacobian_in).map(|m| m.0) };
+    let result: Jacobian = double(&a);
+    // SAFETY: the caller guarantees that `jacobian_out` is valid for writes.
+    unsafe { *jacobian_out = result.map(Montgomery) };
+}
+
+fn double(a: &Jacobian) -> Jacobian {
+    let [x1, y1, z1] = a;
+
+    // https://eprint.iacr.org/2014/130.pdf, algorithm 10
+
+    // t1 = Z1^2
+    let t1 = P256_sqrmod(z1);
+    // Z2 = Y1 * Z1
+    let z2 = P256_mulmod(y1, z1);
+    // t2 = X1 + t1
+    let t2 = P256_addmod(x1, &t1);
+    // t1 = X1 - t1
+    let t1 = P256_submod(x1, &t1);
+    // t1 = t1 * t2
+    let t1 = P256_mulmod(&t1, &t2);
+    // t2 = t1 / 2
+    let t2 = P256_times_half(&t1);
+    // t1 = t1 + t2
+    let t1 = P256_addmod(&t1, &t2);
+    // t2 = t1^2
+    let t2 = P256_sqrmod(&t1);
+    // Y2 = Y1^2
+    let y2 = P256_sqrmod(y1);
+    // t3 = Y2^2
+    let t3 = P256_sqrmod(&y2);
+    // Y2 = X1 * Y2
+    let y2 = P256_mulmod(x1, &y2);
+    // X2 = 2 * Y2
+    let x2 = P256_times2(&y2);
+    // X2 = t2 - X2
+    let x2 = P256_submod(&t2, &x2);
+    // t2 = Y2 - X2
+    let t2 = P256_submod(&y2, &x2);
+    // t1 = t1 * t2
+    let t1 = P256_mulmod(&t1, &t2);
+    // Y2 = t1 - t3
+    let y2 = P256_submod(&t1, &t3);
+
+    [x2, y2, z2]
+}
+
+/// For input `a`, calculate `a * 2 mod p`, where `p` is the P256 prime.
+fn P256_times2(a: &[u32; 8]) -> [u32; 8] {
+    P256_addmod(a, a)
+}
+
+/// For input `a`, calculate `a / 2 mod p`, where `p` is the P256 prime.
+fn P256_times_half(a: &[u32; 8]) -> [u32; 8] {
+    // If `a` is odd, add `p` to make it even before shifting the 257-bit sum.
+    let (sum, carry) = add(a, &P256_PRIME);
+    let (even, top) = (select(a, &sum, a[0] & 1), carry & a[0]);
+    core::array::from_fn(|i| {
+        let next: u32 = if i == 7 { top } else { even[i + 1] };
+        (even[i] >> 1) | (next << 31)
+    })
+}
+
+/// Given inputs `a` (a point in Jacobian form with integers in montgomery form) and `b` (a point in Jacobian form with integers in montgomery form, or an affine point):
+/// 1. if `is_sub == false`, calculate `a + b`
+/// 2. If `is_sub == true`, calculate `a - b`
+///
+/// `a` is treated as the point at infinity if its Z-coordinate is 0. `b` must not be the point at infinity.
+///
+/// If `b_is_affine`, `b` points to a `[Montgomery; 2]`, otherwise to a `[Montgomery; 3]`.
+///
+/// The pointers `a` and `b` _**must not**_ overlap.
+///
+/// # Safety
+/// The caller must guarantee that `a` and `b` are valid for the duration of the function call,
+/// that `a` is valid for writes, and that `b` has the correct length w.r.t. the value of
+/// `b_is_affine`.
+pub(in crate::sys) unsafe fn P256_add_sub_j(
+    a: *mut [Montgomery; 3],
+    b: *const Montgomery,
+    is_sub: bool,
+    b_is_affine: bool,
+) {
+    // SAFETY: the caller guarantees that `a` is valid for reads, and that `b` is valid
+    // for reads of 2 or 3 elements, depending on `b_is_affine`.
+    let (a_in, b_in): (Jacobian, Jacobian) = unsafe {
+        (
+            (*a).map(|m| m.0),
+            [
+                (*b).0,
+                (*b.add(1)).0,
+                if b_is_affine {
+                    Montgomery::one().0
+                } else {
+                    (*b.add(2)).0
+                },
+            ],
+        )
+    };
+
+    let result: Jacobian = add_sub(&a_in, &b_in, is_sub, b_is_affine);
+
+    // SAFETY: the caller guarantees that `a` is valid for writes.
+    unsafe { *a = result.map(Montgomery) };
+}
+
+fn add_sub(a: &Jacobian, b: &Jacobian, is_sub: bool, b_is_affine: bool) -> Jacobian {
+    if is_zero(&a[2]) == 1 {
+        // First point is 0, so just set result to (-) the other point
+        return add_sub_helper(b, is_sub);
+    }
+
+    // Here a variant of
+    // https://www.hyperelliptic.org/EFD/g1p/auto-code/shortw/jacobian-3/addition/add-1998-cmo-2.op3
+    // is used.
+    // The first operand to the function is both (X3,Y3,Z3) and (X2,Y2,Z2).
+    // The second operand to the function is (X1,Y1,Z1)
+    let [x2, y2, z2] = a;
+    let [x1, y1, z1] = b;
+
+    let (u2, s2) = if b_is_affine {
+        (*x2, *y2)
+    } else {
+        // Z1Z1 = Z1^2
+        let z1z1 = P256_sqrmod(z1);
+        // U2 = X2*Z1Z1
+        let u2 = P256_mulmod(x2, &z1z1);
+        // t1 = Z1*Z1Z1
+        let t1 = P256_mulmod(z1, &z1z1);
+        // S2 = Y2*t1
+        (u2, P256_mulmod(y2, &t1))
+    };
+
+    // Z2Z2 = Z2^2
+    let z2z2 = P256_sqrmod(z2);
+    // U1 = X1*Z2Z2
+    let u1 = P256_mulmod(x1, &z2z2);
+    // t2 = Z2*Z2Z2
+    let t2 = P256_mulmod(z2, &z2z2);
+    // S1 = Y1*t2
+    let s1 = P256_mulmod(y1, &t2);
+    // H = U2-U1
+    let h = P256_submod(&u2, &u1);
+    // HH = H^2
+    let hh = P256_sqrmod(&h);
+    // Z3 = Z2*H
+    let mut z3 = P256_mulmod(z2, &h);
+    if !b_is_affine {
+        // Z3 = Z1*Z3
+        z3 = P256_mulmod(z1, &z3);
+    }
+    // HHH = H*HH
+    let hhh = P256_mulmod(&h, &hh);
+
+    // r = S2-+S1
+    let r = if is_sub {
+        P256_addmod(&s2, &s1)
+    } else {
+        P256_submod(&s2, &s1)
+    };
+
+    if is_zero(&hhh) & is_zero(&r) == 1 {
+        // Points should be doubled since addition formula can't handle this case
+        return double(&add_sub_helper(b, is_sub));
+    }
+
+    // V = U1*HH
+    let v = P256_mulmod(&u1, &hh);
+    // t3 = r^2
+    let t3 = P256_sqrmod(&r);
+    // t2 = S1*HHH
+    let t2 = P256_mulmod(&s1, &hhh);
+    // X3 = t3-HHH
+    let x3 = P256_submod(&t3, &hhh);
+    // t3 = 2*V
+    let t3 = P256_times2(&v);
+    // X3 = X3-t3
+    let x3 = P256_submod(&x3, &t3);
+    // t3 = V-X3
+    let t3 = P256_submod(&v, &x3);
+    // t3 = r*t3
+    let t3 = P256_mulmod(&r, &t3);
+    // Y3 = t3-+t2
+    let y3 = if is_sub {
+        P256_addmod(&t3, &t2)
+    } else {
+        P256_submod(&t3, &t2)
+    };
+
+    [x3, y3, z3]
+}
+
+/// Given jacobian (with integers in montgomery form) `a`, calculate the affine `x` and `y` points.
+///
+/// # Safety
+/// The caller must guarantee that `x`, `y` and `a` are valid for the duration of the function
+/// call, and that `x` and `y` are valid for writes.
+pub(in crate::sys) unsafe fn P256_jacobian_to_affine(
+    x: *mut Montgomery,
+    y: *mut Montgomery,
+    a: *const [Montgomery; 3],
+) {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let [ax, ay, az]: Jacobian = unsafe { (*a).map(|m| m.0) };
+
+    let z_inv = P256_modinv_sqrt(&az, 0);
+    let z_inv_2 = P256_sqrmod(&z_inv);
+    let z_inv_3 = P256_mulmod(&z_inv, &z_inv_2);
+    let affine_x = P256_mulmod(&z_inv_2, &ax);
+    let affine_y = P256_mulmod(&z_inv_3, &ay);
+
+    // SAFETY: the caller guarantees that `x` and `y` are valid for writes.
+    unsafe {
+        *x = Montgomery(affine_x);
+        *y = Montgomery(affine_y);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{FGInteger, XYInteger};
+
+use super::util::{add, is_zero, order, select, sub};
+
+/// Montgomery multiplication factor `N'` (when `R = 2^32`), `N*N' = -1 mod R`.
+const P256_ORDER_INV: u32 = 0xee00bc4f;
+
+/// Split a matrix element in the range `[-2^30, 2^31]` into its absolute value and
+/// a sign mask (`0` or `u32::MAX`).
+///
+/// Since the range is `[-2^30, 2^31]`, a value is negative exactly when its top 2 bits are both 1s.
+#[inline(always)]
+fn abs_and_sign(a: u32) -> (u32, u32) {
+    let sign: u32 = (((a & (a << 1)) as i32) >> 31) as u32;
+    ((a ^ sign).wrapping_sub(sign), sign)
+}
+
+/// Conditionally negate the two's complement integer `a` if `mask` is `u32::MAX`.
+#[inline(always)]
+fn negate_if<const N: usize>(a: &[u32; N], mask: u32) -> [u32; N] {
+    let flipped: [u32; N] = core::array::from_fn(|i| a[i] ^ mask);
+    let mut one = [0u32; N];
+    one[0] = mask & 1;
+    add(&flipped, &one).0
+}
+
+/// Multiply the signed (two's complement) 288-bit integer `a` by `b`, giving a
+/// signed 320-bit result.
+#[inline(always)]
+fn mul_signed_288(a: &[u32; 9], b: u32) -> [u32; 10] {
+    let mut out = [0u32; 10];
+    let mut carry: u64 = 0;
+    (0..9).for_each(|i| {
+        let t: u64 = u64::from(a[i]) * u64::from(b) + carry;
+        out[i] = t as u32;
+        carry = t >> 32;
+    });
+    // a negative `a` was interpreted as `a + 2^288`, so subtract `b * 2^288`
+    out[9] = (carry as u32).wrapping_sub(b & (((a[8] as i32) >> 31) as u32));
+    out
+}
+
+/// For `a`, `b` (matrix elements in the range `[-2^30, 2^31]`), and [`XYInteger`]s `x` and `y`,
+/// compute `(a * x + b * y) / 2^32 mod N` (where `N` is the order of the `p256` curve).
+///
+/// # Safety
+/// The caller must guarantee that `out` and `xy` are valid for the duration of the function call,
+/// and that `out` is valid for writes.
+pub(in crate::sys) unsafe fn P256_matrix_mul_mod_n(
+    a: u32,
+    b: u32,
+    xy: *const [XYInteger; 2],
+    out: *mut XYInteger,
+) {
+    // SAFETY: the caller guarantees that `xy` is valid for reads.
+    let [x, y] = unsafe { &*xy };
+
+    let (a, a_sign) = abs_and_sign(a);
+    let (b, b_sign) = abs_and_sign(b);
+
+    // The result is negative if exactly one of x'sign and y's sign is negative,
+    // after combining them with the signs of a and b respectively.
+    let x_sign: u32 = a_sign ^ x.flip_sign as u32;
+    let y_sign: u32 = b_sign ^ y.flip_sign as u32;
+    let negate_x: u32 = x_sign ^ y_sign;
+
+    // x is stored as an unsigned 256-bit integer, now conditionally negate it mod N
+    let (negated, _) = sub(&order(), &x.value);
+    let negated: [u32; 8] = select(&negated, &[0; 8], is_zero(&x.value));
+    let x_value: [u32; 8] = select(&x.value, &negated, negate_x & 1);
+
+    // calculate a * x + b * y, the result will fit in 288 bits
+    let mut t = [0u32; 10];
+    let mut carry_x: u64 = 0;
+    let mut carry_y: u64 = 0;
+    (0..8).for_each(|i| {
+        let ax: u64 = u64::from(a) * u64::from(x_value[i]) + carry_x;
+        let by: u64 = u64::from(b) * u64::from(y.value[i]) + carry_y;
+        let sum: u64 = (ax & 0xffffffff) + (by & 0xffffffff) + u64::from(t[i]);
+        t[i] = sum as u32;
+        t[i + 1] = (sum >> 32) as u32;
+        carry_x = ax >> 32;
+        carry_y = by >> 32;
+    });
+    t[8] = t[8].wrapping_add((carry_x + carry_y) as u32);
+
+    // reduce modulo N using montgomery redc algorithm, t = (T + mN) / R
+    let order: [u32; 8] = order();
+    let m: u32 = t[0].wrapping_mul(P256_ORDER_INV);
+    let mut carry: u64 = 0;
+    (0..9).for_each(|i| {
+        let n: u32 = if i < 8 { order[i] } else { 0 };
+        let sum: u64 = u64::from(t[i]) + u64::from(m) * u64::from(n) + carry;
+        t[i] = sum as u32;
+        carry = sum >> 32;
+    });
+    t[9] = carry as u32;
+
+    // the result is less than 2N, so conditionally subtract N
+    let result: [u32; 9] = core::array::from_fn(|i| t[i + 1]);
+    let (reduced, borrow) = sub(
+        &result,
+        &core::array::from_fn(|i| if i < 8 { order[i] } else { 0 }),
+    );
+    let result: [u32; 9] = select(&reduced, &result, borrow);
+
+    // SAFETY: the caller guarantees that `out` is valid for writes.
+    unsafe {
+        (*out).flip_sign = y_sign as i32;
+        (*out).value = core::array::from_fn(|i| result[i]);
+    }
+}
+
+/// Given values `delta`, `f` and `g`, perform 31 "divstep2" iterations of the safegcd algorithm
+/// (section 8 of <https://gcd.cr.yp.to/safegcd-20190413.pdf>) on the low 32 bits of `f` and `g`.
+///
+/// On return, `res` contains the resulting transition matrix `[u, v, q, r]`, scaled by `2^31`,
+/// and the new value of `delta` is returned.
+///
+/// # Safety
+/// The caller must guarantee that `res`, is valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_divsteps2_31(
+    mut delta: i32,
+    mut f: u32,
+    mut g: u32,
+    res: *mut [u32; 4],
+) -> i32 {
+    let (mut u, mut v, mut q, mut r): (u32, u32, u32, u32) = (1, 0, 0, 1);
+
+    (0..31).for_each(|_| {
+        // m = -1 if delta > 0 and g is odd, 0 otherwise
+        let m: u32 = ((((g << 31) & !(delta.wrapping_sub(1) as u32)) as i32) >> 31) as u32;
+
+        // conditionally negate delta
+        delta = (delta ^ m as i32).wrapping_sub(m as i32);
+
+        // conditionally set (f, g) = (g, -f) and likewise for (u, q) and (v, r)
+        let (f_old, u_old, v_old) = (f, u, v);
+        f ^= (f ^ g) & m;
+        g ^= (g ^ f_old.wrapping_neg()) & m;
+        u ^= (u ^ q) & m;
+        q ^= (q ^ u_old.wrapping_neg()) & m;
+        v ^= (v ^ r) & m;
+        r ^= (r ^ v_old.wrapping_neg()) & m;
+
+        let g0: u32 = (g & 1).wrapping_neg();
+        delta = delta.wrapping_add(1);
+
+        // g = (g + g0 * f) / 2, we don't need the MSB
+        g = g.wrapping_add(f & g0) >> 1;
+
+        q = q.wrapping_add(u & g0);
+        r = r.wrapping_add(v & g0);
+
+        u = u.wrapping_add(u);
+        v = v.wrapping_add(v);
+    });
+
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = [u, v, q, r] };
+
+    delta
+}
+
+/// Given inputs `a`, `b` (matrix elements in the range `[-2^30, 2^31]`), `f` and `g`
+/// (two 257 bit signed numbers), calculate `(a * f + b * g) / 2^31`, which shall be an integer.
+///
+/// # Safety
+/// The caller must guarantee that `fg` and `res` are valid for the duration of the function call,
+/// and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_matrix_mul_fg_9(
+    a: u32,
+    b: u32,
+    fg: *const [FGInteger; 2],
+    res: *mut FGInteger,
+) {
+    // SAFETY: the caller guarantees that `fg` is valid for reads.
+    let [f, g] = unsafe { &*fg };
+
+    let (a, a_sign) = abs_and_sign(a);
+    let (b, b_sign) = abs_and_sign(b);
+
+    let f_sign: u32 = a_sign ^ f.flip_sign as u32;
+    let g_sign: u32 = b_sign ^ g.flip_sign as u32;
+
+    // the result takes the sign of b * g, so negate f if the signs differ
+    let f_value: [u32; 9] = negate_if(&f.signed_value, f_sign ^ g_sign);
+
+    let (sum, _) = add(
+        &mul_signed_288(&f_value, a),
+        &mul_signed_288(&g.signed_value, b),
+    );
+
+    // divide by 2^31 (the low 31 bits are all zero), the result fits in a signed 288-bit value
+    let result: [u32; 9] = core::array::from_fn(|i| (sum[i] >> 31) | (sum[i + 1] << 1));
+
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe {
+        (*res).flip_sign = g_sign as i32;
+        (*res).signed_value = result;
+    }
+}
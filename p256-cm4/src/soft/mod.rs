@@ -0,0 +1,114 @@
+//! A portable implementation of the routines in `asm`, for targets other than ARM.
+//!
+//! Each function mirrors the assembly routine of the same name. Routines that are
+//! callable from rust keep the signature of their assembly counterpart, the register
+//! based helpers are ordinary rust functions.
+#![allow(non_snake_case)]
+
+pub mod add_sub;
+
+pub(crate) mod field;
+
+pub(crate) mod jacobian;
+
+pub(crate) mod montgomery;
+pub use montgomery::Montgomery;
+use montgomery::{P256_from_montgomery, P256_to_montgomery};
+
+mod sqrt;
+use sqrt::P256_modinv_sqrt;
+
+pub mod matrix;
+
+pub mod mulmod;
+use mulmod::{P256_mulmod, P256_sqrmod};
+
+pub mod verify;
+
+mod util;
+use util::P256_negate_mod_m_if;
+pub(super) use util::{
+    P256_check_range_n, P256_check_range_p, P256_negate_mod_n_if, P256_negate_mod_p_if,
+};
+
+pub mod reduce;
+
+use add_sub::{P256_addmod, P256_submod};
+
+/// The order of the P256 curve.
+pub(crate) static P256_ORDER: [u32; 9] = [
+    0xFC632551, 0xF3B9CAC2, 0xA7179E84, 0xBCE6FAAD, 0xFFFFFFFF, 0xFFFFFFFF, 0, 0xFFFFFFFF, 0,
+];
+
+/// The `b` coefficient of the P256 curve, in montgomery form.
+pub(crate) static P256_B: [u32; 8] = [
+    0x29c4bddf, 0xd89cdf62, 0x78843090, 0xacf005cd, 0xf7212ed6, 0xe5a220ab, 0x04874834, 0xdc30061d,
+];
+
+/// The Montgomery representation of the number `3`.
+pub(crate) static P256_THREE: [u32; 8] = [
+    0x3, 0x0, 0x0, 0xfffffffd, 0xffffffff, 0xffffffff, 0xfffffffc, 0x2,
+];
+
+/// The P256 prime.
+pub(crate) static P256_PRIME: [u32; 8] =
+    [0xffffffff, 0xffffffff, 0xffffffff, 0, 0, 0, 1, 0xffffffff];
+
+/// Compute `x^3 - 3x + b`, the right-hand side of the curve equation, for `x` in montgomery form.
+fn curve_rhs(x: &[u32; 8]) -> [u32; 8] {
+    // x^2 - 3
+    let t: [u32; 8] = P256_submod(&P256_sqrmod(x), &P256_THREE);
+    // x(x^2 - 3) + b
+    P256_addmod(&P256_mulmod(x, &t), &P256_B)
+}
+
+/// Check if a point `xy` is on the `p256` curve.
+///
+/// # SAFETY
+/// The caller must guarantee that `x` and `y` are valid for the duration of the function call.
+pub(in crate::sys) unsafe fn P256_point_is_on_curve(
+    x: *const Montgomery,
+    y: *const Montgomery,
+) -> bool {
+    // SAFETY: the caller guarantees that `x` and `y` are valid for reads.
+    let (x, y): (Montgomery, Montgomery) = unsafe { (*x, *y) };
+
+    // We verify y^2 - x(x^2 - 3) = b
+    P256_sqrmod(&y.0) == curve_rhs(&x.0)
+}
+
+/// Decompress a point.
+///
+/// Returns `true` if the input `x` and `parity` combination produced a valid point, and `false` otherwise.
+/// On success, `y` will contain the output `y` coordinate.
+///
+/// # SAFETY
+/// The caller must guarantee that `x` and `y` are valid for the duration of the
+/// function call, and that `y` is valid for writes.
+pub(in crate::sys) unsafe fn P256_decompress_point(
+    y: *mut [u32; 8],
+    x: *const [u32; 8],
+    parity: u32,
+) -> bool {
+    let mut x_mont = Montgomery::zero();
+    // SAFETY: `x_mont` is valid for writes, and the caller guarantees that
+    // `x` is valid for reads.
+    unsafe { P256_to_montgomery(&mut x_mont, x) };
+
+    let rhs: [u32; 8] = curve_rhs(&x_mont.0);
+    let y_mont = Montgomery(P256_modinv_sqrt(&rhs, 1));
+    if P256_sqrmod(&y_mont.0) != rhs {
+        return false;
+    }
+
+    let mut y_normal: [u32; 8] = [0; 8];
+    // SAFETY: `y_normal` and `y_mont` are valid for the duration of the call,
+    // and `y_normal` is valid for writes.
+    unsafe { P256_from_montgomery(&mut y_normal, &y_mont) };
+
+    let should_negate: u32 = (y_normal[0] & 1) ^ parity;
+    // SAFETY: the caller guarantees that `y` is valid for writes.
+    unsafe { *y = P256_negate_mod_m_if(&y_normal, should_negate, &P256_PRIME) };
+
+    true
+}
@@ -0,0 +1,58 @@
+use super::{P256_PRIME, mulmod::mont_mul};
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Montgomery(pub(in crate::sys) [u32; 8]);
+
+impl Montgomery {
+    /// Create a new [`Montgomery`] with the given value.
+    ///
+    /// For conversion from little-endian integers, see the
+    /// [`From`] impls for this type.
+    pub(crate) const fn new(value: [u32; 8]) -> Self {
+        Self(value)
+    }
+
+    pub const fn one() -> Self {
+        Self([1, 0, 0, 0xffffffff, 0xffffffff, 0xffffffff, 0xfffffffe, 0])
+    }
+
+    pub const fn zero() -> Self {
+        Self([0u32; _])
+    }
+}
+
+/// `R^2 mod p`, where `R = 2^256`.
+const R2_MOD_P: [u32; 8] = [
+    3, 0, 0xffffffff, 0xfffffffb, 0xfffffffe, 0xffffffff, 0xfffffffd, 4,
+];
+
+/// Convert a number from normal representation to montgomery representation.
+///
+/// The pointers `a` and `aR` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `a` and `aR` are valid for the duration of the function call,
+/// and that `a` is valid for writes.
+pub(in crate::sys) unsafe fn P256_to_montgomery(a: *mut Montgomery, aR: *const [u32; 8]) {
+    // SAFETY: the caller guarantees that `aR` is valid for reads.
+    let normal: [u32; 8] = unsafe { *aR };
+    let mont: [u32; 8] = mont_mul(&normal, &R2_MOD_P, &P256_PRIME, 1);
+    // SAFETY: the caller guarantees that `a` is valid for writes.
+    unsafe { *a = Montgomery(mont) };
+}
+
+/// Convert a number from montgomery representation to normal representation.
+///
+/// The pointers `a` and `aR` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `a` and `aR` are valid for the duration of the function call,
+/// and that `a` is valid for writes.
+pub(in crate::sys) unsafe fn P256_from_montgomery(a: *mut [u32; 8], aR: *const Montgomery) {
+    // SAFETY: the caller guarantees that `aR` is valid for reads.
+    let mont: [u32; 8] = unsafe { (*aR).0 };
+    let normal: [u32; 8] = mont_mul(&mont, &[1, 0, 0, 0, 0, 0, 0, 0], &P256_PRIME, 1);
+    // SAFETY: the caller guarantees that `a` is valid for writes.
+    unsafe { *a = normal };
+}
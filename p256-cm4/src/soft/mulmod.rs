@@ -0,0 +1,81 @@
+use super::{
+    P256_PRIME,
+    reduce::P256_reduce_mod_n_64bytes,
+    util::{mul288x288, select, sub},
+};
+
+/// For inputs `a`, `b` and an odd modulus `m`, compute `a * b * 2^-256 mod m`.
+///
+/// `m_inv` shall be `-m^-1 mod 2^32`.
+///
+/// The result is fully reduced as long as `b < m`.
+pub(super) fn mont_mul(a: &[u32; 8], b: &[u32; 8], m: &[u32; 8], m_inv: u32) -> [u32; 8] {
+    // Coarsely integrated operand scanning, one 32-bit limb of `b` at a time.
+    let mut t = [0u32; 10];
+    (0..8).for_each(|i| {
+        let mut carry: u64 = 0;
+        (0..8).for_each(|j| {
+            let x: u64 = u64::from(t[j]) + u64::from(a[j]) * u64::from(b[i]) + carry;
+            t[j] = x as u32;
+            carry = x >> 32;
+        });
+        let x: u64 = u64::from(t[8]) + carry;
+        t[8] = x as u32;
+        t[9] = (x >> 32) as u32;
+
+        let u: u32 = t[0].wrapping_mul(m_inv);
+        let x: u64 = u64::from(t[0]) + u64::from(u) * u64::from(m[0]);
+        let mut carry: u64 = x >> 32;
+        (1..8).for_each(|j| {
+            let x: u64 = u64::from(t[j]) + u64::from(u) * u64::from(m[j]) + carry;
+            t[j - 1] = x as u32;
+            carry = x >> 32;
+        });
+        let x: u64 = u64::from(t[8]) + carry;
+        t[7] = x as u32;
+        t[8] = t[9] + (x >> 32) as u32;
+    });
+
+    // The result is less than 2m, so one conditional subtraction suffices.
+    let result: [u32; 9] = core::array::from_fn(|i| t[i]);
+    let modulus: [u32; 9] = core::array::from_fn(|i| if i < 8 { m[i] } else { 0 });
+    let (reduced, borrow) = sub(&result, &modulus);
+    let result: [u32; 9] = select(&reduced, &result, borrow);
+    core::array::from_fn(|i| result[i])
+}
+
+/// For inputs `A*R mod p` and `B*R mod p`, compute `A*B*R mod p`.
+pub(super) fn P256_mulmod(a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    mont_mul(a, b, &P256_PRIME, 1)
+}
+
+/// For input `A*R mod p`, computes `A^2*R mod p`.
+pub(super) fn P256_sqrmod(a: &[u32; 8]) -> [u32; 8] {
+    P256_mulmod(a, a)
+}
+
+/// Given inputs `a` and `b`, calculate `a * b mod n`, where `n` is the P256 order.
+///
+/// `res` and `a` and/or `b` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res`, `a` and `b` are valid for the duration
+/// of the function call, and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_mul_mod_n(
+    res: *mut [u32; 8],
+    a: *const [u32; 8],
+    b: *const [u32; 8],
+) {
+    // SAFETY: the caller guarantees that `a` and `b` are valid for reads.
+    let (a, b): ([u32; 8], [u32; 8]) = unsafe { (*a, *b) };
+
+    // just reuse the 288x288-bit multiplier rather than also writing a 256x256
+    let a: [u32; 9] = core::array::from_fn(|i| if i < 8 { a[i] } else { 0 });
+    let b: [u32; 9] = core::array::from_fn(|i| if i < 8 { b[i] } else { 0 });
+    let product: [u32; 18] = mul288x288(&a, &b);
+    let product: [u32; 16] = core::array::from_fn(|i| product[i]);
+
+    // SAFETY: the caller guarantees that `res` is valid for writes, and
+    // `product` is a local value.
+    unsafe { P256_reduce_mod_n_64bytes(res, &product) };
+}
@@ -0,0 +1,58 @@
+use super::{
+    P256_ORDER,
+    util::{mul288x288, select, sub},
+};
+
+/// `floor(2^512 / n)`, where `n` is the P256 order.
+const P256_ORDER_MU: [u32; 9] = [
+    0xeedf9bfe, 0x012ffd85, 0xdf1a6c21, 0x43190552, 0xffffffff, 0xfffffffe, 0xffffffff, 0x0, 0x1,
+];
+
+/// Given 288-bit value `a` and `n`, the P256 order:
+/// 1. if `a >= n`, calculate `a - n`
+/// 2. if `a < n`, calculate `a` (no-op)
+pub(super) fn P256_reduce_mod_n_once(a: &[u32; 9]) -> [u32; 9] {
+    let (reduced, borrow) = sub(a, &P256_ORDER);
+    select(&reduced, a, borrow)
+}
+
+/// Given a number `a < 2^256`, compute `a mod n`, where `n` is the P256 order.
+///
+/// The pointers `res` and `a` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration of the function call,
+/// and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_reduce_mod_n_32bytes(res: *mut [u32; 8], a: *const [u32; 8]) {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: [u32; 8] = unsafe { *a };
+    let a: [u32; 9] = core::array::from_fn(|i| if i < 8 { a[i] } else { 0 });
+    let result: [u32; 9] = P256_reduce_mod_n_once(&a);
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = core::array::from_fn(|i| result[i]) };
+}
+
+/// Given a number `a < 2^512`, compute `a mod n`, where `n` is the P256 order,
+/// using Barrett reduction.
+///
+/// # Safety
+/// The caller must guarantee that `res` and `a` are valid for the duration of the function call,
+/// and that `res` is valid for writes.
+pub(in crate::sys) unsafe fn P256_reduce_mod_n_64bytes(res: *mut [u32; 8], a: *const [u32; 16]) {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: [u32; 16] = unsafe { *a };
+
+    // q = floor(floor(a / 2^224) * mu / 2^288), which is at most 2 less than floor(a / n)
+    let a_high: [u32; 9] = core::array::from_fn(|i| a[i + 7]);
+    let q: [u32; 18] = mul288x288(&a_high, &P256_ORDER_MU);
+    let q: [u32; 9] = core::array::from_fn(|i| q[i + 9]);
+    let qn: [u32; 18] = mul288x288(&q, &P256_ORDER);
+
+    // r = a - q * n, which fits in 288 bits and is less than 3n
+    let a_low: [u32; 9] = core::array::from_fn(|i| a[i]);
+    let (r, _) = sub(&a_low, &core::array::from_fn(|i| qn[i]));
+    let r: [u32; 9] = P256_reduce_mod_n_once(&P256_reduce_mod_n_once(&r));
+
+    // SAFETY: the caller guarantees that `res` is valid for writes.
+    unsafe { *res = core::array::from_fn(|i| r[i]) };
+}
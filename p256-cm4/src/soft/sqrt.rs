@@ -0,0 +1,36 @@
+use super::{
+    Montgomery,
+    mulmod::{P256_mulmod, P256_sqrmod},
+};
+
+/// `p - 2`, where `p` is the P256 prime.
+const P_MINUS_TWO: [u32; 8] = [0xfffffffd, 0xffffffff, 0xffffffff, 0, 0, 0, 1, 0xffffffff];
+
+/// `(p + 1) / 4`, where `p` is the P256 prime.
+const P_PLUS_ONE_DIV_FOUR: [u32; 8] = [0, 0, 0x40000000, 0, 0, 0x40000000, 0xc0000000, 0x3fffffff];
+
+/// For input `a = A * R mod p` and mode `mode`:
+/// 1. If `mode == modinv`, calculate `A^-1 * R mod p = (a/R)^-1 * R mod p = R^2 / a mod p`
+/// 2. If `mode == sqrt`, calculate `sqrt(A) * R mod p`
+///
+/// `mode` shall be `0` for modinv and `1` for sqrt.
+///
+/// Both are computed as a fixed exponentiation (`A^(p-2)` and `A^((p+1)/4)`), so the
+/// running time does not depend on `a`. If `A` has no square root, the result is
+/// the square root of `-A`.
+pub(super) fn P256_modinv_sqrt(a: &[u32; 8], mode: u32) -> [u32; 8] {
+    let exponent: &[u32; 8] = if mode == 1 {
+        &P_PLUS_ONE_DIV_FOUR
+    } else {
+        &P_MINUS_TWO
+    };
+
+    (0..256).rev().fold(Montgomery::one().0, |acc, i| {
+        let acc: [u32; 8] = P256_sqrmod(&acc);
+        if (exponent[i / 32] >> (i % 32)) & 1 == 1 {
+            P256_mulmod(&acc, a)
+        } else {
+            acc
+        }
+    })
+}
@@ -0,0 +1,163 @@
+use super::{Montgomery, P256_ORDER, P256_PRIME};
+
+/// Compute `a + b`, returning the low `N` limbs of the sum and the carry (0 or 1).
+#[inline(always)]
+pub(super) fn add<const N: usize>(a: &[u32; N], b: &[u32; N]) -> ([u32; N], u32) {
+    let mut out = [0u32; N];
+    let mut carry: u64 = 0;
+    (0..N).for_each(|i| {
+        let t: u64 = u64::from(a[i]) + u64::from(b[i]) + carry;
+        out[i] = t as u32;
+        carry = t >> 32;
+    });
+    (out, carry as u32)
+}
+
+/// Compute `a - b`, returning the low `N` limbs of the difference and the borrow (0 or 1).
+#[inline(always)]
+pub(super) fn sub<const N: usize>(a: &[u32; N], b: &[u32; N]) -> ([u32; N], u32) {
+    let mut out = [0u32; N];
+    let mut borrow: u32 = 0;
+    (0..N).for_each(|i| {
+        let (t, b1) = a[i].overflowing_sub(b[i]);
+        let (t, b2) = t.overflowing_sub(borrow);
+        out[i] = t;
+        borrow = (b1 | b2) as u32;
+    });
+    (out, borrow)
+}
+
+/// Select `b` if `choice == 1` and `a` if `choice == 0`, in constant time.
+#[inline(always)]
+pub(super) fn select<const N: usize>(a: &[u32; N], b: &[u32; N], choice: u32) -> [u32; N] {
+    let mask: u32 = choice.wrapping_neg();
+    core::array::from_fn(|i| a[i] ^ ((a[i] ^ b[i]) & mask))
+}
+
+/// Returns `1` if all limbs of `a` are zero, and `0` otherwise.
+#[inline(always)]
+pub(super) fn is_zero<const N: usize>(a: &[u32; N]) -> u32 {
+    let acc: u32 = a.iter().fold(0, |acc, &limb| acc | limb);
+    ((acc | acc.wrapping_neg()) >> 31) ^ 1
+}
+
+/// Compute `a + b mod m`, assuming that `a, b < m`.
+#[inline(always)]
+pub(super) fn add_mod(a: &[u32; 8], b: &[u32; 8], m: &[u32; 8]) -> [u32; 8] {
+    let (sum, carry) = add(a, b);
+    let (reduced, borrow) = sub(&sum, m);
+    select(&sum, &reduced, carry | (borrow ^ 1))
+}
+
+/// Compute `a - b mod m`, assuming that `a, b < m`.
+#[inline(always)]
+pub(super) fn sub_mod(a: &[u32; 8], b: &[u32; 8], m: &[u32; 8]) -> [u32; 8] {
+    let (diff, borrow) = sub(a, b);
+    let (wrapped, _) = add(&diff, m);
+    select(&diff, &wrapped, borrow)
+}
+
+/// Given inputs `a`, `should_negate` and `m`:
+/// 1. If `should_negate == 1`, compute `m - a`.
+/// 2. Else, copy `a`.
+#[inline(always)]
+pub(super) fn P256_negate_mod_m_if(a: &[u32; 8], should_negate: u32, m: &[u32; 8]) -> [u32; 8] {
+    let (negated, _) = sub(m, a);
+    select(a, &negated, should_negate & 1)
+}
+
+/// The P256 order, without the trailing zero limb.
+#[inline(always)]
+pub(super) fn order() -> [u32; 8] {
+    core::array::from_fn(|i| P256_ORDER[i])
+}
+
+/// Given two 288 bit numbers `a` and `b`, calculate `a * b`.
+pub(super) fn mul288x288(a: &[u32; 9], b: &[u32; 9]) -> [u32; 18] {
+    let mut out = [0u32; 18];
+    (0..9).for_each(|i| {
+        let mut carry: u64 = 0;
+        (0..9).for_each(|j| {
+            let t: u64 = u64::from(out[i + j]) + u64::from(a[i]) * u64::from(b[j]) + carry;
+            out[i + j] = t as u32;
+            carry = t >> 32;
+        });
+        out[i + 9] = carry as u32;
+    });
+    out
+}
+
+/// Given a jacobian point `b` (with integers in montgomery form), return `-b` if
+/// `negate_y == true`, otherwise `b`.
+pub(super) fn add_sub_helper(b: &[[u32; 8]; 3], negate_y: bool) -> [[u32; 8]; 3] {
+    // note that Y is never 0 for a valid point
+    [
+        b[0],
+        P256_negate_mod_m_if(&b[1], negate_y as u32, &P256_PRIME),
+        b[2],
+    ]
+}
+
+/// Given a number `a`, validate that it is in the range `[1, n-1]` where `n` is the P256 order.
+///
+/// # Safety
+/// The caller must guarantee that `a` is valid for the duration of the function call.
+pub(in crate::sys) unsafe fn P256_check_range_n(a: *const [u32; 8]) -> bool {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: [u32; 8] = unsafe { *a };
+    let (_, borrow) = sub(&a, &order());
+    (is_zero(&a) ^ 1) & borrow == 1
+}
+
+/// Given a number `a`, compute `0 <= a <= p - 1`, where `p` is the P256-prime.
+///
+/// # Safety
+/// The caller must guarantee that `a` is valid for the duration of the function call.
+pub(in crate::sys) unsafe fn P256_check_range_p(a: *const [u32; 8]) -> bool {
+    // SAFETY: the caller guarantees that `a` is valid for reads.
+    let a: [u32; 8] = unsafe { *a };
+    let (_, borrow) = sub(&a, &P256_PRIME);
+    borrow == 1
+}
+
+/// Given inputs `a` and `should_negate`:
+/// 1. If `should_negate == 1`, compute `n - a` where `n` is the P256 order.
+/// 2. Else, copy `a`.
+///
+/// The pointers `out` and `inn` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `out` and `inn` are valid for the duration of the function
+/// call, and that `out` is valid for writes.
+pub(in crate::sys) unsafe fn P256_negate_mod_n_if(
+    out: *mut [u32; 8],
+    inn: *const [u32; 8],
+    should_negate: u32,
+) {
+    // SAFETY: the caller guarantees that `inn` is valid for reads.
+    let a: [u32; 8] = unsafe { *inn };
+    let result: [u32; 8] = P256_negate_mod_m_if(&a, should_negate, &order());
+    // SAFETY: the caller guarantees that `out` is valid for writes.
+    unsafe { *out = result };
+}
+
+/// Given inputs `a` and `should_negate`:
+/// 1. If `should_negate == 1`, compute `p - a` where `p` is the P256 prime.
+/// 2. Else, copy `a`.
+///
+/// The pointers `out` and `inn` may overlap.
+///
+/// # Safety
+/// The caller must guarantee that `out` and `inn` are valid for the duration of the function
+/// call, and that `out` is valid for writes.
+pub(in crate::sys) unsafe fn P256_negate_mod_p_if(
+    out: *mut Montgomery,
+    inn: *const Montgomery,
+    should_negate: u32,
+) {
+    // SAFETY: the caller guarantees that `inn` is valid for reads.
+    let a: [u32; 8] = unsafe { (*inn).0 };
+    let result: [u32; 8] = P256_negate_mod_m_if(&a, should_negate, &P256_PRIME);
+    // SAFETY: the caller guarantees that `out` is valid for writes.
+    unsafe { *out = Montgomery(result) };
+}
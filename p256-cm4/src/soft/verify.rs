@@ -0,0 +1,59 @@
+use super::{
+    Montgomery, P256_PRIME,
+    montgomery::P256_to_montgomery,
+    mulmod::{P256_mulmod, P256_sqrmod},
+    util::{add, is_zero, order, sub},
+};
+
+/// Perform the last step of verifying a P256 signature.
+///
+/// This function checks that `r === x (mod n)`.
+///
+/// `x` shall contain the result of performing a double scalarmult in jacobian form.
+///
+/// # Safety
+/// The caller must guarantee that `r` and `x` are valid for the duration of the function
+/// call.
+pub(in crate::sys) unsafe fn P256_verify_last_step(
+    r: *const [u32; 8],
+    x: *const [Montgomery; 3],
+) -> bool {
+    // SAFETY: the caller guarantees that `r` and `x` are valid for reads.
+    let (r, [x, _, z]): ([u32; 8], [Montgomery; 3]) = unsafe { (*r, *x) };
+
+    // Instead of doing an expensive field inversion and checking r = (X/Z^2 % p) (mod n),
+    // accept the signature iff r*Z^2 % p = X OR (r+n<p AND (r+n)*Z^2 % p = X).
+    // See the assembly implementation for a proof that this is correct.
+
+    // Calculate Z^2, if it is 0 reject
+    let z2: [u32; 8] = P256_sqrmod(&z.0);
+    if is_zero(&z2) == 1 {
+        return false;
+    }
+
+    let matches = |r: &[u32; 8]| {
+        let mut r_mont = Montgomery::zero();
+        // SAFETY: `r_mont` and `r` are valid for the duration of the call,
+        // and `r_mont` is valid for writes.
+        unsafe { P256_to_montgomery(&mut r_mont, r) };
+        P256_mulmod(&r_mont.0, &z2) == x.0
+    };
+
+    if matches(&r) {
+        return true;
+    }
+
+    // The check may fail if r < p-n, so also check for r' = r+n
+    let (r_plus_n, carry) = add(&r, &order());
+    // reject if r+n >= 2^256 (which is >= p)
+    if carry == 1 {
+        return false;
+    }
+    // reject if r+n >= p
+    let (_, borrow) = sub(&r_plus_n, &P256_PRIME);
+    if borrow == 0 {
+        return false;
+    }
+
+    matches(&r_plus_n)
+}
@@ -2,6 +2,14 @@
 #[cfg(target_arch = "arm")]
 mod asm;
 
+// On other architectures the assembly is replaced by a portable rust implementation
+// with the same interface.
+#[path = "./soft/mod.rs"]
+#[cfg(not(target_arch = "arm"))]
+mod soft;
+#[cfg(not(target_arch = "arm"))]
+use soft as asm;
+
 pub(crate) use asm::P256_ORDER;
 
 pub use asm::montgomery::Montgomery;
@@ -206,6 +214,15 @@ pub fn reduce_mod_n_32bytes_in_place(op: &mut [u32; 8]) {
     unsafe { asm::reduce::P256_reduce_mod_n_32bytes(op, op) };
 }
 
+/// Calculate `res = a mod n` for the 512-bit little-endian integer `a`, where `n` is
+/// the `p256` order.
+#[inline(always)]
+pub fn reduce_mod_n_64bytes(res: &mut [u32; 8], a: &[u32; 16]) {
+    // SAFETY: `res` and `a` are valid for the duration of the function
+    // call, and `res` is valid for writes.
+    unsafe { asm::reduce::P256_reduce_mod_n_64bytes(res, a) };
+}
+
 #[inline(always)]
 pub fn matrix_mul_fg_9(a: u32, b: u32, fg: &[FGInteger; 2], res: &mut FGInteger) {
     // SAFETY: `fg` and `res` are valid for the duration of the function
@@ -222,3 +239,56 @@ pub fn matrix_mul_mod_n(a: u32, b: u32, xy: &[XYInteger; 2], out: &mut XYInteger
     // `out` is valid for writes.
     unsafe { asm::matrix::P256_matrix_mul_mod_n(a, b, xy, out) }
 }
+
+/// Calculate `res = a * b mod p`, for `a` and `b` in montgomery form.
+#[inline(always)]
+pub fn mul_mod_p(res: &mut Montgomery, a: &Montgomery, b: &Montgomery) {
+    // SAFETY: `res`, `a` and `b` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_mul_mod_p(res, a, b) }
+}
+
+/// Calculate `res = a^2 mod p`, for `a` in montgomery form.
+#[inline(always)]
+pub fn sqr_mod_p(res: &mut Montgomery, a: &Montgomery) {
+    // SAFETY: `res` and `a` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_sqr_mod_p(res, a) }
+}
+
+/// Calculate `res = a + b mod p`, for `a` and `b` in montgomery form.
+#[inline(always)]
+pub fn add_mod_p(res: &mut Montgomery, a: &Montgomery, b: &Montgomery) {
+    // SAFETY: `res`, `a` and `b` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_add_mod_p(res, a, b) }
+}
+
+/// Calculate `res = a - b mod p`, for `a` and `b` in montgomery form.
+#[inline(always)]
+pub fn sub_mod_p(res: &mut Montgomery, a: &Montgomery, b: &Montgomery) {
+    // SAFETY: `res`, `a` and `b` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_sub_mod_p(res, a, b) }
+}
+
+/// Calculate `res = a^-1 mod p`, for `a` in montgomery form.
+///
+/// The result is zero if `a` is zero.
+#[inline(always)]
+pub fn inv_mod_p(res: &mut Montgomery, a: &Montgomery) {
+    // SAFETY: `res` and `a` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_modinv_sqrt_mod_p(res, a, 0) }
+}
+
+/// Calculate a candidate for `res = sqrt(a) mod p`, for `a` in montgomery form.
+///
+/// If `a` is not a square the result is the square root of `-a`, so the
+/// result must be checked by squaring it.
+#[inline(always)]
+pub fn sqrt_mod_p(res: &mut Montgomery, a: &Montgomery) {
+    // SAFETY: `res` and `a` are valid for the duration of the
+    // function call, and `res` is valid for writes.
+    unsafe { asm::field::P256_modinv_sqrt_mod_p(res, a, 1) }
+}
@@ -0,0 +1,537 @@
+//! Typed wrappers around the raw limb based API.
+//!
+//! All types store integers as little-endian `[u32; 8]` limbs, the same as the functions in the
+//! crate root, and can only be constructed from values that passed validation.
+
+#[cfg(feature = "cose")]
+use crate::{
+    COSE_KEY_LEN, cose_sign1_len, point_to_cose_key, try_cose_key_to_point, try_cose_sign1,
+    try_cose_sign1_verify,
+};
+use crate::{
+    DerSignature, Error, PKCS8_PRIVATE_KEY_DER_LEN, PreparedPublicKey, SEC1_PRIVATE_KEY_DER_LEN,
+    SPKI_COMPRESSED_DER_LEN, SPKI_UNCOMPRESSED_DER_LEN, check_range_n, check_range_p,
+    convert_endianness, ecdh_calc_shared_secret, keygen, point_to_octet_string_compressed,
+    point_to_octet_string_uncompressed, point_to_spki_der_compressed,
+    point_to_spki_der_uncompressed, private_key_to_pkcs8_der, private_key_to_sec1_der,
+    sys::Montgomery, sys::point_is_on_curve, try_octet_string_to_point,
+    try_private_key_from_pkcs8_der, try_private_key_from_sec1_der, try_sign,
+    try_signature_from_der, try_spki_der_to_point, u32x8_to_u8x32, u32x8_to_u8x32_mut, verify,
+};
+#[cfg(feature = "jwk")]
+use crate::{
+    PRIVATE_KEY_JWK_LEN, PUBLIC_KEY_JWK_LEN, point_to_jwk, private_key_to_jwk, try_jwk_to_point,
+    try_private_key_from_jwk,
+};
+#[cfg(feature = "pem")]
+use crate::{
+    SPKI_COMPRESSED_PEM_LEN, SPKI_UNCOMPRESSED_PEM_LEN, point_to_spki_pem_compressed,
+    point_to_spki_pem_uncompressed, try_spki_pem_to_point,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub(crate) fn from_be_bytes(bytes: &[u8; 32]) -> [u32; 8] {
+    let mut limbs: [u32; 8] = [0; 8];
+    convert_endianness(u32x8_to_u8x32_mut(&mut limbs), bytes);
+    limbs
+}
+
+pub(crate) fn to_be_bytes(limbs: &[u32; 8]) -> [u8; 32] {
+    let mut bytes: [u8; 32] = [0; 32];
+    convert_endianness(&mut bytes, u32x8_to_u8x32(limbs));
+    bytes
+}
+
+/// A private key, an integer in the range `1..=n-1`.
+///
+/// Only use a key for either ECDSA or ECDH, not both, and don't use it for any other purposes.
+///
+/// The limbs are zeroized when the key is dropped.
+#[derive(Clone)]
+pub struct SecretKey([u32; 8]);
+
+impl SecretKey {
+    /// Create a secret key from little-endian limbs.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the value is not in the range `1..=n-1`.
+    pub fn from_limbs(limbs: &[u32; 8]) -> Result<Self, Error> {
+        if check_range_n(limbs) {
+            Ok(Self(*limbs))
+        } else {
+            Err(Error::ScalarOutOfRange)
+        }
+    }
+
+    /// Create a secret key from a big-endian integer.
+    ///
+    /// Returns [`Error::ScalarOutOfRange`] if the value is not in the range `1..=n-1`.
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        Self::from_limbs(&from_be_bytes(bytes))
+    }
+
+    /// Generate a secret key with a cryptographically secure random number generator.
+    ///
+    /// See [`generate_keypair`](crate::generate_keypair).
+    #[cfg(feature = "rand_core")]
+    pub fn random(rng: &mut impl rand_core::CryptoRngCore) -> Self {
+        let mut limbs: [u32; 8] = [0; 8];
+        crate::rng::random_scalar(&mut limbs, rng);
+        Self(limbs)
+    }
+
+    /// Decode a secret key from a SEC1 `ECPrivateKey`.
+    ///
+    /// See [`private_key_from_sec1_der`](crate::private_key_from_sec1_der).
+    pub fn from_sec1_der(der: &[u8]) -> Result<Self, Error> {
+        let mut limbs: [u32; 8] = [0; 8];
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_private_key_from_sec1_der(&mut limbs, &mut x, &mut y, der)?;
+        Ok(Self(limbs))
+    }
+
+    /// Decode a secret key from a PKCS#8 `PrivateKeyInfo`.
+    ///
+    /// See [`private_key_from_pkcs8_der`](crate::private_key_from_pkcs8_der).
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, Error> {
+        let mut limbs: [u32; 8] = [0; 8];
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_private_key_from_pkcs8_der(&mut limbs, &mut x, &mut y, der)?;
+        Ok(Self(limbs))
+    }
+
+    /// Decode a secret key from a JWK.
+    ///
+    /// See [`private_key_from_jwk`](crate::private_key_from_jwk).
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(jwk: &[u8]) -> Result<Self, Error> {
+        let mut limbs: [u32; 8] = [0; 8];
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_private_key_from_jwk(&mut limbs, &mut x, &mut y, jwk)?;
+        Ok(Self(limbs))
+    }
+
+    /// SEC1 `ECPrivateKey` encoding, with the curve parameters and public key.
+    pub fn to_sec1_der(&self) -> [u8; SEC1_PRIVATE_KEY_DER_LEN] {
+        let mut out: [u8; SEC1_PRIVATE_KEY_DER_LEN] = [0; SEC1_PRIVATE_KEY_DER_LEN];
+        // cannot fail, the secret key is in range
+        let _ = private_key_to_sec1_der(&mut out, &self.0);
+        out
+    }
+
+    /// PKCS#8 `PrivateKeyInfo` encoding, with the public key.
+    pub fn to_pkcs8_der(&self) -> [u8; PKCS8_PRIVATE_KEY_DER_LEN] {
+        let mut out: [u8; PKCS8_PRIVATE_KEY_DER_LEN] = [0; PKCS8_PRIVATE_KEY_DER_LEN];
+        // cannot fail, the secret key is in range
+        let _ = private_key_to_pkcs8_der(&mut out, &self.0);
+        out
+    }
+
+    /// JWK encoding, with the public key.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> [u8; PRIVATE_KEY_JWK_LEN] {
+        let mut out: [u8; PRIVATE_KEY_JWK_LEN] = [0; PRIVATE_KEY_JWK_LEN];
+        // cannot fail, the secret key is in range
+        let _ = private_key_to_jwk(&mut out, &self.0);
+        out
+    }
+
+    /// The secret key as little-endian limbs.
+    pub fn as_limbs(&self) -> &[u32; 8] {
+        &self.0
+    }
+
+    /// The secret key as a big-endian integer.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        to_be_bytes(&self.0)
+    }
+
+    /// Calculate the public key belonging to this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        // cannot fail, the secret key is in range
+        let _ = keygen(&mut x, &mut y, &self.0);
+        PublicKey { x, y }
+    }
+
+    /// Create an ECDSA signature of a message hash.
+    ///
+    /// See [`sign`](crate::sign) for the requirements on `k`.
+    ///
+    /// Fails if `k` is not in the range `1..=n-1`, or in the rare case that `k` results
+    /// in an invalid signature. Sign again with a new `k` in that case.
+    pub fn sign(&self, hash: &[u8], k: &[u32; 8]) -> Result<Signature, Error> {
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        try_sign(&mut r, &mut s, hash, &self.0, k)?;
+        Ok(Signature { r, s })
+    }
+
+    /// Create a tagged `COSE_Sign1` message of a payload, signed with ES256, returns the length
+    /// written to `out`.
+    ///
+    /// See [`cose_sign1`](crate::cose_sign1).
+    #[cfg(feature = "cose")]
+    pub fn cose_sign1(
+        &self,
+        out: &mut [u8],
+        payload: &[u8],
+        external_aad: &[u8],
+        k: &[u32; 8],
+    ) -> Result<usize, Error> {
+        try_cose_sign1(out, payload, external_aad, &self.0, k)?;
+        Ok(cose_sign1_len(payload.len()))
+    }
+
+    /// Create an ECDSA signature of a SHA-256 message hash, with `k` derived according to
+    /// RFC 6979.
+    ///
+    /// See [`sign_deterministic`](crate::sign_deterministic).
+    #[cfg(feature = "rfc6979")]
+    pub fn sign_deterministic(&self, hash: &[u8]) -> Signature {
+        self.sign_deterministic_with_digest::<sha2::Sha256>(hash)
+    }
+
+    /// Create an ECDSA signature of a message hash, with `k` derived according to RFC 6979
+    /// using the HMAC of the hash function `D`.
+    ///
+    /// See [`try_sign_deterministic_with_digest`](crate::try_sign_deterministic_with_digest).
+    #[cfg(feature = "rfc6979")]
+    pub fn sign_deterministic_with_digest<D>(&self, hash: &[u8]) -> Signature
+    where
+        D: sha2::digest::Digest
+            + sha2::digest::FixedOutputReset
+            + sha2::digest::core_api::BlockSizeUser,
+    {
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        // cannot fail, the secret key is in range
+        let _ = crate::try_sign_deterministic_with_digest::<D>(&mut r, &mut s, hash, &self.0);
+        Signature { r, s }
+    }
+
+    /// Create an ECDSA signature of a SHA-256 message hash, with `k` derived according to
+    /// RFC 6979 with additional randomness from `rng`.
+    ///
+    /// See [`sign_hedged`](crate::sign_hedged).
+    #[cfg(all(feature = "rfc6979", feature = "rand_core"))]
+    pub fn sign_hedged(&self, hash: &[u8], rng: &mut impl rand_core::CryptoRngCore) -> Signature {
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        // cannot fail, the secret key is in range
+        let _ = crate::try_sign_hedged(&mut r, &mut s, hash, &self.0, rng);
+        Signature { r, s }
+    }
+
+    /// Create an ECDSA signature of a message hash, with a random `k` drawn from `rng`.
+    ///
+    /// See [`sign_with_rng`](crate::sign_with_rng).
+    #[cfg(feature = "rand_core")]
+    pub fn sign_with_rng(&self, hash: &[u8], rng: &mut impl rand_core::CryptoRngCore) -> Signature {
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        // cannot fail, the secret key is in range
+        let _ = crate::try_sign_with_rng(&mut r, &mut s, hash, &self.0, rng);
+        Signature { r, s }
+    }
+
+    /// Calculate the ECDH shared secret with the public key of the other party.
+    pub fn diffie_hellman(&self, others_public_key: &PublicKey) -> SharedSecret {
+        let mut shared_secret: [u8; 32] = [0; 32];
+        // cannot fail, the public key is on the curve
+        let _ = ecdh_calc_shared_secret(
+            &mut shared_secret,
+            &self.0,
+            &others_public_key.x,
+            &others_public_key.y,
+        );
+        SharedSecret(shared_secret)
+    }
+}
+
+impl core::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecretKey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+/// A public key, a point on the curve in affine coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    x: [u32; 8],
+    y: [u32; 8],
+}
+
+impl PublicKey {
+    /// Create a public key from affine coordinates as little-endian limbs.
+    ///
+    /// Fails if a coordinate is not reduced, or if the point is not on the curve.
+    pub fn from_affine(x: &[u32; 8], y: &[u32; 8]) -> Result<Self, Error> {
+        if !check_range_p(x) || !check_range_p(y) {
+            Err(Error::CoordinateOutOfRange)
+        } else if !point_is_on_curve(&Montgomery::from(*x), &Montgomery::from(*y)) {
+            Err(Error::NotOnCurve)
+        } else {
+            Ok(Self { x: *x, y: *y })
+        }
+    }
+
+    /// Decode a public key from a SEC1 encoded point.
+    ///
+    /// The uncompressed, compressed and hybrid encodings are supported.
+    pub fn from_sec1_bytes(input: &[u8]) -> Result<Self, Error> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_octet_string_to_point(&mut x, &mut y, input)?;
+        Ok(Self { x, y })
+    }
+
+    /// Decode a public key from a DER `SubjectPublicKeyInfo`.
+    ///
+    /// See [`spki_der_to_point`](crate::spki_der_to_point).
+    pub fn from_spki_der(der: &[u8]) -> Result<Self, Error> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_spki_der_to_point(&mut x, &mut y, der)?;
+        Ok(Self { x, y })
+    }
+
+    /// Decode a public key from a PEM `PUBLIC KEY`.
+    ///
+    /// See [`spki_pem_to_point`](crate::spki_pem_to_point).
+    #[cfg(feature = "pem")]
+    pub fn from_spki_pem(pem: &[u8]) -> Result<Self, Error> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_spki_pem_to_point(&mut x, &mut y, pem)?;
+        Ok(Self { x, y })
+    }
+
+    /// Decode a public key from a JWK.
+    ///
+    /// See [`jwk_to_point`](crate::jwk_to_point).
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(jwk: &[u8]) -> Result<Self, Error> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_jwk_to_point(&mut x, &mut y, jwk)?;
+        Ok(Self { x, y })
+    }
+
+    /// Decode a public key from a `COSE_Key`.
+    ///
+    /// See [`cose_key_to_point`](crate::cose_key_to_point).
+    #[cfg(feature = "cose")]
+    pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        try_cose_key_to_point(&mut x, &mut y, cose_key)?;
+        Ok(Self { x, y })
+    }
+
+    /// The x coordinate as little-endian limbs.
+    pub fn x(&self) -> &[u32; 8] {
+        &self.x
+    }
+
+    /// The y coordinate as little-endian limbs.
+    pub fn y(&self) -> &[u32; 8] {
+        &self.y
+    }
+
+    /// Uncompressed SEC1 encoding, `04 || Px || Py`.
+    pub fn to_uncompressed(&self) -> [u8; 65] {
+        let mut out: [u8; 65] = [0; 65];
+        point_to_octet_string_uncompressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// Compressed SEC1 encoding, `02 || Px` if Py is even and `03 || Px` if Py is odd.
+    pub fn to_compressed(&self) -> [u8; 33] {
+        let mut out: [u8; 33] = [0; 33];
+        point_to_octet_string_compressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// DER `SubjectPublicKeyInfo` encoding with an uncompressed point.
+    pub fn to_spki_der(&self) -> [u8; SPKI_UNCOMPRESSED_DER_LEN] {
+        let mut out: [u8; SPKI_UNCOMPRESSED_DER_LEN] = [0; SPKI_UNCOMPRESSED_DER_LEN];
+        point_to_spki_der_uncompressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// DER `SubjectPublicKeyInfo` encoding with a compressed point.
+    pub fn to_spki_der_compressed(&self) -> [u8; SPKI_COMPRESSED_DER_LEN] {
+        let mut out: [u8; SPKI_COMPRESSED_DER_LEN] = [0; SPKI_COMPRESSED_DER_LEN];
+        point_to_spki_der_compressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// PEM `PUBLIC KEY` encoding with an uncompressed point, with `\n` line endings.
+    #[cfg(feature = "pem")]
+    pub fn to_spki_pem(&self) -> [u8; SPKI_UNCOMPRESSED_PEM_LEN] {
+        let mut out: [u8; SPKI_UNCOMPRESSED_PEM_LEN] = [0; SPKI_UNCOMPRESSED_PEM_LEN];
+        point_to_spki_pem_uncompressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// PEM `PUBLIC KEY` encoding with a compressed point, with `\n` line endings.
+    #[cfg(feature = "pem")]
+    pub fn to_spki_pem_compressed(&self) -> [u8; SPKI_COMPRESSED_PEM_LEN] {
+        let mut out: [u8; SPKI_COMPRESSED_PEM_LEN] = [0; SPKI_COMPRESSED_PEM_LEN];
+        point_to_spki_pem_compressed(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// JWK encoding.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> [u8; PUBLIC_KEY_JWK_LEN] {
+        let mut out: [u8; PUBLIC_KEY_JWK_LEN] = [0; PUBLIC_KEY_JWK_LEN];
+        point_to_jwk(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// `COSE_Key` encoding with an uncompressed point.
+    #[cfg(feature = "cose")]
+    pub fn to_cose_key(&self) -> [u8; COSE_KEY_LEN] {
+        let mut out: [u8; COSE_KEY_LEN] = [0; COSE_KEY_LEN];
+        point_to_cose_key(&mut out, &self.x, &self.y);
+        out
+    }
+
+    /// Verify an ECDSA signature of a message hash.
+    #[must_use = "The return value indicates if the message is authentic"]
+    pub fn verify(&self, hash: &[u8], signature: &Signature) -> bool {
+        verify(&self.x, &self.y, hash, &signature.r, &signature.s)
+    }
+
+    /// Precompute a table for verifying many signatures with this key.
+    ///
+    /// See [`PreparedPublicKey`].
+    pub fn prepare(&self) -> PreparedPublicKey {
+        // cannot fail, the point was validated
+        PreparedPublicKey::new(&self.x, &self.y).unwrap()
+    }
+
+    /// Verify a `COSE_Sign1` message signed with ES256, returns the authenticated payload.
+    ///
+    /// See [`cose_sign1_verify`](crate::cose_sign1_verify).
+    #[cfg(feature = "cose")]
+    pub fn verify_cose_sign1<'a>(
+        &self,
+        cose_sign1: &'a [u8],
+        external_aad: &[u8],
+    ) -> Result<&'a [u8], Error> {
+        try_cose_sign1_verify(&self.x, &self.y, cose_sign1, external_aad)
+    }
+}
+
+/// An ECDSA signature, two integers `r` and `s` in the range `1..=n-1`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Signature {
+    r: [u32; 8],
+    s: [u32; 8],
+}
+
+impl Signature {
+    /// Create a signature from `r` and `s` as little-endian limbs.
+    ///
+    /// Returns [`Error::SignatureOutOfRange`] if either value is not in the range `1..=n-1`.
+    pub fn from_scalars(r: &[u32; 8], s: &[u32; 8]) -> Result<Self, Error> {
+        if check_range_n(r) && check_range_n(s) {
+            Ok(Self { r: *r, s: *s })
+        } else {
+            Err(Error::SignatureOutOfRange)
+        }
+    }
+
+    /// Create a signature from `r` and `s` as big-endian integers.
+    ///
+    /// Returns [`Error::SignatureOutOfRange`] if either value is not in the range `1..=n-1`.
+    pub fn from_be_bytes(r: &[u8; 32], s: &[u8; 32]) -> Result<Self, Error> {
+        Self::from_scalars(&from_be_bytes(r), &from_be_bytes(s))
+    }
+
+    /// Create a signature from the big-endian encoding `r || s`, the IEEE P1363 format used by
+    /// JOSE and COSE.
+    ///
+    /// Returns [`Error::SignatureOutOfRange`] if either value is not in the range `1..=n-1`.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, Error> {
+        let (r, s) = bytes.split_at(32);
+        // cannot fail, both halves are 32 bytes
+        Self::from_be_bytes(r.try_into().unwrap(), s.try_into().unwrap())
+    }
+
+    /// The big-endian encoding `r || s`, the IEEE P1363 format used by JOSE and COSE.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes: [u8; 64] = [0; 64];
+        bytes[..32].copy_from_slice(&to_be_bytes(&self.r));
+        bytes[32..].copy_from_slice(&to_be_bytes(&self.s));
+        bytes
+    }
+
+    /// Decode a signature from the DER encoding `SEQUENCE { INTEGER r, INTEGER s }`.
+    ///
+    /// See [`signature_from_der`](crate::signature_from_der).
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        try_signature_from_der(&mut r, &mut s, der)?;
+        Ok(Self { r, s })
+    }
+
+    /// DER encoding, `SEQUENCE { INTEGER r, INTEGER s }`.
+    pub fn to_der(&self) -> DerSignature {
+        DerSignature::new(&self.r, &self.s)
+    }
+
+    /// `r` as little-endian limbs.
+    pub fn r(&self) -> &[u32; 8] {
+        &self.r
+    }
+
+    /// `s` as little-endian limbs.
+    pub fn s(&self) -> &[u32; 8] {
+        &self.s
+    }
+}
+
+/// An ECDH shared secret, the big-endian x coordinate of the shared point.
+///
+/// This is not uniformly random and should be passed through a key derivation function.
+///
+/// The bytes are zeroized when the shared secret is dropped.
+#[derive(Clone)]
+pub struct SharedSecret([u8; 32]);
+
+impl SharedSecret {
+    /// The shared secret as a big-endian integer.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedSecret").finish_non_exhaustive()
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SharedSecret {}
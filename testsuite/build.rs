@@ -0,0 +1,337 @@
+//! Converts the test vector files in `vectors/` to rust source code.
+//!
+//! The vector files are too large to parse on the target,
+//! instead they are converted to static tables that are included by the test binaries.
+
+use serde_json::Value;
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use std::{collections::HashMap, fmt::Write as _, path::Path};
+
+const WYCHEPROOF_ECDSA: &str = "vectors/wycheproof/ecdsa_secp256r1_sha256_test.json";
+const WYCHEPROOF_ECDH: &str = "vectors/wycheproof/ecdh_secp256r1_test.json";
+
+const CAVP_SIGGEN: &str = "vectors/cavp/SigGen.txt";
+const CAVP_SIGVER: &str = "vectors/cavp/SigVer.rsp";
+const CAVP_KEYPAIR: &str = "vectors/cavp/KeyPair.rsp";
+const CAVP_CDH: &str = "vectors/cavp/KAS_ECC_CDH_PrimitiveTest.txt";
+
+// DER encoded SubjectPublicKeyInfo prefixes of P-256 keys, followed by the SEC1 encoded point
+const SPKI_UNCOMPRESSED: &str = "3059301306072a8648ce3d020106082a8648ce3d030107034200";
+const SPKI_COMPRESSED: &str = "3039301306072a8648ce3d020106082a8648ce3d030107032200";
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    println!("cargo::rerun-if-changed=vectors");
+
+    // only the binaries that need the vectors fail to build if they are missing
+    if std::env::var_os("CARGO_FEATURE_WYCHEPROOF").is_some() {
+        let wycheproof: String = match (read_json(WYCHEPROOF_ECDSA), read_json(WYCHEPROOF_ECDH)) {
+            (Some(ecdsa), Some(ecdh)) => {
+                let mut code: String = wycheproof_ecdsa(&ecdsa);
+                code.push_str(&wycheproof_ecdh(&ecdh));
+                code
+            }
+            _ => missing("wycheproof"),
+        };
+        std::fs::write(Path::new(&out_dir).join("wycheproof.rs"), wycheproof).unwrap();
+    }
+
+    if std::env::var_os("CARGO_FEATURE_CAVP").is_some() {
+        let cavp: String = match [CAVP_SIGGEN, CAVP_SIGVER, CAVP_KEYPAIR, CAVP_CDH]
+            .map(|path| std::fs::read_to_string(path).ok())
+        {
+            [Some(siggen), Some(sigver), Some(keypair), Some(cdh)] => {
+                let mut code: String = cavp_siggen(&siggen);
+                code.push_str(&cavp_sigver(&sigver));
+                code.push_str(&cavp_keypair(&keypair));
+                code.push_str(&cavp_cdh(&cdh));
+                code
+            }
+            _ => missing("cavp"),
+        };
+        std::fs::write(Path::new(&out_dir).join("cavp.rs"), cavp).unwrap();
+    }
+}
+
+fn read_json(path: &str) -> Option<Value> {
+    let contents: String = std::fs::read_to_string(path).ok()?;
+    Some(serde_json::from_str(&contents).unwrap_or_else(|e| panic!("{path}: {e}")))
+}
+
+fn missing(name: &str) -> String {
+    format!(
+        "compile_error!(\"the {name} test vectors are missing, run `testsuite/vectors/fetch.sh`\");\n"
+    )
+}
+
+fn hex(value: &Value) -> Vec<u8> {
+    hex_str(value.as_str().expect("expected a hex string"))
+}
+
+fn hex_str(s: &str) -> Vec<u8> {
+    assert!(s.len().is_multiple_of(2), "odd length hex string {s}");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Strip leading zeros from a big-endian integer and left-pad it to 32 bytes.
+///
+/// Returns `None` if the integer does not fit in 32 bytes.
+fn be_u256(bytes: &[u8]) -> Option<[u8; 32]> {
+    let start: usize = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let bytes: &[u8] = &bytes[start..];
+    let mut out = [0u8; 32];
+    out.get_mut(32usize.checked_sub(bytes.len())?..)?
+        .copy_from_slice(bytes);
+    Some(out)
+}
+
+fn bytes_literal(bytes: &[u8]) -> String {
+    let mut out = String::from("[");
+    bytes.iter().for_each(|b| write!(out, "{b:#04x},").unwrap());
+    out.push(']');
+    out
+}
+
+fn expected(test: &Value) -> &'static str {
+    match test["result"].as_str().unwrap() {
+        "valid" => "Expected::Valid",
+        "invalid" => "Expected::Invalid",
+        "acceptable" => "Expected::Acceptable",
+        other => panic!("unknown result {other}"),
+    }
+}
+
+/// Parse a strict DER `Ecdsa-Sig-Value`, returning `r` and `s` as big-endian integers.
+fn parse_der_signature(der: &[u8]) -> Option<([u8; 32], [u8; 32])> {
+    fn integer(input: &[u8]) -> Option<(&[u8], &[u8])> {
+        let (&tag, input) = input.split_first()?;
+        let (&len, input) = input.split_first()?;
+        let len: usize = usize::from(len);
+        if tag != 0x02 || len >= 0x80 || len == 0 || input.len() < len {
+            return None;
+        }
+        let (value, rest) = input.split_at(len);
+        // negative, or not minimally encoded
+        if value[0] & 0x80 != 0 || (len > 1 && value[0] == 0 && value[1] & 0x80 == 0) {
+            return None;
+        }
+        Some((value, rest))
+    }
+
+    let (&tag, input) = der.split_first()?;
+    let (&len, input) = input.split_first()?;
+    if tag != 0x30 || len >= 0x80 || usize::from(len) != input.len() {
+        return None;
+    }
+    let (r, input) = integer(input)?;
+    let (s, input) = integer(input)?;
+    if !input.is_empty() {
+        return None;
+    }
+    Some((be_u256(r)?, be_u256(s)?))
+}
+
+fn wycheproof_ecdsa(json: &Value) -> String {
+    let mut keys = String::new();
+    let mut tests = String::new();
+    json["testGroups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .for_each(|(key_idx, group)| {
+            let key: &Value = group.get("publicKey").unwrap_or(&group["key"]);
+            writeln!(keys, "    {},", bytes_literal(&hex(&key["uncompressed"]))).unwrap();
+
+            group["tests"].as_array().unwrap().iter().for_each(|test| {
+                let hash: [u8; 32] = Sha256::digest(hex(&test["msg"])).into();
+                let signature: String = match parse_der_signature(&hex(&test["sig"])) {
+                    Some((r, s)) => format!("Some(({}, {}))", bytes_literal(&r), bytes_literal(&s)),
+                    None => "None".to_string(),
+                };
+                writeln!(
+                    tests,
+                    "    EcdsaTest {{ tc_id: {}, key: {key_idx}, hash: {}, signature: {signature}, result: {} }},",
+                    test["tcId"],
+                    bytes_literal(&hash),
+                    expected(test),
+                )
+                .unwrap();
+            });
+        });
+    format!(
+        "static ECDSA_KEYS: &[[u8; 65]] = &[\n{keys}];\n\nstatic ECDSA_TESTS: &[EcdsaTest] = &[\n{tests}];\n\n"
+    )
+}
+
+fn wycheproof_ecdh(json: &Value) -> String {
+    let mut tests = String::new();
+    json["testGroups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|group| group["tests"].as_array().unwrap())
+        .for_each(|test| {
+            let public: String = hex(&test["public"]).iter().map(|b| format!("{b:02x}")).collect();
+            let point: String = [SPKI_UNCOMPRESSED, SPKI_COMPRESSED]
+                .iter()
+                .find_map(|prefix| public.strip_prefix(prefix))
+                .filter(|point| point.len() == 130 || point.len() == 66)
+                .map(|point| format!("Some(&{})", bytes_literal(&hex(&Value::from(point)))))
+                .unwrap_or_else(|| "None".to_string());
+            let private: [u8; 32] =
+                be_u256(&hex(&test["private"])).expect("private key larger than 32 bytes");
+            let shared: Vec<u8> = hex(&test["shared"]);
+            let shared: String = match <[u8; 32]>::try_from(shared.as_slice()) {
+                Ok(shared) => format!("Some({})", bytes_literal(&shared)),
+                Err(_) => "None".to_string(),
+            };
+            writeln!(
+                tests,
+                "    EcdhTest {{ tc_id: {}, public: {point}, private: {}, shared: {shared}, result: {} }},",
+                test["tcId"],
+                bytes_literal(&private),
+                expected(test),
+            )
+            .unwrap();
+        });
+    format!("static ECDH_TESTS: &[EcdhTest] = &[\n{tests}];\n")
+}
+
+/// A test case from a CAVP response file, with the `[...]` header of the section it is in.
+struct Record<'a> {
+    section: &'a str,
+    fields: HashMap<&'a str, &'a str>,
+}
+
+impl Record<'_> {
+    fn hex(&self, key: &str) -> Vec<u8> {
+        hex_str(self.fields[key])
+    }
+
+    fn u256(&self, key: &str) -> String {
+        bytes_literal(&be_u256(&self.hex(key)).unwrap_or_else(|| panic!("{key} is too large")))
+    }
+}
+
+/// Parse a CAVP response file, where each record begins with the `first` key.
+fn parse_rsp<'a>(contents: &'a str, first: &str) -> Vec<Record<'a>> {
+    let mut section: &str = "";
+    let mut records: Vec<Record> = Vec::new();
+    contents.lines().map(str::trim).for_each(|line| {
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = header;
+        } else if let Some((key, value)) = line.split_once(" = ") {
+            if key == first {
+                records.push(Record {
+                    section,
+                    fields: HashMap::new(),
+                });
+            }
+            if let Some(record) = records.last_mut() {
+                record.fields.insert(key, value);
+            }
+        }
+    });
+    records
+}
+
+/// Hash a message with the hash function named in a `[P-256,SHA-...]` section header.
+///
+/// Returns `None` for other curves, and panics for P-256 sections with an unknown hash function,
+/// so that no P-256 test case is skipped.
+fn cavp_digest(section: &str, msg: &[u8]) -> Option<Vec<u8>> {
+    match section.strip_prefix("P-256,")? {
+        "SHA-1" => Some(Sha1::digest(msg).to_vec()),
+        "SHA-224" => Some(Sha224::digest(msg).to_vec()),
+        "SHA-256" => Some(Sha256::digest(msg).to_vec()),
+        "SHA-384" => Some(Sha384::digest(msg).to_vec()),
+        "SHA-512" => Some(Sha512::digest(msg).to_vec()),
+        other => panic!("unknown hash function {other} in [{section}]"),
+    }
+}
+
+fn cavp_siggen(contents: &str) -> String {
+    let mut tests = String::new();
+    parse_rsp(contents, "Msg").iter().for_each(|record| {
+        if let Some(hash) = cavp_digest(record.section, &record.hex("Msg")) {
+            writeln!(
+                tests,
+                "    SigGenTest {{ section: {:?}, hash: &{}, d: {}, k: {}, r: {}, s: {} }},",
+                record.section,
+                bytes_literal(&hash),
+                record.u256("d"),
+                record.u256("k"),
+                record.u256("R"),
+                record.u256("S"),
+            )
+            .unwrap();
+        }
+    });
+    format!("static SIGGEN_TESTS: &[SigGenTest] = &[\n{tests}];\n\n")
+}
+
+fn cavp_sigver(contents: &str) -> String {
+    let mut tests = String::new();
+    parse_rsp(contents, "Msg").iter().for_each(|record| {
+        if let Some(hash) = cavp_digest(record.section, &record.hex("Msg")) {
+            writeln!(
+                tests,
+                "    SigVerTest {{ section: {:?}, hash: &{}, qx: {}, qy: {}, r: {}, s: {}, pass: {} }},",
+                record.section,
+                bytes_literal(&hash),
+                record.u256("Qx"),
+                record.u256("Qy"),
+                record.u256("R"),
+                record.u256("S"),
+                record.fields["Result"].starts_with('P'),
+            )
+            .unwrap();
+        }
+    });
+    format!("static SIGVER_TESTS: &[SigVerTest] = &[\n{tests}];\n\n")
+}
+
+fn cavp_keypair(contents: &str) -> String {
+    let mut tests = String::new();
+    parse_rsp(contents, "d")
+        .iter()
+        .filter(|record| record.section == "P-256")
+        .for_each(|record| {
+            writeln!(
+                tests,
+                "    KeyPairTest {{ d: {}, qx: {}, qy: {} }},",
+                record.u256("d"),
+                record.u256("Qx"),
+                record.u256("Qy"),
+            )
+            .unwrap();
+        });
+    format!("static KEYPAIR_TESTS: &[KeyPairTest] = &[\n{tests}];\n\n")
+}
+
+fn cavp_cdh(contents: &str) -> String {
+    let mut tests = String::new();
+    parse_rsp(contents, "COUNT")
+        .iter()
+        .filter(|record| record.section == "P-256")
+        .for_each(|record| {
+            writeln!(
+                tests,
+                "    CdhTest {{ count: {}, qcavs_x: {}, qcavs_y: {}, d: {}, qiut_x: {}, qiut_y: {}, z: {} }},",
+                record.fields["COUNT"],
+                record.u256("QCAVSx"),
+                record.u256("QCAVSy"),
+                record.u256("dIUT"),
+                record.u256("QIUTx"),
+                record.u256("QIUTy"),
+                record.u256("ZIUT"),
+            )
+            .unwrap();
+        });
+    format!("static CDH_TESTS: &[CdhTest] = &[\n{tests}];\n")
+}
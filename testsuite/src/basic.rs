@@ -253,6 +253,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn octet_string_to_point_short_input() {
+        use p256_cm4::octet_string_to_point;
+
+        const INPUT: [u8; 65] = [0x04; 65];
+
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+
+        // too short for any encoding, including the empty input
+        (0..33).for_each(|len| {
+            defmt::assert!(!octet_string_to_point(&mut x, &mut y, &INPUT[..len]));
+        });
+    }
+
     #[test]
     fn test_verify() {
         use p256_cm4::{convert_endianness, octet_string_to_point, verify};
@@ -290,6 +305,44 @@ mod tests {
         defmt::assert!(authentic);
     }
 
+    #[test]
+    fn prepared_verify_cycles() {
+        use p256_cm4::{PreparedPublicKey, verify};
+
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        convert_endianness(u32x8_to_u8x32_mut(&mut x), &into_bytes(CURVE_PT_X));
+        convert_endianness(u32x8_to_u8x32_mut(&mut y), &into_bytes(CURVE_PT_Y));
+
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        convert_endianness(u32x8_to_u8x32_mut(&mut r), u32x8_to_u8x32(&R_SIGN));
+        convert_endianness(u32x8_to_u8x32_mut(&mut s), u32x8_to_u8x32(&S_SIGN));
+        let hash: [u8; 32] = into_bytes(HASH);
+
+        let start: u32 = DWT::cycle_count();
+        let prepared: PreparedPublicKey = unwrap!(PreparedPublicKey::new(&x, &y));
+        let prepare_cycles: u32 = DWT::cycle_count().wrapping_sub(start);
+
+        let start: u32 = DWT::cycle_count();
+        let authentic: bool = verify(&x, &y, &hash, &r, &s);
+        let verify_cycles: u32 = DWT::cycle_count().wrapping_sub(start);
+        defmt::assert!(authentic);
+
+        let start: u32 = DWT::cycle_count();
+        let authentic: bool = prepared.verify(&hash, &r, &s);
+        let prepared_cycles: u32 = DWT::cycle_count().wrapping_sub(start);
+        defmt::assert!(authentic);
+
+        defmt::info!("Approximate cycles per PreparedPublicKey::new: {}", prepare_cycles);
+        defmt::info!(
+            "Approximate cycles per p256 verify: {}, with a PreparedPublicKey: {}",
+            verify_cycles,
+            prepared_cycles
+        );
+        defmt::assert!(prepared_cycles < verify_cycles);
+    }
+
     #[test]
     fn sec1_compressed_even_parity() {
         use p256_cm4::octet_string_to_point;
@@ -410,6 +463,61 @@ mod tests {
         defmt::assert_eq!(s, S_SIGN);
     }
 
+    #[test]
+    fn test_scalarmult_base() {
+        use p256_cm4::{convert_endianness, keygen, scalarmult_base};
+
+        let mut private_key: [u32; 8] = [0; 8];
+        convert_endianness(
+            u32x8_to_u8x32_mut(&mut private_key),
+            &into_bytes(PRIVATE_KEY),
+        );
+
+        let mut x_expected: [u32; 8] = [0; 8];
+        let mut y_expected: [u32; 8] = [0; 8];
+        convert_endianness(u32x8_to_u8x32_mut(&mut x_expected), &into_bytes(CURVE_PT_X));
+        convert_endianness(u32x8_to_u8x32_mut(&mut y_expected), &into_bytes(CURVE_PT_Y));
+
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        defmt::assert!(scalarmult_base(&mut x, &mut y, &private_key));
+        defmt::assert_eq!(x, x_expected);
+        defmt::assert_eq!(y, y_expected);
+
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        defmt::assert!(keygen(&mut x, &mut y, &private_key));
+        defmt::assert_eq!(x, x_expected);
+        defmt::assert_eq!(y, y_expected);
+
+        defmt::assert!(!scalarmult_base(&mut x, &mut y, &ZERO), "0 is not in range");
+        defmt::assert!(!keygen(&mut x, &mut y, &ZERO), "0 is not in range");
+    }
+
+    #[test]
+    fn test_scalarmult_generic() {
+        use p256_cm4::{convert_endianness, scalarmult_generic};
+
+        let mut in_x: [u32; 8] = [0; 8];
+        let mut in_y: [u32; 8] = [0; 8];
+        convert_endianness(u32x8_to_u8x32_mut(&mut in_x), &into_bytes(CURVE_PT_X));
+        convert_endianness(u32x8_to_u8x32_mut(&mut in_y), &into_bytes(CURVE_PT_Y));
+
+        // 1 * P = P
+        const SCALAR_ONE: [u32; 8] = [1, 0, 0, 0, 0, 0, 0, 0];
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+        defmt::assert!(scalarmult_generic(
+            &mut x,
+            &mut y,
+            &SCALAR_ONE,
+            &in_x,
+            &in_y
+        ));
+        defmt::assert_eq!(x, in_x);
+        defmt::assert_eq!(y, in_y);
+    }
+
     // TODO: clean up this test, these values are hard-coded from something that I know works
     #[test]
     fn test_ecdh() {
@@ -473,4 +581,210 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn typed_sign_verify() {
+        use p256_cm4::{PublicKey, SecretKey, Signature};
+
+        let secret_key: SecretKey = unwrap!(SecretKey::from_be_bytes(&into_bytes(PRIVATE_KEY)));
+        let mut k: [u32; 8] = [0; 8];
+        convert_endianness(u32x8_to_u8x32_mut(&mut k), &into_bytes(INTEGER));
+        let hash: [u8; 32] = into_bytes(HASH);
+
+        let signature: Signature = unwrap!(secret_key.sign(&hash, &k));
+        let expected: Signature = unwrap!(Signature::from_be_bytes(
+            &into_bytes(R_SIGN),
+            &into_bytes(S_SIGN)
+        ));
+        defmt::assert!(signature == expected);
+
+        let public_key: PublicKey = secret_key.public_key();
+        defmt::assert!(public_key.verify(&hash, &signature));
+        defmt::assert!(!public_key.verify(&[0; 32], &signature));
+
+        let decoded: PublicKey = unwrap!(PublicKey::from_sec1_bytes(&public_key.to_compressed()));
+        defmt::assert!(decoded == public_key);
+    }
+
+    #[test]
+    fn sign_deterministic_rfc6979() {
+        use p256_cm4::{SecretKey, Signature};
+
+        // RFC 6979 A.2.5, NIST P-256 with SHA-256
+        let secret_key: SecretKey = unwrap!(SecretKey::from_be_bytes(&hex!(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721"
+        )));
+        let expected: [(&[u8; 32], Signature); 2] = [
+            (
+                // SHA-256("sample")
+                &hex!("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf"),
+                unwrap!(Signature::from_be_bytes(
+                    &hex!("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+                    &hex!("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+                )),
+            ),
+            (
+                // SHA-256("test")
+                &hex!("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+                unwrap!(Signature::from_be_bytes(
+                    &hex!("f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367"),
+                    &hex!("019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"),
+                )),
+            ),
+        ];
+
+        expected.iter().for_each(|(hash, signature)| {
+            let start: u32 = DWT::cycle_count();
+            let result: Signature = secret_key.sign_deterministic(*hash);
+            let elapsed: u32 = DWT::cycle_count().wrapping_sub(start);
+            defmt::info!("Approximate cycles per p256 deterministic sign: {}", elapsed);
+            defmt::assert!(result == *signature);
+        });
+    }
+
+    #[test]
+    fn signature_bytes() {
+        use p256_cm4::Signature;
+
+        let signature: Signature = unwrap!(Signature::from_be_bytes(
+            &into_bytes(R_SIGN),
+            &into_bytes(S_SIGN)
+        ));
+        let bytes: [u8; 64] = signature.to_bytes();
+        defmt::assert_eq!(bytes[..32], into_bytes(R_SIGN));
+        defmt::assert_eq!(bytes[32..], into_bytes(S_SIGN));
+        defmt::assert!(unwrap!(Signature::from_bytes(&bytes)) == signature);
+        defmt::assert!(Signature::from_bytes(&[0xff; 64]).is_err());
+    }
+
+    #[test]
+    fn der_signature() {
+        use p256_cm4::{Error, Signature, signature_from_der, try_signature_from_der, verify};
+
+        let signature: Signature = unwrap!(Signature::from_be_bytes(
+            &into_bytes(R_SIGN),
+            &into_bytes(S_SIGN)
+        ));
+        let der = signature.to_der();
+        // both integers have the most significant bit set and need a leading zero
+        defmt::assert_eq!(der.as_bytes().len(), 72);
+        defmt::assert!(unwrap!(Signature::from_der(der.as_bytes())) == signature);
+
+        let mut public_key_x: [u32; 8] = [0; 8];
+        let mut public_key_y: [u32; 8] = [0; 8];
+        let mut private_key: [u32; 8] = [0; 8];
+        convert_endianness(
+            u32x8_to_u8x32_mut(&mut private_key),
+            &into_bytes(PRIVATE_KEY),
+        );
+        defmt::assert!(p256_cm4::keygen(
+            &mut public_key_x,
+            &mut public_key_y,
+            &private_key
+        ));
+
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        defmt::assert!(signature_from_der(&mut r, &mut s, der.as_bytes()));
+        defmt::assert!(verify(
+            &public_key_x,
+            &public_key_y,
+            &into_bytes(HASH),
+            &r,
+            &s
+        ));
+
+        defmt::assert_eq!(
+            try_signature_from_der(&mut r, &mut s, &der.as_bytes()[..71]),
+            Err(Error::InvalidDer)
+        );
+    }
+
+    #[test]
+    fn typed_ecdh() {
+        use p256_cm4::{PublicKey, SecretKey};
+
+        let secret_key: SecretKey = unwrap!(SecretKey::from_be_bytes(&hex!(
+            "6e0e8aee0324c7667be4f3950e9b267e1e623e4fb1a61b6e9948f87c140ad260"
+        )));
+        let public_key: PublicKey = unwrap!(PublicKey::from_sec1_bytes(&hex!(
+            "04ae981c0a88d381a88e3e9999d9feb0e068c918b9b4ff5e015f8d1be714c73cf61145b96af854c98bdd737d7b85fbce82a2e4f613ee82f4864e9bd906808c26d9"
+        )));
+        defmt::assert_eq!(
+            secret_key.diffie_hellman(&public_key).as_bytes(),
+            &hex!("82434cb56a762d1c12aedd1ac1ba61859c5124dbbff96bd08513dd3d09ba9da7")
+        );
+    }
+
+    #[test]
+    fn typed_rejects_invalid() {
+        use p256_cm4::{PublicKey, SecretKey, Signature};
+
+        defmt::assert!(SecretKey::from_limbs(&ZERO).is_err());
+        defmt::assert!(SecretKey::from_be_bytes(&[0xff; 32]).is_err());
+        defmt::assert!(Signature::from_scalars(&ZERO, &ONE).is_err());
+        defmt::assert!(Signature::from_scalars(&ONE, &ZERO).is_err());
+        defmt::assert!(PublicKey::from_affine(&ZERO, &ZERO).is_err());
+        defmt::assert!(PublicKey::from_affine(&CURVE_PT_X, &CURVE_PT_X).is_err());
+        defmt::assert!(PublicKey::from_sec1_bytes(&[0x04]).is_err());
+    }
+
+    #[test]
+    fn error_reasons() {
+        use p256_cm4::{
+            Error, SignPrecomp, try_keygen, try_octet_string_to_point, try_sign_step1,
+            try_sign_step2, try_verify,
+        };
+
+        let mut x: [u32; 8] = [0; 8];
+        let mut y: [u32; 8] = [0; 8];
+
+        defmt::assert_eq!(
+            try_octet_string_to_point(&mut x, &mut y, &[0x05; 33]),
+            Err(Error::InvalidEncoding)
+        );
+        defmt::assert_eq!(
+            try_octet_string_to_point(&mut x, &mut y, &[0x02; 3]),
+            Err(Error::InvalidEncoding)
+        );
+        defmt::assert_eq!(
+            try_octet_string_to_point(&mut x, &mut y, &[0xff; 33]),
+            Err(Error::InvalidEncoding)
+        );
+        let mut sec1: [u8; 33] = [0xff; 33];
+        sec1[0] = 0x02;
+        defmt::assert_eq!(
+            try_octet_string_to_point(&mut x, &mut y, &sec1),
+            Err(Error::CoordinateOutOfRange)
+        );
+        let mut sec1: [u8; 65] = [0; 65];
+        sec1[0] = 0x04;
+        defmt::assert_eq!(
+            try_octet_string_to_point(&mut x, &mut y, &sec1),
+            Err(Error::NotOnCurve)
+        );
+
+        defmt::assert_eq!(
+            try_keygen(&mut x, &mut y, &ZERO),
+            Err(Error::ScalarOutOfRange)
+        );
+
+        let mut precomp: SignPrecomp = SignPrecomp::default();
+        defmt::assert_eq!(
+            try_sign_step1(&mut precomp, &ZERO),
+            Err(Error::ScalarOutOfRange)
+        );
+
+        let mut r: [u32; 8] = [0; 8];
+        let mut s: [u32; 8] = [0; 8];
+        defmt::assert_eq!(
+            try_sign_step2(&mut r, &mut s, &[0; 32], &ONE, &mut precomp),
+            Err(Error::InvalidPrecomp)
+        );
+
+        defmt::assert_eq!(
+            try_verify(&CURVE_PT_X, &CURVE_PT_Y, &[0; 32], &ZERO, &ONE),
+            Err(Error::SignatureOutOfRange)
+        );
+    }
 }
@@ -0,0 +1,209 @@
+#![no_std]
+#![no_main]
+#![cfg(test)]
+
+use cortex_m::peripheral::DWT;
+use defmt::unwrap;
+
+#[cfg(not(feature = "rtt"))]
+use defmt_semihosting as _;
+
+#[cfg(feature = "rtt")]
+use defmt_rtt as _;
+
+const FREQ: u32 = 48_000_000;
+const CYC_PER_MICRO: u32 = FREQ / 1000 / 1000;
+
+// WARNING will wrap-around eventually, use this for relative timing only
+defmt::timestamp!("{=u32:us}", DWT::cycle_count() / CYC_PER_MICRO);
+
+#[panic_handler]
+fn panic_handler(info: &core::panic::PanicInfo) -> ! {
+    use cortex_m_semihosting::debug;
+
+    defmt::error!("{}", defmt::Display2Format(info));
+    debug::exit(debug::EXIT_FAILURE);
+    loop {}
+}
+
+// All integers are big-endian, as in the vector files.
+
+struct SigGenTest {
+    section: &'static str,
+    hash: &'static [u8],
+    d: [u8; 32],
+    k: [u8; 32],
+    r: [u8; 32],
+    s: [u8; 32],
+}
+
+struct SigVerTest {
+    section: &'static str,
+    hash: &'static [u8],
+    qx: [u8; 32],
+    qy: [u8; 32],
+    r: [u8; 32],
+    s: [u8; 32],
+    pass: bool,
+}
+
+struct KeyPairTest {
+    d: [u8; 32],
+    qx: [u8; 32],
+    qy: [u8; 32],
+}
+
+struct CdhTest {
+    count: u32,
+    qcavs_x: [u8; 32],
+    qcavs_y: [u8; 32],
+    d: [u8; 32],
+    qiut_x: [u8; 32],
+    qiut_y: [u8; 32],
+    z: [u8; 32],
+}
+
+include!(concat!(env!("OUT_DIR"), "/cavp.rs"));
+
+fn from_be(input: &[u8; 32]) -> [u32; 8] {
+    let mut output: [u32; 8] = [0; 8];
+    // SAFETY: [u32; 8] and [u8; 32] have the same size, and any bit pattern is valid for both.
+    p256_cm4::convert_endianness(
+        unsafe { core::mem::transmute::<&mut [u32; 8], &mut [u8; 32]>(&mut output) },
+        input,
+    );
+    output
+}
+
+/// Log the outcome of a test case, and return `true` if it failed.
+fn report(name: &str, section: &str, count: usize, pass: bool) -> bool {
+    if pass {
+        defmt::info!("{} [{}] test {=usize}: pass", name, section, count);
+    } else {
+        defmt::error!("{} [{}] test {=usize}: FAIL", name, section, count);
+    }
+    !pass
+}
+
+#[defmt_test::tests]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() {
+        let mut cp = unwrap!(cortex_m::peripheral::Peripherals::take());
+
+        cp.DCB.enable_trace();
+        cp.DWT.enable_cycle_counter();
+        cp.DWT.set_cycle_count(0);
+    }
+
+    #[test]
+    fn siggen() {
+        use p256_cm4::sign;
+
+        let failures: usize = SIGGEN_TESTS
+            .iter()
+            .enumerate()
+            .filter(|(count, test)| {
+                let mut r: [u32; 8] = [0; 8];
+                let mut s: [u32; 8] = [0; 8];
+                let ok: bool = sign(
+                    &mut r,
+                    &mut s,
+                    test.hash,
+                    &from_be(&test.d),
+                    &from_be(&test.k),
+                );
+                let pass: bool = ok && r == from_be(&test.r) && s == from_be(&test.s);
+                report("SigGen", test.section, *count, pass)
+            })
+            .count();
+
+        defmt::info!("SigGen: {=usize} tests", SIGGEN_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+
+    #[test]
+    fn sigver() {
+        use p256_cm4::verify;
+
+        let failures: usize = SIGVER_TESTS
+            .iter()
+            .enumerate()
+            .filter(|(count, test)| {
+                let authentic: bool = verify(
+                    &from_be(&test.qx),
+                    &from_be(&test.qy),
+                    test.hash,
+                    &from_be(&test.r),
+                    &from_be(&test.s),
+                );
+                report("SigVer", test.section, *count, authentic == test.pass)
+            })
+            .count();
+
+        defmt::info!("SigVer: {=usize} tests", SIGVER_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+
+    #[test]
+    fn keypair() {
+        use p256_cm4::keygen;
+
+        let failures: usize = KEYPAIR_TESTS
+            .iter()
+            .enumerate()
+            .filter(|(count, test)| {
+                let mut x: [u32; 8] = [0; 8];
+                let mut y: [u32; 8] = [0; 8];
+                let ok: bool = keygen(&mut x, &mut y, &from_be(&test.d));
+                let pass: bool = ok && x == from_be(&test.qx) && y == from_be(&test.qy);
+                report("KeyPair", "P-256", *count, pass)
+            })
+            .count();
+
+        defmt::info!("KeyPair: {=usize} tests", KEYPAIR_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+
+    #[test]
+    fn kas_ecc_cdh() {
+        use p256_cm4::{ecdh_calc_shared_secret, keygen, scalarmult_generic};
+
+        let failures: usize = CDH_TESTS
+            .iter()
+            .filter(|test| {
+                let d: [u32; 8] = from_be(&test.d);
+                let qcavs_x: [u32; 8] = from_be(&test.qcavs_x);
+                let qcavs_y: [u32; 8] = from_be(&test.qcavs_y);
+
+                let mut qiut_x: [u32; 8] = [0; 8];
+                let mut qiut_y: [u32; 8] = [0; 8];
+                let keygen_ok: bool = keygen(&mut qiut_x, &mut qiut_y, &d);
+
+                // the CDH primitive is the x coordinate of dIUT * QCAVS
+                let mut z_x: [u32; 8] = [0; 8];
+                let mut z_y: [u32; 8] = [0; 8];
+                let scalarmult_ok: bool =
+                    scalarmult_generic(&mut z_x, &mut z_y, &d, &qcavs_x, &qcavs_y);
+
+                let mut shared_secret: [u8; 32] = [0; 32];
+                let ecdh_ok: bool =
+                    ecdh_calc_shared_secret(&mut shared_secret, &d, &qcavs_x, &qcavs_y);
+
+                let pass: bool = keygen_ok
+                    && qiut_x == from_be(&test.qiut_x)
+                    && qiut_y == from_be(&test.qiut_y)
+                    && scalarmult_ok
+                    && z_x == from_be(&test.z)
+                    && ecdh_ok
+                    && shared_secret == test.z;
+                report("KAS ECC CDH", "P-256", test.count as usize, pass)
+            })
+            .count();
+
+        defmt::info!("KAS ECC CDH: {=usize} tests", CDH_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+}
@@ -0,0 +1,152 @@
+#![no_std]
+#![no_main]
+#![cfg(test)]
+
+use cortex_m::peripheral::DWT;
+use defmt::unwrap;
+
+#[cfg(not(feature = "rtt"))]
+use defmt_semihosting as _;
+
+#[cfg(feature = "rtt")]
+use defmt_rtt as _;
+
+const FREQ: u32 = 48_000_000;
+const CYC_PER_MICRO: u32 = FREQ / 1000 / 1000;
+
+// WARNING will wrap-around eventually, use this for relative timing only
+defmt::timestamp!("{=u32:us}", DWT::cycle_count() / CYC_PER_MICRO);
+
+#[panic_handler]
+fn panic_handler(info: &core::panic::PanicInfo) -> ! {
+    use cortex_m_semihosting::debug;
+
+    defmt::error!("{}", defmt::Display2Format(info));
+    debug::exit(debug::EXIT_FAILURE);
+    loop {}
+}
+
+/// Expected result of a Wycheproof test case.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    Valid,
+    Invalid,
+    /// Either result is allowed, for example for legacy encodings.
+    Acceptable,
+}
+
+struct EcdsaTest {
+    tc_id: u32,
+    /// Index into `ECDSA_KEYS`.
+    key: usize,
+    /// SHA-256 of the message.
+    hash: [u8; 32],
+    /// Big-endian `r` and `s`, or `None` if the signature is not strict DER with 256-bit integers.
+    signature: Option<([u8; 32], [u8; 32])>,
+    result: Expected,
+}
+
+struct EcdhTest {
+    tc_id: u32,
+    /// SEC1 encoded public key, or `None` if it is not a P-256 SubjectPublicKeyInfo.
+    public: Option<&'static [u8]>,
+    /// Big-endian private key.
+    private: [u8; 32],
+    shared: Option<[u8; 32]>,
+    result: Expected,
+}
+
+include!(concat!(env!("OUT_DIR"), "/wycheproof.rs"));
+
+fn from_be(input: &[u8; 32]) -> [u32; 8] {
+    let mut output: [u32; 8] = [0; 8];
+    // SAFETY: [u32; 8] and [u8; 32] have the same size, and any bit pattern is valid for both.
+    p256_cm4::convert_endianness(
+        unsafe { core::mem::transmute::<&mut [u32; 8], &mut [u8; 32]>(&mut output) },
+        input,
+    );
+    output
+}
+
+/// Log the outcome of a test case, and return `true` if it passed.
+fn report(name: &str, tc_id: u32, expected: Expected, accepted: bool, correct: bool) -> bool {
+    let pass: bool = match expected {
+        Expected::Valid => accepted && correct,
+        Expected::Invalid => !accepted,
+        Expected::Acceptable => !accepted || correct,
+    };
+    if pass {
+        defmt::info!("{} tcId {=u32}: pass", name, tc_id);
+    } else {
+        defmt::error!(
+            "{} tcId {=u32}: FAIL (accepted={=bool}, correct={=bool})",
+            name,
+            tc_id,
+            accepted,
+            correct
+        );
+    }
+    pass
+}
+
+#[defmt_test::tests]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() {
+        let mut cp = unwrap!(cortex_m::peripheral::Peripherals::take());
+
+        cp.DCB.enable_trace();
+        cp.DWT.enable_cycle_counter();
+        cp.DWT.set_cycle_count(0);
+    }
+
+    #[test]
+    fn ecdsa_secp256r1_sha256() {
+        use p256_cm4::{octet_string_to_point, verify};
+
+        let failures: usize = ECDSA_TESTS
+            .iter()
+            .filter(|test| {
+                let mut x: [u32; 8] = [0; 8];
+                let mut y: [u32; 8] = [0; 8];
+                defmt::assert!(octet_string_to_point(&mut x, &mut y, &ECDSA_KEYS[test.key]));
+
+                // signatures that cannot be decoded are rejected
+                let accepted: bool = test
+                    .signature
+                    .is_some_and(|(r, s)| verify(&x, &y, &test.hash, &from_be(&r), &from_be(&s)));
+                !report("ecdsa", test.tc_id, test.result, accepted, true)
+            })
+            .count();
+
+        defmt::info!("ecdsa: {=usize} tests", ECDSA_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+
+    #[test]
+    fn ecdh_secp256r1() {
+        use p256_cm4::{ecdh_calc_shared_secret, octet_string_to_point};
+
+        let failures: usize = ECDH_TESTS
+            .iter()
+            .filter(|test| {
+                let mut x: [u32; 8] = [0; 8];
+                let mut y: [u32; 8] = [0; 8];
+                let mut shared: [u8; 32] = [0; 32];
+
+                // public keys that cannot be decoded are rejected
+                let accepted: bool = test.public.is_some_and(|public| {
+                    octet_string_to_point(&mut x, &mut y, public)
+                        && ecdh_calc_shared_secret(&mut shared, &from_be(&test.private), &x, &y)
+                });
+                let correct: bool = test.shared == Some(shared);
+                !report("ecdh", test.tc_id, test.result, accepted, correct)
+            })
+            .count();
+
+        defmt::info!("ecdh: {=usize} tests", ECDH_TESTS.len());
+        defmt::assert_eq!(failures, 0);
+    }
+}
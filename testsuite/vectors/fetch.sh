@@ -0,0 +1,61 @@
+#!/usr/bin/env bash
+# Downloads the test vector files used by the testsuite, and checks them against the digests
+# pinned in SHA256SUMS before they are used.
+#
+# The Wycheproof files are fetched from the commit in WYCHEPROOF_REV, and the NIST CAVP archives
+# from their fixed URLs, the archives are checked before they are extracted. To update the vectors,
+# run `fetch.sh --pin <commit>` on a trusted network, which records the commit and the digests of
+# the downloaded files instead of checking them, then review and commit WYCHEPROOF_REV and
+# SHA256SUMS.
+set -euo pipefail
+
+cd "$(dirname "${BASH_SOURCE[0]}")"
+
+if [[ "${1:-}" == "--pin" ]]; then
+    if [[ ! "${2:-}" =~ ^[0-9a-f]{40}$ ]]; then
+        echo "usage: $0 --pin <full commit hash of the Wycheproof repository>" >&2
+        exit 1
+    fi
+    pin=true
+    WYCHEPROOF_REV="$2"
+elif [[ -f WYCHEPROOF_REV && -f SHA256SUMS ]]; then
+    pin=false
+    WYCHEPROOF_REV="$(<WYCHEPROOF_REV)"
+else
+    echo "the test vectors are not pinned, run \`$0 --pin <commit>\` first" >&2
+    exit 1
+fi
+
+WYCHEPROOF_URL="https://raw.githubusercontent.com/C2SP/wycheproof/${WYCHEPROOF_REV}/testvectors_v1"
+
+tmp="$(mktemp -d)"
+trap 'rm -rf "${tmp}"' EXIT
+
+# downloaded files, relative to this directory
+files=()
+
+mkdir -p "${tmp}/wycheproof"
+for file in ecdsa_secp256r1_sha256_test.json ecdh_secp256r1_test.json; do
+    curl --fail --silent --show-error --location --output "${tmp}/wycheproof/${file}" "${WYCHEPROOF_URL}/${file}"
+    files+=("wycheproof/${file}")
+done
+
+CAVP_URL="https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Algorithm-Validation-Program/documents"
+
+mkdir -p "${tmp}/cavp"
+for path in dss/186-4ecdsatestvectors.zip components/ecccdhtestvectors.zip; do
+    curl --fail --silent --show-error --location --output "${tmp}/cavp/${path##*/}" "${CAVP_URL}/${path}"
+    files+=("cavp/${path##*/}")
+done
+
+if "${pin}"; then
+    (cd "${tmp}" && sha256sum "${files[@]}") > SHA256SUMS
+    echo "${WYCHEPROOF_REV}" > WYCHEPROOF_REV
+else
+    (cd "${tmp}" && sha256sum --check --strict --quiet -) < SHA256SUMS
+fi
+
+mkdir -p wycheproof cavp
+cp "${tmp}"/wycheproof/*.json wycheproof
+unzip -o -j -q "${tmp}/cavp/186-4ecdsatestvectors.zip" SigGen.txt SigVer.rsp KeyPair.rsp -d cavp
+unzip -o -j -q "${tmp}/cavp/ecccdhtestvectors.zip" KAS_ECC_CDH_PrimitiveTest.txt -d cavp